@@ -1,35 +1,148 @@
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::{Arc, OnceLock, RwLock, mpsc};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
 
 use super::views::article_details::ArticleDetails;
-use super::views::articles_list::ArticlesList;
+use super::views::articles_list::{ArticleSaver, ArticlesList};
+use super::views::collections_popup::CollectionsPopup;
+use super::views::companies_list::CompaniesList;
+use super::views::hub_details::HubDetails;
 use super::views::hubs_list::HubsList;
+use super::views::link_router::open_link;
 use super::views::saved_articles_list::SavedArticlesList;
+use super::views::search::SearchView;
 use super::views::settings::Settings;
 
+use super::habr_client::HabrClient;
 use super::habr_client::article::ArticleData;
 use super::habr_client::hub::Hub;
 
+use super::notifications::{Notifier, spawn_poller};
+use super::storage::ArticleStorage;
+use super::storage::collections::SavedCollections;
+use super::storage::feeds::Feeds;
+use super::storage::history::ReadingHistory;
+use super::storage::subscriptions::Subscriptions;
 use super::view_stack::ViewStack;
 
+/// Requests coming to the app from outside of its window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppCommand {
+    OpenUrl(String),
+    SaveArticle(String),
+}
+
+/// Article shown on the screen, shared with the D-Bus service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadingState {
+    pub article_id: String,
+    pub title: String,
+    pub author: String,
+    /// Scrolled part of the article, from 0 to 1.
+    pub progress: f32,
+}
+
+pub type SharedReadingState = Arc<RwLock<Option<ReadingState>>>;
+
+/// Sends commands to the app from other threads and wakes it up to handle them.
+#[derive(Clone)]
+pub struct CommandSender {
+    sender: mpsc::Sender<AppCommand>,
+    egui_ctx: Arc<OnceLock<egui::Context>>,
+}
+
+impl CommandSender {
+    pub fn set_context(&self, ctx: &egui::Context) {
+        let _ = self.egui_ctx.set(ctx.clone());
+    }
+
+    pub fn send(&self, command: AppCommand) {
+        if self.sender.send(command).is_err() {
+            log::warn!("The app is closed, command is dropped");
+            return;
+        }
+        if let Some(ctx) = self.egui_ctx.get() {
+            ctx.request_repaint();
+        }
+    }
+}
+
+pub fn command_channel() -> (CommandSender, mpsc::Receiver<AppCommand>) {
+    let (sender, receiver) = mpsc::channel();
+    let sender = CommandSender {
+        sender,
+        egui_ctx: Default::default(),
+    };
+    (sender, receiver)
+}
+
+/// Connections of the app with the rest of the system.
+pub struct Integration {
+    pub commands: mpsc::Receiver<AppCommand>,
+    pub reading_state: SharedReadingState,
+    /// Not available without the session bus.
+    pub notifier: Option<Notifier>,
+}
+
 pub struct MyApp {
     pub state: Rc<RefCell<HabreState>>,
     pub view_stack: ViewStack,
+    integration: Integration,
+    /// New articles poller with its interval in minutes.
+    poller: Option<(u32, JoinHandle<()>)>,
 }
 
-impl Default for MyApp {
-    fn default() -> Self {
+impl MyApp {
+    pub fn new(integration: Integration) -> Self {
+        ArticleStorage::cleanup_partial_saves();
+
         let state = Rc::new(RefCell::new(HabreState::new()));
+        state
+            .borrow()
+            .async_handle()
+            .spawn_blocking(ArticleStorage::migrate_legacy_images);
         let article_details = Rc::new(RefCell::new(ArticleDetails::new(state.clone())));
         let articles_list = Rc::new(RefCell::new(ArticlesList::new(state.clone())));
         let saved_articles_list = Rc::new(RefCell::new(SavedArticlesList::new(state.clone())));
         let hubs_list = Rc::new(RefCell::new(HubsList::new(state.clone())));
+        let search_view = Rc::new(RefCell::new(SearchView::new(state.clone())));
+        let hub_details = Rc::new(RefCell::new(HubDetails::new(state.clone())));
+        let companies_list = Rc::new(RefCell::new(CompaniesList::new(state.clone())));
         let mut view_stack = ViewStack::new();
 
         hubs_list.borrow_mut().on_hub_selected({
+            let hub_details = hub_details.clone();
+            move |selected_hub, view_stack| {
+                hub_details.borrow_mut().set_hub(selected_hub.clone());
+                view_stack.push(hub_details.clone());
+            }
+        });
+
+        hub_details.borrow_mut().on_articles_selected({
+            let state = state.clone();
+            let articles_list = articles_list.clone();
+            move |hub, view_stack| {
+                state.borrow_mut().selected_hub = Some(hub.clone());
+                articles_list.borrow_mut().open_hub();
+                view_stack.push(articles_list.clone());
+            }
+        });
+
+        hubs_list.borrow_mut().on_my_feed_selected({
+            let articles_list = articles_list.clone();
+            move |view_stack| {
+                articles_list.borrow_mut().open_my_feed();
+                view_stack.push(articles_list.clone());
+            }
+        });
+
+        hubs_list.borrow_mut().on_feed_selected({
             let articles_list = articles_list.clone();
-            move |_selected_hub, view_stack| {
-                articles_list.borrow_mut().get_articles();
+            move |feed, view_stack| {
+                articles_list.borrow_mut().open_feed(feed);
                 view_stack.push(articles_list.clone());
             }
         });
@@ -42,6 +155,29 @@ impl Default for MyApp {
             }
         });
 
+        hubs_list.borrow_mut().on_search_selected({
+            let search_view = search_view.clone();
+            move |view_stack| {
+                view_stack.push(search_view.clone());
+            }
+        });
+
+        hubs_list.borrow_mut().on_companies_selected({
+            let companies_list = companies_list.clone();
+            move |view_stack| {
+                companies_list.borrow_mut().open();
+                view_stack.push(companies_list.clone());
+            }
+        });
+
+        search_view.borrow_mut().on_hub_selected({
+            let hub_details = hub_details.clone();
+            move |selected_hub, view_stack| {
+                hub_details.borrow_mut().set_hub(selected_hub.clone());
+                view_stack.push(hub_details.clone());
+            }
+        });
+
         saved_articles_list.borrow_mut().on_article_selected({
             let state = state.clone();
             move |article_data, view_stack| {
@@ -53,18 +189,92 @@ impl Default for MyApp {
         });
 
         hubs_list.borrow_mut().get_hubs();
+        hubs_list.borrow().refresh_feeds();
         view_stack.push(hubs_list.clone());
 
-        Self { state, view_stack }
+        Self {
+            state,
+            view_stack,
+            integration,
+            poller: None,
+        }
+    }
+
+    /// Should be called every frame before the views.
+    pub fn handle_commands(&mut self, ui: &egui::Ui) {
+        while let Ok(command) = self.integration.commands.try_recv() {
+            match command {
+                AppCommand::OpenUrl(url) => {
+                    open_link(ui, self.state.clone(), &url, &mut self.view_stack)
+                }
+                AppCommand::SaveArticle(article_id) => self.save_article(article_id),
+            }
+        }
+    }
+
+    /// Should be called every frame after the views.
+    pub fn update_reading_state(&self) {
+        *self.integration.reading_state.write().unwrap() = self.view_stack.reading_state();
+    }
+
+    /// Should be called every frame after the views.
+    pub fn popups_ui(&self, ui: &mut egui::Ui) {
+        let state = self.state.borrow();
+        state
+            .collections_popup
+            .borrow_mut()
+            .ui(ui, &state.collections);
+    }
+
+    /// Starts or stops the new articles poller following the settings.
+    pub fn update_poller(&mut self) {
+        let settings = self.state.borrow().settings.borrow().data();
+        let interval = (settings.notify_new_articles && self.integration.notifier.is_some())
+            .then_some(settings.notifications_interval);
+        if self.poller.as_ref().map(|(i, _)| *i) == interval {
+            return;
+        }
+
+        if let Some((_, poller)) = self.poller.take() {
+            poller.abort();
+        }
+        if let (Some(interval), Some(notifier)) = (interval, self.integration.notifier.clone()) {
+            let poller = spawn_poller(
+                &self.state.borrow().async_handle(),
+                Duration::from_secs(interval as u64 * 60),
+                notifier,
+            );
+            self.poller = Some((interval, poller));
+        }
+    }
+
+    fn save_article(&self, article_id: String) {
+        if ArticleStorage::is_article_saved(&article_id) {
+            log::info!("Article {} is already saved", article_id);
+            return;
+        }
+
+        let article_saver = self.state.borrow().article_saver.clone();
+        self.state.borrow().async_handle().spawn(async move {
+            match HabrClient::new().get_article(&article_id).await {
+                Ok(article) => article_saver.save_article(article),
+                Err(e) => log::error!("Failed to load article {} for saving: {}", article_id, e),
+            }
+        });
     }
 }
 
-#[derive(Debug)]
 pub struct HabreState {
     pub selected_hub: Option<Hub>,
     pub selected_article: Option<ArticleData>,
+    pub article_saver: ArticleSaver,
 
     pub settings: Rc<RefCell<Settings>>,
+    pub feeds: Rc<RefCell<Feeds>>,
+    pub history: Rc<RefCell<ReadingHistory>>,
+    pub collections: Rc<RefCell<SavedCollections>>,
+    pub collections_popup: Rc<RefCell<CollectionsPopup>>,
+    pub subscriptions: Rc<RefCell<Subscriptions>>,
     tokio_rt: tokio::runtime::Runtime,
 }
 
@@ -77,6 +287,7 @@ impl HabreState {
             .unwrap();
 
         Self {
+            article_saver: ArticleSaver::new(tokio_rt.handle().clone()),
             tokio_rt,
             selected_hub: None,
             selected_article: None,
@@ -84,6 +295,11 @@ impl HabreState {
             settings: Rc::new(RefCell::new(
                 Settings::read_from_file().unwrap_or_else(Default::default),
             )),
+            feeds: Rc::new(RefCell::new(Feeds::load())),
+            history: Rc::new(RefCell::new(ReadingHistory::load())),
+            collections: Rc::new(RefCell::new(SavedCollections::load())),
+            collections_popup: Default::default(),
+            subscriptions: Rc::new(RefCell::new(Subscriptions::load())),
         }
     }
 
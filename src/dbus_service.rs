@@ -0,0 +1,366 @@
+use std::ffi::CString;
+use std::thread;
+use std::time::Duration;
+
+use dbus::Message;
+use dbus::arg::{PropMap, RefArg, Variant};
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::RequestNameReply;
+use dbus::channel::{MatchingReceiver, Sender};
+use dbus::message::MatchRule;
+
+use crate::app::{AppCommand, CommandSender, ReadingState, SharedReadingState};
+use crate::habr_client::links::HabrLink;
+use crate::storage::ArticleStorage;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::mpsc::Receiver;
+
+    use dbus::channel::Channel;
+
+    use crate::app::command_channel;
+
+    /// Private bus of a `dbus-daemon` started for the test.
+    struct TestBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl TestBus {
+        fn start() -> Option<TestBus> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.as_mut()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some(TestBus {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+
+        fn connect(&self) -> Connection {
+            let mut channel = Channel::open_private(&self.address).unwrap();
+            channel.register().unwrap();
+            Connection::from(channel)
+        }
+    }
+
+    impl Drop for TestBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn start_service(bus: &TestBus, reading_state: SharedReadingState) -> Receiver<AppCommand> {
+        let conn = bus.connect();
+        conn.request_name(BUS_NAME, false, false, true).unwrap();
+        let (commands, receiver) = command_channel();
+        serve(conn, commands, reading_state);
+        receiver
+    }
+
+    fn error_name(error: dbus::Error) -> String {
+        error.name().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_commands() {
+        let Some(bus) = TestBus::start() else {
+            eprintln!("dbus-daemon is not available, skipping");
+            return;
+        };
+        let commands = start_service(&bus, SharedReadingState::default());
+        let client = bus.connect();
+        let proxy = client.with_proxy(BUS_NAME, OBJECT_PATH, CALL_TIMEOUT);
+        let next_command = || commands.recv_timeout(CALL_TIMEOUT).unwrap();
+
+        open_url_in_running(&client, "https://habr.com/ru/hubs/rust/").unwrap();
+        assert_eq!(
+            next_command(),
+            AppCommand::OpenUrl("https://habr.com/ru/hubs/rust/".to_string())
+        );
+
+        let () = proxy
+            .method_call(INTERFACE, "OpenArticle", ("812345",))
+            .unwrap();
+        assert_eq!(
+            next_command(),
+            AppCommand::OpenUrl("https://habr.com/ru/articles/812345/".to_string())
+        );
+
+        let () = proxy
+            .method_call(
+                INTERFACE,
+                "SaveArticle",
+                ("https://habr.com/ru/articles/812345/",),
+            )
+            .unwrap();
+        assert_eq!(
+            next_command(),
+            AppCommand::SaveArticle("812345".to_string())
+        );
+
+        let error = proxy
+            .method_call::<(), _, _, _>(
+                INTERFACE,
+                "SaveArticle",
+                ("https://habr.com/ru/hubs/rust/",),
+            )
+            .unwrap_err();
+        assert_eq!(error_name(error), "org.freedesktop.DBus.Error.InvalidArgs");
+
+        let error = proxy
+            .method_call::<(), _, _, _>(INTERFACE, "CloseApp", ())
+            .unwrap_err();
+        assert_eq!(
+            error_name(error),
+            "org.freedesktop.DBus.Error.UnknownMethod"
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let Some(bus) = TestBus::start() else {
+            eprintln!("dbus-daemon is not available, skipping");
+            return;
+        };
+        let reading_state = SharedReadingState::default();
+        let _commands = start_service(&bus, reading_state.clone());
+        let client = bus.connect();
+        let proxy = client.with_proxy(BUS_NAME, OBJECT_PATH, CALL_TIMEOUT);
+
+        let (state,): (PropMap,) = proxy.method_call(INTERFACE, "GetReadingState", ()).unwrap();
+        assert!(state.is_empty());
+
+        *reading_state.write().unwrap() = Some(ReadingState {
+            article_id: "812345".to_string(),
+            title: "Title".to_string(),
+            author: "author".to_string(),
+            progress: 0.5,
+        });
+        let (state,): (PropMap,) = proxy.method_call(INTERFACE, "GetReadingState", ()).unwrap();
+        assert_eq!(
+            dbus::arg::prop_cast::<String>(&state, "url").map(String::as_str),
+            Some("https://habr.com/ru/articles/812345/")
+        );
+        assert_eq!(dbus::arg::prop_cast::<f64>(&state, "progress"), Some(&0.5));
+
+        let (_articles,): (Vec<SavedArticleRecord>,) = proxy
+            .method_call(INTERFACE, "ListSavedArticles", ())
+            .unwrap();
+
+        let (xml,): (String,) = proxy.method_call(INTROSPECTABLE, "Introspect", ()).unwrap();
+        assert!(xml.contains("<method name=\"GetReadingState\">"));
+    }
+}
+
+pub const BUS_NAME: &str = "com.lmaxyz.Haboost";
+pub const OBJECT_PATH: &str = "/com/lmaxyz/Haboost";
+pub const INTERFACE: &str = "com.lmaxyz.Haboost";
+
+const INTROSPECTABLE: &str = "org.freedesktop.DBus.Introspectable";
+const CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+const INTROSPECTION_XML: &str = r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node>
+  <interface name="com.lmaxyz.Haboost">
+    <method name="OpenUrl">
+      <arg name="url" type="s" direction="in"/>
+    </method>
+    <method name="OpenArticle">
+      <arg name="article" type="s" direction="in"/>
+    </method>
+    <method name="SaveArticle">
+      <arg name="article" type="s" direction="in"/>
+    </method>
+    <method name="ListSavedArticles">
+      <arg name="articles" type="a(sssxd)" direction="out"/>
+    </method>
+    <method name="GetReadingState">
+      <arg name="state" type="a{sv}" direction="out"/>
+    </method>
+  </interface>
+  <interface name="org.freedesktop.DBus.Introspectable">
+    <method name="Introspect">
+      <arg name="xml" type="s" direction="out"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// Id, title, author, saving time and read progress of a saved article.
+type SavedArticleRecord = (String, String, String, i64, f64);
+
+/// Session bus connection of the app. Only the first started instance owns the bus name,
+/// the other ones pass their links to it and exit.
+pub enum Instance {
+    Primary(Connection),
+    Secondary(Connection),
+}
+
+impl Instance {
+    pub fn connect() -> Result<Instance, dbus::Error> {
+        let conn = Connection::new_session()?;
+        match conn.request_name(BUS_NAME, false, false, true)? {
+            RequestNameReply::PrimaryOwner | RequestNameReply::AlreadyOwner => {
+                Ok(Instance::Primary(conn))
+            }
+            RequestNameReply::InQueue | RequestNameReply::Exists => Ok(Instance::Secondary(conn)),
+        }
+    }
+}
+
+/// Asks the instance owning the bus name to open the url.
+pub fn open_url_in_running(conn: &Connection, url: &str) -> Result<(), dbus::Error> {
+    conn.with_proxy(BUS_NAME, OBJECT_PATH, CALL_TIMEOUT)
+        .method_call(INTERFACE, "OpenUrl", (url,))
+}
+
+/// Handles the calls of the app interface in a background thread.
+///
+/// Opening and saving are passed to the UI thread as commands, the queries are
+/// answered right here from the storage and the reading state updated by the app.
+pub fn serve(conn: Connection, commands: CommandSender, reading_state: SharedReadingState) {
+    thread::spawn(move || {
+        let rule = MatchRule::new_method_call()
+            .with_path(OBJECT_PATH)
+            .with_interface(INTERFACE);
+        conn.start_receive(
+            rule,
+            Box::new(move |msg: Message, conn: &Connection| {
+                let reply = handle_call(&msg, &commands, &reading_state);
+                send_reply(&msg, reply, conn);
+                true
+            }),
+        );
+
+        let rule = MatchRule::new_method_call()
+            .with_path(OBJECT_PATH)
+            .with_interface(INTROSPECTABLE)
+            .with_member("Introspect");
+        conn.start_receive(
+            rule,
+            Box::new(|msg: Message, conn: &Connection| {
+                let reply = msg.method_return().append1(INTROSPECTION_XML);
+                send_reply(&msg, reply, conn);
+                true
+            }),
+        );
+
+        loop {
+            if let Err(e) = conn.process(Duration::from_secs(60)) {
+                log::error!("D-Bus service stopped: {}", e);
+                break;
+            }
+        }
+    });
+}
+
+fn send_reply(msg: &Message, reply: Message, conn: &Connection) {
+    if !msg.get_no_reply() {
+        let _ = conn.send(reply);
+    }
+}
+
+fn handle_call(
+    msg: &Message,
+    commands: &CommandSender,
+    reading_state: &SharedReadingState,
+) -> Message {
+    match msg.member().as_deref() {
+        Some("OpenUrl") => match msg.read1::<&str>() {
+            Ok(url) => {
+                commands.send(AppCommand::OpenUrl(url.to_string()));
+                msg.method_return()
+            }
+            Err(e) => invalid_args(msg, &e.to_string()),
+        },
+        Some("OpenArticle") => match read_article_id(msg) {
+            Ok(article_id) => {
+                commands.send(AppCommand::OpenUrl(HabrLink::Article(article_id).url()));
+                msg.method_return()
+            }
+            Err(e) => invalid_args(msg, &e),
+        },
+        Some("SaveArticle") => match read_article_id(msg) {
+            Ok(article_id) => {
+                commands.send(AppCommand::SaveArticle(article_id));
+                msg.method_return()
+            }
+            Err(e) => invalid_args(msg, &e),
+        },
+        Some("ListSavedArticles") => msg.method_return().append1(saved_articles()),
+        Some("GetReadingState") => {
+            let props = reading_state_props(reading_state.read().unwrap().as_ref());
+            msg.method_return().append1(props)
+        }
+        _ => error_reply(
+            msg,
+            "org.freedesktop.DBus.Error.UnknownMethod",
+            "Method does not exist",
+        ),
+    }
+}
+
+/// Article id or url of an article.
+fn read_article_id(msg: &Message) -> Result<String, String> {
+    let arg = msg.read1::<&str>().map_err(|e| e.to_string())?;
+    match HabrLink::from_arg(arg) {
+        Some(HabrLink::Article(article_id)) => Ok(article_id),
+        _ => Err(format!("Not an article: {}", arg)),
+    }
+}
+
+fn saved_articles() -> Vec<SavedArticleRecord> {
+    ArticleStorage::list_saved_articles()
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.metadata.id,
+                entry.metadata.title,
+                entry.metadata.author,
+                entry.saved_at,
+                entry.read_progress as f64,
+            )
+        })
+        .collect()
+}
+
+/// Empty when no article is opened.
+fn reading_state_props(reading_state: Option<&ReadingState>) -> PropMap {
+    let mut props = PropMap::new();
+    let Some(state) = reading_state else {
+        return props;
+    };
+
+    let url = HabrLink::Article(state.article_id.clone()).url();
+    let mut insert = |key: &str, value: Box<dyn RefArg>| {
+        props.insert(key.to_string(), Variant(value));
+    };
+    insert("article_id", Box::new(state.article_id.clone()));
+    insert("title", Box::new(state.title.clone()));
+    insert("author", Box::new(state.author.clone()));
+    insert("url", Box::new(url));
+    insert("progress", Box::new(state.progress as f64));
+    props
+}
+
+fn invalid_args(msg: &Message, text: &str) -> Message {
+    error_reply(msg, "org.freedesktop.DBus.Error.InvalidArgs", text)
+}
+
+fn error_reply(msg: &Message, name: &str, text: &str) -> Message {
+    let text = CString::new(text.replace('\0', "")).unwrap_or_default();
+    msg.error(&name.into(), &text)
+}
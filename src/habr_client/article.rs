@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use serde_json;
 
@@ -64,9 +65,23 @@ mod tests {
         assert_eq!(article.statistics.comments_count, 42);
         assert_eq!(article.statistics.score, 50);
     }
+
+    #[test]
+    fn test_minutes_left() {
+        assert_eq!(minutes_left(10, 0.), 10);
+        assert_eq!(minutes_left(10, 0.42), 6);
+        assert_eq!(minutes_left(10, 0.99), 1);
+        assert_eq!(minutes_left(10, 1.), 0);
+        assert_eq!(minutes_left(0, 0.5), 0);
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Estimated reading time of the rest of the article, the started minute counts.
+pub fn minutes_left(reading_time: usize, progress: f32) -> usize {
+    (reading_time as f32 * (1. - progress.clamp(0., 1.))).ceil() as usize
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct LeadData {
     #[serde(alias = "textHtml")]
     pub description: String,
@@ -88,6 +103,13 @@ pub struct Author {
     pub(crate) avatar_url: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ArticleHub {
+    pub alias: String,
+    #[serde(rename(deserialize = "type"), default)]
+    pub hub_type: String,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Statistics {
     #[serde(alias = "commentsCount")]
@@ -102,9 +124,11 @@ pub struct ArticlePreviewResponse {
     pub id: String,
     #[serde(alias = "timePublished")]
     pub published_at: String,
-    #[serde(alias = "titleHtml")]
+    #[serde(alias = "postType", default)]
+    pub post_type: String,
+    #[serde(alias = "titleHtml", default)]
     pub title: String,
-    #[serde(rename(deserialize = "leadData"))]
+    #[serde(rename(deserialize = "leadData"), default)]
     pub lead_data: LeadData,
     pub tags: Vec<Tag>,
     pub complexity: Option<String>,
@@ -112,6 +136,8 @@ pub struct ArticlePreviewResponse {
     pub reading_time: usize,
     pub author: Option<Author>,
     pub statistics: Statistics,
+    #[serde(default)]
+    pub hubs: Vec<ArticleHub>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -126,7 +152,7 @@ pub struct ArticlesResponse {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct ArticleResponse {
-    #[serde(alias = "titleHtml")]
+    #[serde(alias = "titleHtml", default)]
     pub title: String,
     #[serde(alias = "textHtml")]
     pub text: String,
@@ -136,17 +162,50 @@ pub(crate) struct ArticleResponse {
 pub struct ArticleData {
     pub(crate) id: String,
     pub(crate) title: String,
-    #[allow(dead_code)]
     pub(crate) tags: Vec<String>,
     pub(crate) complexity: String,
     pub(crate) author: String,
     pub(crate) published_at: String,
     pub(crate) reading_time: usize,
+    #[serde(default)]
+    pub(crate) publication_type: PublicationType,
+    /// Aliases of the regular hubs of the article.
+    #[serde(default)]
+    pub(crate) hubs: Vec<String>,
+    /// Alias of the company blog the article was published in.
+    #[serde(default)]
+    pub(crate) company: Option<String>,
     pub image_url: String,
     pub score: isize,
     pub comments_count: usize,
 }
 
+impl ArticleData {
+    pub fn published_at_datetime(&self) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&self.published_at, "%d.%m.%Y %H:%M").ok()
+    }
+}
+
+/// Article with just the id for the tests, which set the other fields they check.
+#[cfg(test)]
+pub(crate) fn test_article(id: &str) -> ArticleData {
+    ArticleData {
+        id: id.to_string(),
+        title: String::new(),
+        tags: vec![],
+        complexity: String::new(),
+        author: String::new(),
+        published_at: String::new(),
+        reading_time: 0,
+        publication_type: PublicationType::Article,
+        hubs: vec![],
+        company: None,
+        image_url: String::new(),
+        score: 0,
+        comments_count: 0,
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ArticleContent {
     Image(String),
@@ -160,8 +219,79 @@ pub enum ArticleContent {
     BR,
 }
 
+impl ArticleContent {
+    /// Text of the block without formatting, list items are separated by new lines.
+    pub fn plain_text(&self) -> String {
+        match self {
+            ArticleContent::Header(_, text) | ArticleContent::Blockquote(text) => text.clone(),
+            ArticleContent::Code { content, .. } => content.clone(),
+            ArticleContent::Paragraph(texts) => texts.iter().map(|t| t.text()).collect(),
+            ArticleContent::Text(text) => text.text().to_string(),
+            ArticleContent::UnorderedList(items) | ArticleContent::OrderedList(items) => items
+                .iter()
+                .map(|item| item.plain_text())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ArticleContent::Image(_) | ArticleContent::BR => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PublicationType {
+    #[default]
+    Article,
+    News,
+    /// Short post without a title.
+    Post,
+}
+
+impl PublicationType {
+    pub const ALL: [PublicationType; 3] = [
+        PublicationType::Article,
+        PublicationType::News,
+        PublicationType::Post,
+    ];
+
+    pub fn from_post_type(post_type: &str) -> Self {
+        match post_type {
+            "news" => PublicationType::News,
+            "post" | "posts" => PublicationType::Post,
+            _ => PublicationType::Article,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            PublicationType::Article => "Статьи",
+            PublicationType::News => "Новости",
+            PublicationType::Post => "Посты",
+        }
+    }
+
+    /// Label shown on cards and in the article header.
+    pub fn badge(&self) -> Option<&'static str> {
+        match self {
+            PublicationType::Article => None,
+            PublicationType::News => Some("Новость"),
+            PublicationType::Post => Some("Пост"),
+        }
+    }
+
+    /// Parameter switching the feed from articles to other publications.
+    pub fn feed_param(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            PublicationType::Article => None,
+            PublicationType::News => Some(("news", "true")),
+            PublicationType::Post => Some(("posts", "true")),
+        }
+    }
+}
+
 type RatingFilter = Option<usize>;
 
+pub const RATING_THRESHOLDS: [usize; 5] = [0, 10, 25, 50, 100];
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ArticlesListSorting {
     Newest,
@@ -210,14 +340,28 @@ impl DateFilter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ComplexityFilter {
     Easy,
     Medium,
     Hard,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+impl ComplexityFilter {
+    pub fn to_string(&self) -> String {
+        match self {
+            ComplexityFilter::Easy => "low".to_string(),
+            ComplexityFilter::Medium => "medium".to_string(),
+            ComplexityFilter::Hard => "high".to_string(),
+        }
+    }
+
+    pub fn matches(&self, complexity: &str) -> bool {
+        self.to_string() == complexity
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ArticlesSearchSorting {
     Relevance,
     Date,
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use reqwest::{Error, Method};
+use serde::{Deserialize, Serialize};
+
+use super::HabrClient;
+use super::article::ArticleData;
+use super::html_parse::extract_text_from_html;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_company_card() {
+        let json = r#"{
+            "alias": "habr",
+            "titleHtml": "Habr",
+            "descriptionHtml": "Экосистема для развития людей",
+            "imageUrl": "//habrastorage.org/company.png",
+            "siteUrl": "https://habr.com",
+            "staffNumber": "101–200 человек",
+            "statistics": {"subscribersCount": 20000, "rating": 250.5}
+        }"#;
+
+        let company: CompanyProfile = serde_json::from_str(json).unwrap();
+
+        assert_eq!(company.alias, "habr");
+        assert_eq!(company.site_url.as_deref(), Some("https://habr.com"));
+        assert_eq!(company.statistics.subscribers_count, 20000);
+        assert!(company.about_html.is_empty());
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompanySummary {
+    pub alias: String,
+    #[serde(alias = "titleHtml")]
+    pub title: String,
+    #[serde(alias = "descriptionHtml", default)]
+    pub description: String,
+    #[serde(alias = "imageUrl", default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub statistics: CompanyStatistics,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CompanyStatistics {
+    #[serde(alias = "subscribersCount", default)]
+    pub subscribers_count: usize,
+    #[serde(default)]
+    pub rating: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompanyProfile {
+    pub alias: String,
+    #[serde(alias = "titleHtml")]
+    pub title: String,
+    #[serde(alias = "descriptionHtml", default)]
+    pub description: String,
+    #[serde(alias = "imageUrl", default)]
+    pub image_url: Option<String>,
+    #[serde(alias = "siteUrl", default)]
+    pub site_url: Option<String>,
+    #[serde(alias = "staffNumber", default)]
+    pub staff_number: Option<String>,
+    #[serde(default)]
+    pub statistics: CompanyStatistics,
+    #[serde(skip)]
+    pub about_html: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CompanyWhois {
+    #[serde(alias = "aboutHtml", default)]
+    about_html: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CompaniesResponse {
+    #[serde(rename(deserialize = "pagesCount"))]
+    pub pages_count: usize,
+    #[serde(rename(deserialize = "companyRefs"), default)]
+    pub companies: HashMap<String, CompanySummary>,
+}
+
+impl HabrClient {
+    /// Companies ordered by rating or found by the text.
+    pub async fn get_companies(
+        &self,
+        text: &str,
+        page: u8,
+    ) -> Result<(Vec<CompanySummary>, usize), Error> {
+        if !text.is_empty() {
+            return self.search_companies(text, page).await;
+        }
+        let resp = self
+            .setup_request(Method::GET, "https://habr.com/kek/v2/companies")
+            .query(&[("order", "rating"), ("page", page.to_string().as_str())])
+            .send()
+            .await?;
+
+        Ok(companies_from_response(
+            resp.error_for_status()?.json().await?,
+        ))
+    }
+
+    /// Company card with the text about the company.
+    /// Only the card is required: errors of the text are logged and leave it empty.
+    pub async fn get_company_profile(&self, alias: &str) -> Result<CompanyProfile, Error> {
+        let url = format!("https://habr.com/kek/v2/companies/{}/card", alias);
+        let mut company: CompanyProfile = self.get_json(&url).await?;
+        company.title = extract_text_from_html(&company.title).trim().to_string();
+        company.description = extract_text_from_html(&company.description)
+            .trim()
+            .to_string();
+
+        let url = format!("https://habr.com/kek/v2/companies/{}/whois", alias);
+        match self.get_json::<CompanyWhois>(&url).await {
+            Ok(whois) => company.about_html = whois.about_html,
+            Err(e) => log::warn!("Failed to get about of {}: {}", alias, e),
+        }
+
+        Ok(company)
+    }
+
+    pub async fn get_company_articles(
+        &self,
+        alias: &str,
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, usize), Error> {
+        self.get_articles_by(("company", alias), page).await
+    }
+
+    pub async fn search_companies(
+        &self,
+        text: &str,
+        page: u8,
+    ) -> Result<(Vec<CompanySummary>, usize), Error> {
+        let resp = self
+            .setup_request(Method::GET, "https://habr.com/kek/v2/companies/search")
+            .query(&[("q", text), ("page", page.to_string().as_str())])
+            .send()
+            .await?;
+
+        Ok(companies_from_response(
+            resp.error_for_status()?.json().await?,
+        ))
+    }
+
+    /// Companies which blog the most in the hub.
+    pub async fn get_hub_companies(&self, hub_alias: &str) -> Result<Vec<CompanySummary>, Error> {
+        let url = format!("https://habr.com/kek/v2/hubs/{}/companies", hub_alias);
+        let resp = self
+            .setup_request(Method::GET, url.as_str())
+            .query(&[("page", "1")])
+            .send()
+            .await?;
+
+        Ok(companies_from_response(resp.error_for_status()?.json().await?).0)
+    }
+}
+
+fn companies_from_response(resp_parsed: CompaniesResponse) -> (Vec<CompanySummary>, usize) {
+    let mut companies: Vec<CompanySummary> = resp_parsed.companies.into_values().collect();
+    companies.iter_mut().for_each(|c| {
+        c.title = extract_text_from_html(&c.title);
+        c.description = extract_text_from_html(&c.description);
+    });
+    companies.sort_by(|a, b| a.title.cmp(&b.title));
+
+    (companies, resp_parsed.pages_count)
+}
@@ -219,3 +219,15 @@ pub enum TypedText {
     Italic(String),
     Strong(String),
 }
+
+impl TypedText {
+    pub fn text(&self) -> &str {
+        match self {
+            TypedText::Common(text)
+            | TypedText::Code(text)
+            | TypedText::Italic(text)
+            | TypedText::Strong(text) => text,
+            TypedText::Link { value, .. } => value,
+        }
+    }
+}
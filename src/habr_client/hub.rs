@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
-use reqwest::Error;
+use reqwest::{Error, Method};
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+use super::HabrClient;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,22 +43,23 @@ mod tests {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Hub {
+    #[serde(default)]
     pub id: String,
     pub alias: String,
     #[serde(alias = "titleHtml")]
     pub title: String,
-    #[serde(rename(deserialize = "descriptionHtml"))]
+    #[serde(alias = "descriptionHtml")]
     pub description_html: String,
-    #[serde(rename(deserialize = "commonTags"))]
+    #[serde(alias = "commonTags", default)]
     pub common_tags: Vec<String>,
-    #[serde(rename(deserialize = "imageUrl"))]
+    #[serde(alias = "imageUrl")]
     pub image_url: String,
     pub statistics: HubStatistics,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HubStatistics {
-    #[serde(rename(deserialize = "subscribersCount"))]
+    #[serde(alias = "subscribersCount")]
     pub subscribers_count: usize,
     pub rating: f32,
 }
@@ -91,8 +94,7 @@ pub async fn get_hubs(page: u8, search_text: String) -> Result<(Vec<Hub>, usize)
         .send()
         .await?;
 
-    let resp_parsed: HubsResponse = serde_json::from_slice(&resp.bytes().await.unwrap())
-        .expect("[!] Error with response parsing");
+    let resp_parsed: HubsResponse = resp.error_for_status()?.json().await?;
 
     let mut hubs: Vec<Hub> = resp_parsed.hub_refs.into_values().collect();
 
@@ -102,3 +104,17 @@ pub async fn get_hubs(page: u8, search_text: String) -> Result<(Vec<Hub>, usize)
 
     Ok((hubs, resp_parsed.pages_count))
 }
+
+impl HabrClient {
+    pub async fn get_hub(&self, alias: &str) -> Result<Hub, Error> {
+        let url = format!("https://habr.com/kek/v2/hubs/{}/profile", alias);
+        let resp = self.setup_request(Method::GET, url.as_str()).send().await?;
+
+        let mut hub: Hub = resp.error_for_status()?.json().await?;
+        hub.title = super::html_parse::extract_text_from_html(&hub.title)
+            .trim()
+            .to_string();
+
+        Ok(hub)
+    }
+}
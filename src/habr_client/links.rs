@@ -0,0 +1,257 @@
+use reqwest::Url;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(id: &str) -> Option<HabrLink> {
+        Some(HabrLink::Article(id.to_string()))
+    }
+
+    #[test]
+    fn test_publication_links() {
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/articles/812345/"),
+            article("812345")
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/en/post/12345"),
+            article("12345")
+        );
+        assert_eq!(
+            HabrLink::parse("http://www.habr.com/ru/news/900/"),
+            article("900")
+        );
+        assert_eq!(
+            HabrLink::parse("https://m.habr.com/ru/posts/901/"),
+            article("901")
+        );
+        assert_eq!(HabrLink::parse("https://habr.com/p/902/"), article("902"));
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/companies/yandex/articles/903/"),
+            article("903")
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/company/yandex/blog/904/"),
+            article("904")
+        );
+        assert_eq!(HabrLink::parse("/ru/articles/905/"), article("905"));
+        assert_eq!(
+            HabrLink::parse("//habr.com/ru/articles/906/"),
+            article("906")
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/articles/907/?utm_source=x#habracut"),
+            article("907")
+        );
+    }
+
+    #[test]
+    fn test_comment_links() {
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/articles/812345/comments/"),
+            Some(HabrLink::Comments {
+                article_id: "812345".to_string(),
+                comment_id: None,
+            })
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/articles/812345/comments/#comment_2600"),
+            Some(HabrLink::Comments {
+                article_id: "812345".to_string(),
+                comment_id: Some("2600".to_string()),
+            })
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/articles/812345/#comment_2601"),
+            Some(HabrLink::Comments {
+                article_id: "812345".to_string(),
+                comment_id: Some("2601".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_profile_links() {
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/hubs/rust/articles/"),
+            Some(HabrLink::Hub("rust".to_string()))
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/hub/go/"),
+            Some(HabrLink::Hub("go".to_string()))
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/users/someone/comments/"),
+            Some(HabrLink::User("someone".to_string()))
+        );
+        assert_eq!(
+            HabrLink::parse("https://habr.com/ru/companies/yandex/"),
+            Some(HabrLink::Company("yandex".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_external_links() {
+        assert_eq!(HabrLink::parse("https://github.com/ru/articles/1/"), None);
+        assert_eq!(
+            HabrLink::parse("https://habr.com.evil.org/ru/articles/1/"),
+            None
+        );
+        assert_eq!(HabrLink::parse("https://habr.com/ru/articles/"), None);
+        assert_eq!(HabrLink::parse("https://habr.com/ru/articles/top/"), None);
+        assert_eq!(HabrLink::parse("https://habr.com/ru/hubs/"), None);
+        assert_eq!(HabrLink::parse("https://habr.com/ru/sandbox/1/"), None);
+        assert_eq!(HabrLink::parse("mailto:someone@habr.com"), None);
+    }
+
+    #[test]
+    fn test_absolute_url() {
+        assert_eq!(
+            absolute_url("/ru/sandbox/1/").as_deref(),
+            Some("https://habr.com/ru/sandbox/1/")
+        );
+        assert_eq!(
+            absolute_url("https://github.com/a").as_deref(),
+            Some("https://github.com/a")
+        );
+    }
+
+    #[test]
+    fn test_command_line_links() {
+        assert_eq!(HabrLink::from_arg(" 812345 "), article("812345"));
+        assert_eq!(
+            HabrLink::from_arg("https://habr.com/ru/users/someone/"),
+            Some(HabrLink::User("someone".to_string()))
+        );
+        assert_eq!(HabrLink::from_arg("--help"), None);
+        assert_eq!(HabrLink::from_arg("https://example.com/"), None);
+
+        let links = [
+            HabrLink::Article("1".to_string()),
+            HabrLink::Comments {
+                article_id: "1".to_string(),
+                comment_id: Some("2".to_string()),
+            },
+            HabrLink::Hub("rust".to_string()),
+            HabrLink::User("someone".to_string()),
+            HabrLink::Company("yandex".to_string()),
+        ];
+        for link in links {
+            assert_eq!(HabrLink::parse(&link.url()), Some(link));
+        }
+    }
+}
+
+/// Path segments of the publications urls, like `/ru/articles/{id}/`.
+const PUBLICATION_SEGMENTS: [&str; 7] =
+    ["articles", "article", "post", "posts", "news", "p", "blog"];
+
+/// Pages of habr.com which have their own views in the app.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HabrLink {
+    Article(String),
+    Comments {
+        article_id: String,
+        comment_id: Option<String>,
+    },
+    Hub(String),
+    User(String),
+    Company(String),
+}
+
+impl HabrLink {
+    /// Recognizes absolute and relative habr.com urls, `None` for all the other ones.
+    pub fn parse(url: &str) -> Option<HabrLink> {
+        let url = Url::parse(&absolute_url(url)?).ok()?;
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return None;
+        }
+        let host = url.host_str()?;
+        let host = host
+            .strip_prefix("www.")
+            .or_else(|| host.strip_prefix("m."))
+            .unwrap_or(host);
+        if host != "habr.com" {
+            return None;
+        }
+
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        let segments = match segments.as_slice() {
+            ["ru" | "en", rest @ ..] => rest,
+            all => all,
+        };
+        let comment_id = url
+            .fragment()
+            .and_then(|f| f.strip_prefix("comment_"))
+            .filter(|id| is_id(id))
+            .map(str::to_string);
+
+        let link = match segments {
+            [kind, id, rest @ ..] if is_publication(kind, id) => {
+                publication_link(id, rest, comment_id)
+            }
+            ["companies" | "company", _, kind, id, rest @ ..] if is_publication(kind, id) => {
+                publication_link(id, rest, comment_id)
+            }
+            ["companies" | "company", alias, ..] => HabrLink::Company(alias.to_string()),
+            ["hubs" | "hub", alias, ..] => HabrLink::Hub(alias.to_string()),
+            ["users" | "user", alias, ..] => HabrLink::User(alias.to_string()),
+            _ => return None,
+        };
+        Some(link)
+    }
+
+    /// Link passed on the command line, a url or just an article id.
+    pub fn from_arg(arg: &str) -> Option<HabrLink> {
+        let arg = arg.trim();
+        if is_id(arg) {
+            return Some(HabrLink::Article(arg.to_string()));
+        }
+        HabrLink::parse(arg)
+    }
+
+    pub fn url(&self) -> String {
+        match self {
+            HabrLink::Article(id) => format!("https://habr.com/ru/articles/{}/", id),
+            HabrLink::Comments {
+                article_id,
+                comment_id,
+            } => {
+                let url = format!("https://habr.com/ru/articles/{}/comments/", article_id);
+                match comment_id {
+                    Some(comment_id) => format!("{}#comment_{}", url, comment_id),
+                    None => url,
+                }
+            }
+            HabrLink::Hub(alias) => format!("https://habr.com/ru/hubs/{}/", alias),
+            HabrLink::User(alias) => format!("https://habr.com/ru/users/{}/", alias),
+            HabrLink::Company(alias) => format!("https://habr.com/ru/companies/{}/", alias),
+        }
+    }
+}
+
+/// Resolves links from the articles, like `/ru/articles/1/`, against habr.com.
+pub fn absolute_url(url: &str) -> Option<String> {
+    let base = Url::parse("https://habr.com/").unwrap();
+    base.join(url.trim()).ok().map(String::from)
+}
+
+fn is_id(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_publication(kind: &str, id: &str) -> bool {
+    PUBLICATION_SEGMENTS.contains(&kind) && is_id(id)
+}
+
+fn publication_link(id: &str, rest: &[&str], comment_id: Option<String>) -> HabrLink {
+    if comment_id.is_some() || rest.first() == Some(&"comments") {
+        HabrLink::Comments {
+            article_id: id.to_string(),
+            comment_id,
+        }
+    } else {
+        HabrLink::Article(id.to_string())
+    }
+}
@@ -1,22 +1,109 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use chrono::{DateTime, Local};
 use reqwest::{Client, Error, Method, RequestBuilder, header};
+use serde::de::DeserializeOwned;
 
 pub mod article;
 pub mod comment;
+pub mod company;
 pub mod html_parse;
 pub mod hub;
+pub mod links;
+pub mod search;
+pub mod tag;
+pub mod user;
 
 use article::{
-    ArticleContent, ArticleData, ArticleResponse, ArticlesListFilter, ArticlesListSorting,
-    ArticlesResponse, ArticlesSearchSorting,
+    ArticleContent, ArticleData, ArticlePreviewResponse, ArticleResponse, ArticlesListFilter,
+    ArticlesListSorting, ArticlesResponse, ArticlesSearchSorting, ComplexityFilter,
+    PublicationType,
 };
 use comment::{Comment, CommentsResponse};
+use search::SearchQuery;
 
 use html_parse::{extract_content_from_html, extract_text_from_html};
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article(id: &str, published_at: &str, score: isize) -> ArticleData {
+        ArticleData {
+            published_at: published_at.to_string(),
+            score,
+            ..test_article(id)
+        }
+    }
+
+    #[test]
+    fn test_merge_articles() {
+        let first = vec![
+            article("1", "01.02.2026 10:00", 5),
+            article("2", "03.02.2026 10:00", 1),
+        ];
+        let second = vec![
+            article("2", "03.02.2026 10:00", 1),
+            article("3", "02.02.2026 10:00", 10),
+        ];
+
+        let newest = merge_articles(
+            vec![first.clone(), second.clone()],
+            ArticlesListSorting::Newest,
+        );
+        let ids: Vec<&str> = newest.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["2", "3", "1"]);
+
+        let best = merge_articles(vec![first, second], ArticlesListSorting::Best);
+        let ids: Vec<&str> = best.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["3", "1", "2"]);
+    }
+
+    #[test]
+    fn test_publication_types() {
+        let json = r#"{
+            "pagesCount": 1,
+            "publicationIds": ["1", "2"],
+            "publicationRefs": {
+                "1": {
+                    "id": "1",
+                    "timePublished": "2026-01-25T08:09:55+00:00",
+                    "postType": "news",
+                    "titleHtml": "News title",
+                    "leadData": {"textHtml": "<p>Lead</p>", "imageUrl": null},
+                    "tags": [],
+                    "complexity": null,
+                    "readingTime": 1,
+                    "author": null,
+                    "statistics": {"commentsCount": 0, "readingCount": 0, "score": 0}
+                },
+                "2": {
+                    "id": "2",
+                    "timePublished": "2026-01-25T08:09:55+00:00",
+                    "postType": "post",
+                    "leadData": {"textHtml": "<p>Short   post\n text</p>", "imageUrl": null},
+                    "tags": [],
+                    "complexity": null,
+                    "readingTime": 1,
+                    "author": null,
+                    "statistics": {"commentsCount": 0, "readingCount": 0, "score": 0}
+                }
+            }
+        }"#;
+
+        let response: ArticlesResponse = serde_json::from_str(json).unwrap();
+        let mut articles = articles_from_response(response);
+        articles.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(articles[0].publication_type, PublicationType::News);
+        assert_eq!(articles[0].title.trim(), "News title");
+        assert_eq!(articles[1].publication_type, PublicationType::Post);
+        assert_eq!(articles[1].title, "Short post text");
+    }
+}
+
 type PagesCount = usize;
 
 #[derive(Clone)]
@@ -43,6 +130,12 @@ impl HabrClient {
             .query(&[("fl", "ru"), ("hl", "ru")])
     }
 
+    /// Response of the GET request, the error statuses are returned as errors too.
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, Error> {
+        let resp = self.setup_request(Method::GET, url).send().await?;
+        resp.error_for_status()?.json().await
+    }
+
     pub async fn get_article_details(
         &self,
         article_id: &str,
@@ -50,8 +143,7 @@ impl HabrClient {
         let url = format!("https://habr.com/kek/v2/articles/{}", article_id);
         let resp = self.setup_request(Method::GET, url.as_str()).send().await?;
 
-        let resp_parsed: ArticleResponse = serde_json::from_slice(&resp.bytes().await.unwrap())
-            .expect("[!] Error with response parsing");
+        let resp_parsed: ArticleResponse = resp.error_for_status()?.json().await?;
 
         Ok((
             resp_parsed.title,
@@ -61,64 +153,74 @@ impl HabrClient {
         ))
     }
 
+    /// Card data of the article, for the articles opened by id without a list.
+    pub async fn get_article(&self, article_id: &str) -> Result<ArticleData, Error> {
+        let url = format!("https://habr.com/kek/v2/articles/{}", article_id);
+        let resp = self.setup_request(Method::GET, url.as_str()).send().await?;
+
+        let resp_parsed: ArticlePreviewResponse = resp.error_for_status()?.json().await?;
+
+        Ok(article_from_response(resp_parsed))
+    }
+
+    /// Articles found by the query. The API can't filter by dates, so the found
+    /// articles are filtered here and a page may have fewer articles than requested.
     pub async fn search_articles(
         &self,
-        search_query: &str,
-        sort: ArticlesSearchSorting,
+        query: &SearchQuery,
         page: u8,
     ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
-        let resp = self
+        let (mut articles, pages_count) = self.search_unfiltered_articles(query, page).await?;
+        articles.retain(|a| query.matches(a));
+        Ok((articles, pages_count))
+    }
+
+    /// Page of the server search results, `SearchQuery::matches` isn't checked.
+    async fn search_unfiltered_articles(
+        &self,
+        query: &SearchQuery,
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
+        let mut request = self
             .setup_request(Method::GET, "https://habr.com/kek/v2/articles/")
             .query(&[
                 ("page", page.to_string().as_str()),
-                ("query", search_query),
-                ("order", &sort.to_string()),
+                ("query", query.articles_query().as_str()),
+                ("order", &query.sorting.to_string()),
                 ("perPage", "20"),
-            ])
-            .send()
-            .await?;
+            ]);
+        if let Some(complexity) = query.complexity {
+            request = request.query(&[("complexity", complexity.to_string())]);
+        }
+        if let Some(min_score) = query.min_score {
+            request = request.query(&[("score", min_score.to_string())]);
+        }
+        if let Some(hub) = query.hub.as_ref() {
+            request = request.query(&[("hub", hub)]);
+        }
+        let resp = request.send().await?;
+        let resp_parsed: ArticlesResponse = resp.error_for_status()?.json().await?;
 
-        let response_bytes = resp.bytes().await.unwrap();
-        // println!("{}", String::from_utf8_lossy(&response_bytes));
-        let resp_parsed: ArticlesResponse =
-            serde_json::from_slice(&response_bytes).expect("[!] Error with response parsing");
-
-        let mut articles: Vec<ArticleData> = resp_parsed
-            .articles
-            .into_values()
-            .map(|a| {
-                let published_at: DateTime<Local> = DateTime::from_str(&a.published_at).unwrap();
-
-                ArticleData {
-                    id: a.id.into(),
-                    title: extract_text_from_html(a.title.trim()),
-                    author: a.author.map_or("".to_string(), |a| a.alias),
-                    reading_time: a.reading_time,
-                    published_at: format!("{}", published_at.format("%d.%m.%Y %H:%M")),
-                    tags: a.tags.into_iter().map(|t| t.title).collect(),
-                    complexity: a.complexity.unwrap_or(String::new()),
-                    image_url: a.lead_data.image_url.unwrap_or("".to_string()),
-                    score: a.statistics.score,
-                    comments_count: a.statistics.comments_count,
-                }
-            })
-            .collect();
+        let pages_count = resp_parsed.pages_count;
+        let mut articles = articles_from_response(resp_parsed);
 
-        match sort {
+        match query.sorting {
             ArticlesSearchSorting::Rating => {
                 articles.sort_by(|a, b| a.score.cmp(&b.score));
             }
             _ => {}
         }
 
-        Ok((articles, resp_parsed.pages_count))
+        Ok((articles, pages_count))
     }
 
     pub async fn get_articles(
         &self,
         hub: String,
+        publication_type: PublicationType,
         sorting: ArticlesListSorting,
         filter: ArticlesListFilter,
+        complexity: Option<ComplexityFilter>,
         page: u8,
     ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
         let filter_params: (&str, String) = match filter {
@@ -127,7 +229,7 @@ impl HabrClient {
                 ("score", rating.map_or(String::new(), |s| s.to_string()))
             }
         };
-        let resp = self
+        let mut request = self
             .setup_request(Method::GET, "https://habr.com/kek/v2/articles/")
             .query(&[
                 ("page", page.to_string()),
@@ -142,44 +244,101 @@ impl HabrClient {
                 ),
                 ("perPage", String::from("20")),
                 filter_params,
+            ]);
+        if let Some(complexity) = complexity {
+            request = request.query(&[("complexity", complexity.to_string())]);
+        }
+        if let Some(param) = publication_type.feed_param() {
+            request = request.query(&[param]);
+        }
+        let resp = request.send().await?;
+
+        let resp_parsed: ArticlesResponse = resp.error_for_status()?.json().await?;
+
+        let pages_count = resp_parsed.pages_count;
+        let mut articles = articles_from_response(resp_parsed);
+
+        match sorting {
+            ArticlesListSorting::Best => {
+                articles.sort_by(|a, b| b.score.cmp(&a.score));
+            }
+            _ => {}
+        }
+
+        Ok((articles, pages_count))
+    }
+
+    /// Newest articles of one author or company blog, `param` selects whose.
+    async fn get_articles_by(
+        &self,
+        param: (&str, &str),
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
+        let resp = self
+            .setup_request(Method::GET, "https://habr.com/kek/v2/articles/")
+            .query(&[
+                param,
+                ("page", page.to_string().as_str()),
+                ("perPage", "20"),
             ])
             .send()
             .await?;
 
-        let response_bytes = resp.bytes().await.unwrap();
-        // println!("{}", String::from_utf8_lossy(&response_bytes));
-        let resp_parsed: ArticlesResponse =
-            serde_json::from_slice(&response_bytes).expect("[!] Error with response parsing");
-
-        let mut articles: Vec<ArticleData> = resp_parsed
-            .articles
-            .into_values()
-            .map(|a| {
-                let published_at: DateTime<Local> = DateTime::from_str(&a.published_at).unwrap();
-
-                ArticleData {
-                    id: a.id.into(),
-                    title: extract_text_from_html(a.title.trim()),
-                    author: a.author.map_or("".to_string(), |a| a.alias),
-                    reading_time: a.reading_time,
-                    published_at: format!("{}", published_at.format("%d.%m.%Y %H:%M")),
-                    tags: a.tags.into_iter().map(|t| t.title).collect(),
-                    complexity: a.complexity.unwrap_or(String::new()),
-                    image_url: a.lead_data.image_url.unwrap_or("".to_string()),
-                    score: a.statistics.score,
-                    comments_count: a.statistics.comments_count,
-                }
+        let resp_parsed: ArticlesResponse = resp.error_for_status()?.json().await?;
+        let pages_count = resp_parsed.pages_count;
+        let mut articles = articles_from_response(resp_parsed);
+        articles.sort_by_key(|a| std::cmp::Reverse(a.published_at_datetime()));
+
+        Ok((articles, pages_count))
+    }
+
+    /// Articles of several hubs merged into one list. Hubs are requested concurrently,
+    /// a hub which failed to load is skipped unless all of them failed.
+    pub async fn get_hubs_articles(
+        &self,
+        hubs: Vec<String>,
+        publication_type: PublicationType,
+        sorting: ArticlesListSorting,
+        filter: ArticlesListFilter,
+        complexity: Option<ComplexityFilter>,
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
+        let tasks: Vec<_> = hubs
+            .into_iter()
+            .map(|hub| {
+                let client = self.clone();
+                tokio::spawn(async move {
+                    client
+                        .get_articles(hub, publication_type, sorting, filter, complexity, page)
+                        .await
+                })
             })
             .collect();
 
-        match sorting {
-            ArticlesListSorting::Best => {
-                articles.sort_by(|a, b| b.score.cmp(&a.score));
+        let mut lists = Vec::new();
+        let mut pages_count = 0;
+        let mut error = None;
+        for task in tasks {
+            match task.await {
+                Ok(Ok((articles, hub_pages_count))) => {
+                    pages_count = pages_count.max(hub_pages_count);
+                    lists.push(articles);
+                }
+                Ok(Err(e)) => {
+                    log::warn!("Failed to get hub articles: {}", e);
+                    error = Some(e);
+                }
+                Err(e) => log::warn!("Hub articles task failed: {}", e),
             }
-            _ => {}
         }
 
-        Ok((articles, resp_parsed.pages_count))
+        if lists.is_empty()
+            && let Some(e) = error
+        {
+            return Err(e);
+        }
+
+        Ok((merge_articles(lists, sorting), pages_count))
     }
 
     pub async fn get_comments(&self, article_id: &str) -> Result<Vec<Comment>, Error> {
@@ -210,6 +369,75 @@ impl HabrClient {
     }
 }
 
+fn merge_articles(lists: Vec<Vec<ArticleData>>, sorting: ArticlesListSorting) -> Vec<ArticleData> {
+    let mut seen_ids = HashSet::new();
+    let mut articles: Vec<ArticleData> = lists
+        .into_iter()
+        .flatten()
+        .filter(|a| seen_ids.insert(a.id.clone()))
+        .collect();
+
+    match sorting {
+        ArticlesListSorting::Newest => {
+            articles.sort_by_key(|a| std::cmp::Reverse(a.published_at_datetime()))
+        }
+        ArticlesListSorting::Best => articles.sort_by_key(|a| std::cmp::Reverse(a.score)),
+    }
+    articles
+}
+
+fn articles_from_response(resp_parsed: ArticlesResponse) -> Vec<ArticleData> {
+    resp_parsed
+        .articles
+        .into_values()
+        .map(article_from_response)
+        .collect()
+}
+
+fn article_from_response(a: ArticlePreviewResponse) -> ArticleData {
+    let published_at: DateTime<Local> = DateTime::from_str(&a.published_at).unwrap();
+    let (company_hubs, hubs): (Vec<_>, Vec<_>) = a
+        .hubs
+        .into_iter()
+        .partition(|h| h.hub_type == "corporative");
+
+    let publication_type = PublicationType::from_post_type(&a.post_type);
+    let title = match extract_text_from_html(a.title.trim()) {
+        title if title.trim().is_empty() => {
+            post_snippet(&extract_text_from_html(&a.lead_data.description))
+        }
+        title => title,
+    };
+
+    ArticleData {
+        id: a.id,
+        title,
+        publication_type,
+        author: a.author.map_or("".to_string(), |a| a.alias),
+        reading_time: a.reading_time,
+        hubs: hubs.into_iter().map(|h| h.alias).collect(),
+        company: company_hubs.into_iter().next().map(|h| h.alias),
+        published_at: format!("{}", published_at.format("%d.%m.%Y %H:%M")),
+        tags: a.tags.into_iter().map(|t| t.title).collect(),
+        complexity: a.complexity.unwrap_or(String::new()),
+        image_url: a.lead_data.image_url.unwrap_or("".to_string()),
+        score: a.statistics.score,
+        comments_count: a.statistics.comments_count,
+    }
+}
+
+/// Posts have no title, so the beginning of the text is shown instead.
+fn post_snippet(text: &str) -> String {
+    const SNIPPET_LENGTH: usize = 150;
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= SNIPPET_LENGTH {
+        return text;
+    }
+    let snippet: String = text.chars().take(SNIPPET_LENGTH).collect();
+    format!("{}…", snippet.trim_end())
+}
+
 fn resolve_children(
     child_ids: &[String],
     comment_refs: &mut HashMap<String, Comment>,
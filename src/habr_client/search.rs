@@ -0,0 +1,376 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::HabrClient;
+use super::article::{ArticleData, ArticlesSearchSorting, ComplexityFilter};
+use super::company::CompanySummary;
+use super::hub::Hub;
+use super::user::UserSummary;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article(published_at: &str, score: isize, complexity: &str) -> ArticleData {
+        ArticleData {
+            title: "Title".to_string(),
+            complexity: complexity.to_string(),
+            author: "author".to_string(),
+            published_at: published_at.to_string(),
+            score,
+            ..test_article("1")
+        }
+    }
+
+    #[test]
+    fn test_query_matches_filters() {
+        let query = SearchQuery::new("rust")
+            .date_from(Some("2024-01-01".to_string()))
+            .date_to(Some("2024-12-31".to_string()))
+            .min_score(Some(10))
+            .complexity(Some(ComplexityFilter::Medium));
+
+        assert!(query.matches(&article("15.06.2024 12:00", 20, "medium")));
+        assert!(query.matches(&article("31.12.2024 23:59", 10, "medium")));
+        assert!(!query.matches(&article("01.01.2025 00:00", 20, "medium")));
+        assert!(!query.matches(&article("15.06.2024 12:00", 5, "medium")));
+        assert!(!query.matches(&article("15.06.2024 12:00", 20, "high")));
+    }
+
+    #[test]
+    fn test_tags_target_query() {
+        let query = SearchQuery::new(" rust ").target(SearchTarget::Tags);
+        assert_eq!(query.articles_query(), "[rust]");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SearchTarget {
+    Articles,
+    Hubs,
+    Authors,
+    Companies,
+    Tags,
+}
+
+impl SearchTarget {
+    pub const ALL: [SearchTarget; 5] = [
+        SearchTarget::Articles,
+        SearchTarget::Hubs,
+        SearchTarget::Authors,
+        SearchTarget::Companies,
+        SearchTarget::Tags,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            SearchTarget::Articles => "Статьи",
+            SearchTarget::Hubs => "Хабы",
+            SearchTarget::Authors => "Авторы",
+            SearchTarget::Companies => "Компании",
+            SearchTarget::Tags => "Теги",
+        }
+    }
+}
+
+/// Search parameters. Dates are kept as `YYYY-MM-DD` strings so the query can be
+/// stored as is in recent and saved searches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub text: String,
+    pub target: SearchTarget,
+    pub sorting: ArticlesSearchSorting,
+    #[serde(default)]
+    pub date_from: Option<String>,
+    #[serde(default)]
+    pub date_to: Option<String>,
+    #[serde(default)]
+    pub min_score: Option<usize>,
+    #[serde(default)]
+    pub hub: Option<String>,
+    #[serde(default)]
+    pub complexity: Option<ComplexityFilter>,
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            target: SearchTarget::Articles,
+            sorting: ArticlesSearchSorting::Relevance,
+            date_from: None,
+            date_to: None,
+            min_score: None,
+            hub: None,
+            complexity: None,
+        }
+    }
+
+    pub fn target(mut self, target: SearchTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn sorting(mut self, sorting: ArticlesSearchSorting) -> Self {
+        self.sorting = sorting;
+        self
+    }
+
+    pub fn date_from(mut self, date: Option<String>) -> Self {
+        self.date_from = date;
+        self
+    }
+
+    pub fn date_to(mut self, date: Option<String>) -> Self {
+        self.date_to = date;
+        self
+    }
+
+    pub fn min_score(mut self, min_score: Option<usize>) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    pub fn hub(mut self, hub: Option<String>) -> Self {
+        self.hub = hub;
+        self
+    }
+
+    pub fn complexity(mut self, complexity: Option<ComplexityFilter>) -> Self {
+        self.complexity = complexity;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn has_filters(&self) -> bool {
+        self.date_from.is_some()
+            || self.date_to.is_some()
+            || self.min_score.is_some()
+            || self.hub.is_some()
+            || self.complexity.is_some()
+    }
+
+    /// Short human readable description used in the lists of recent and saved searches.
+    pub fn description(&self) -> String {
+        let mut parts = vec![self.target.title().to_string()];
+        if let Some(hub) = self.hub.as_ref() {
+            parts.push(format!("хаб {}", hub));
+        }
+        match (self.date_from.as_ref(), self.date_to.as_ref()) {
+            (Some(from), Some(to)) => parts.push(format!("{} — {}", from, to)),
+            (Some(from), None) => parts.push(format!("с {}", from)),
+            (None, Some(to)) => parts.push(format!("по {}", to)),
+            (None, None) => {}
+        }
+        if let Some(min_score) = self.min_score {
+            parts.push(format!("≥{}", min_score));
+        }
+        if let Some(complexity) = self.complexity {
+            parts.push(match complexity {
+                ComplexityFilter::Easy => "простые".to_string(),
+                ComplexityFilter::Medium => "средние".to_string(),
+                ComplexityFilter::Hard => "сложные".to_string(),
+            });
+        }
+        parts.join(" · ")
+    }
+
+    /// Text sent to the articles search. Tags are searched with the `[tag]` syntax.
+    pub fn articles_query(&self) -> String {
+        let text = self.text.trim();
+        match self.target {
+            SearchTarget::Tags => format!("[{}]", text),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Checks the filters the API may ignore.
+    pub fn matches(&self, article: &ArticleData) -> bool {
+        if let Some(complexity) = self.complexity
+            && !complexity.matches(&article.complexity)
+        {
+            return false;
+        }
+        if let Some(min_score) = self.min_score
+            && article.score < min_score as isize
+        {
+            return false;
+        }
+        if self.date_from.is_none() && self.date_to.is_none() {
+            return true;
+        }
+        let Some(published_at) = article.published_at_datetime() else {
+            return true;
+        };
+        let published_at = published_at.date();
+        if let Some(from) = self.date_from.as_deref().and_then(parse_date)
+            && published_at < from
+        {
+            return false;
+        }
+        if let Some(to) = self.date_to.as_deref().and_then(parse_date)
+            && published_at > to
+        {
+            return false;
+        }
+        true
+    }
+}
+
+pub fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+}
+
+pub enum SearchResults {
+    Articles(Vec<ArticleData>),
+    Hubs(Vec<Hub>),
+    Authors(Vec<UserSummary>),
+    Companies(Vec<CompanySummary>),
+}
+
+impl SearchResults {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            SearchResults::Articles(a) => a.is_empty(),
+            SearchResults::Hubs(h) => h.is_empty(),
+            SearchResults::Authors(a) => a.is_empty(),
+            SearchResults::Companies(c) => c.is_empty(),
+        }
+    }
+}
+
+pub struct SearchPage {
+    pub results: SearchResults,
+    pub pages_count: usize,
+    /// Articles of the server page left out by the filters which the API doesn't support.
+    pub filtered_out: usize,
+}
+
+/// Approximate number of results per target. The API only reports pages count,
+/// so the counts are rounded up to a full page.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FacetCounts {
+    pub articles: usize,
+    pub hubs: usize,
+    pub authors: usize,
+    pub companies: usize,
+    pub tags: usize,
+}
+
+impl FacetCounts {
+    pub fn get(&self, target: SearchTarget) -> usize {
+        match target {
+            SearchTarget::Articles => self.articles,
+            SearchTarget::Hubs => self.hubs,
+            SearchTarget::Authors => self.authors,
+            SearchTarget::Companies => self.companies,
+            SearchTarget::Tags => self.tags,
+        }
+    }
+}
+
+const PER_PAGE: usize = 20;
+
+impl HabrClient {
+    pub async fn search(
+        &self,
+        query: &SearchQuery,
+        page: u8,
+    ) -> Result<SearchPage, reqwest::Error> {
+        let text = query.text.trim().to_string();
+        let page = match query.target {
+            SearchTarget::Articles | SearchTarget::Tags => {
+                let (mut articles, pages_count) =
+                    self.search_unfiltered_articles(query, page).await?;
+                let found = articles.len();
+                articles.retain(|a| query.matches(a));
+                SearchPage {
+                    filtered_out: found - articles.len(),
+                    results: SearchResults::Articles(articles),
+                    pages_count,
+                }
+            }
+            SearchTarget::Hubs => {
+                let (hubs, pages_count) = super::hub::get_hubs(page, text).await?;
+                SearchPage {
+                    results: SearchResults::Hubs(hubs),
+                    pages_count,
+                    filtered_out: 0,
+                }
+            }
+            SearchTarget::Authors => {
+                let (users, pages_count) = self.search_users(&text, page).await?;
+                SearchPage {
+                    results: SearchResults::Authors(users),
+                    pages_count,
+                    filtered_out: 0,
+                }
+            }
+            SearchTarget::Companies => {
+                let (companies, pages_count) = self.search_companies(&text, page).await?;
+                SearchPage {
+                    results: SearchResults::Companies(companies),
+                    pages_count,
+                    filtered_out: 0,
+                }
+            }
+        };
+        Ok(page)
+    }
+
+    pub async fn facet_counts(&self, query: &SearchQuery) -> FacetCounts {
+        let text = query.text.trim().to_string();
+
+        let articles = tokio::spawn({
+            let client = self.clone();
+            let query = query.clone().target(SearchTarget::Articles);
+            async move { client.search_articles(&query, 1).await }
+        });
+        let tags = tokio::spawn({
+            let client = self.clone();
+            let query = query.clone().target(SearchTarget::Tags);
+            async move { client.search_articles(&query, 1).await }
+        });
+        let hubs = tokio::spawn(super::hub::get_hubs(1, text.clone()));
+        let authors = tokio::spawn({
+            let client = self.clone();
+            let text = text.clone();
+            async move { client.search_users(&text, 1).await }
+        });
+        let companies = tokio::spawn({
+            let client = self.clone();
+            async move { client.search_companies(&text, 1).await }
+        });
+
+        let count = |pages_count: usize, first_page: usize| {
+            if pages_count > 1 {
+                pages_count * PER_PAGE
+            } else {
+                first_page
+            }
+        };
+
+        // Spawned tasks are awaited in turn, but all of them are already running.
+        FacetCounts {
+            articles: articles
+                .await
+                .map_or(0, |r| r.map_or(0, |(a, p)| count(p, a.len()))),
+            tags: tags
+                .await
+                .map_or(0, |r| r.map_or(0, |(a, p)| count(p, a.len()))),
+            hubs: hubs
+                .await
+                .map_or(0, |r| r.map_or(0, |(h, p)| count(p, h.len()))),
+            authors: authors
+                .await
+                .map_or(0, |r| r.map_or(0, |(u, p)| count(p, u.len()))),
+            companies: companies
+                .await
+                .map_or(0, |r| r.map_or(0, |(c, p)| count(p, c.len()))),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use chrono::{Days, Local, Months, NaiveDate};
+use reqwest::Error;
+
+use super::article::{
+    ArticleData, ArticlesListFilter, ArticlesListSorting, ArticlesSearchSorting, ComplexityFilter,
+    DateFilter,
+};
+use super::search::{SearchQuery, SearchTarget};
+use super::{HabrClient, PagesCount};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article(tags: &[&str]) -> ArticleData {
+        ArticleData {
+            title: "Title".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            author: "author".to_string(),
+            ..test_article("1")
+        }
+    }
+
+    #[test]
+    fn test_tag_feed_query() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+
+        let query = tag_feed_query(
+            " Rust ",
+            ArticlesListSorting::Newest,
+            ArticlesListFilter::ByRating(Some(10)),
+            Some(ComplexityFilter::Hard),
+            today,
+        );
+        assert_eq!(query.articles_query(), "[Rust]");
+        assert_eq!(query.sorting, ArticlesSearchSorting::Date);
+        assert_eq!(query.min_score, Some(10));
+        assert_eq!(query.date_from, None);
+
+        let query = tag_feed_query(
+            "rust",
+            ArticlesListSorting::Best,
+            ArticlesListFilter::ByDate(DateFilter::Monthly),
+            None,
+            today,
+        );
+        assert_eq!(query.sorting, ArticlesSearchSorting::Rating);
+        assert_eq!(query.date_from.as_deref(), Some("2025-02-28"));
+    }
+
+    #[test]
+    fn test_tags_from_articles() {
+        let articles = [
+            article(&["Rust", "Go"]),
+            article(&["rust", "Rustup"]),
+            article(&["Python"]),
+        ];
+
+        let tags = tags_from_articles("rust", &articles);
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].title, "Rust");
+        assert_eq!(tags[0].articles_count, 2);
+        assert_eq!(tags[1].title, "Rustup");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagSummary {
+    pub title: String,
+    /// Number of found articles with the tag.
+    pub articles_count: usize,
+}
+
+/// Tag feeds are the articles search with the `[tag]` syntax, so the list sorting
+/// and filters are translated to the search ones.
+pub fn tag_feed_query(
+    tag: &str,
+    sorting: ArticlesListSorting,
+    filter: ArticlesListFilter,
+    complexity: Option<ComplexityFilter>,
+    today: NaiveDate,
+) -> SearchQuery {
+    let query = SearchQuery::new(tag.trim())
+        .target(SearchTarget::Tags)
+        .complexity(complexity)
+        .sorting(match sorting {
+            ArticlesListSorting::Newest => ArticlesSearchSorting::Date,
+            ArticlesListSorting::Best => ArticlesSearchSorting::Rating,
+        });
+
+    match filter {
+        ArticlesListFilter::ByRating(min_score) => query.min_score(min_score),
+        ArticlesListFilter::ByDate(period) => {
+            let date_from = match period {
+                DateFilter::Daily => today.checked_sub_days(Days::new(1)),
+                DateFilter::Weekly => today.checked_sub_days(Days::new(7)),
+                DateFilter::Monthly => today.checked_sub_months(Months::new(1)),
+                DateFilter::Yearly => today.checked_sub_months(Months::new(12)),
+                DateFilter::AllTime => None,
+            };
+            query.date_from(date_from.map(|d| d.format("%Y-%m-%d").to_string()))
+        }
+    }
+}
+
+/// Tags of the given articles containing the text, the most used among them first.
+pub fn tags_from_articles(text: &str, articles: &[ArticleData]) -> Vec<TagSummary> {
+    let text = text.trim().to_lowercase();
+    let mut counts: HashMap<String, TagSummary> = HashMap::new();
+    for tag in articles.iter().flat_map(|a| a.tags.iter()) {
+        let key = tag.to_lowercase();
+        if !key.contains(&text) {
+            continue;
+        }
+        counts
+            .entry(key)
+            .or_insert_with(|| TagSummary {
+                title: tag.clone(),
+                articles_count: 0,
+            })
+            .articles_count += 1;
+    }
+
+    let mut tags: Vec<TagSummary> = counts.into_values().collect();
+    tags.sort_by(|a, b| {
+        b.articles_count
+            .cmp(&a.articles_count)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    tags
+}
+
+impl HabrClient {
+    pub async fn get_tag_articles(
+        &self,
+        tag: &str,
+        sorting: ArticlesListSorting,
+        filter: ArticlesListFilter,
+        complexity: Option<ComplexityFilter>,
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, PagesCount), Error> {
+        let query = tag_feed_query(tag, sorting, filter, complexity, Local::now().date_naive());
+        let (mut articles, pages_count) = self.search_articles(&query, page).await?;
+
+        if sorting == ArticlesListSorting::Best {
+            articles.sort_by_key(|a| std::cmp::Reverse(a.score));
+        }
+
+        Ok((articles, pages_count))
+    }
+
+    /// Best-effort guess of the tags matching the text. Habr has no tags search, so tags
+    /// are collected from the first page of the articles found by the text only: tags
+    /// which aren't used there are missed and the counts are limited to that page.
+    pub async fn guess_tags(&self, text: &str) -> Result<Vec<TagSummary>, Error> {
+        if text.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        let query = SearchQuery::new(text.trim());
+        let (articles, _) = self.search_articles(&query, 1).await?;
+        Ok(tags_from_articles(text, &articles))
+    }
+}
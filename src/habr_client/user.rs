@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+use reqwest::{Error, Method};
+use serde::{Deserialize, Serialize};
+
+use super::HabrClient;
+use super::article::ArticleData;
+use super::html_parse::extract_text_from_html;
+use super::hub::{Hub, HubsResponse};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_user_card() {
+        let json = r#"{
+            "alias": "TestUser",
+            "fullname": "Test User",
+            "avatarUrl": "//habrastorage.org/avatar.png",
+            "speciality": "Rust developer",
+            "rating": 42.5,
+            "ratingPos": 100,
+            "scoreStats": {"score": 120, "votesCount": 150},
+            "registerDateTime": "2015-03-10T12:00:00+00:00",
+            "counterStats": {"postCount": 12, "commentCount": 340, "favoriteCount": 5}
+        }"#;
+
+        let profile: UserProfile = serde_json::from_str(json).unwrap();
+
+        assert_eq!(profile.alias, "TestUser");
+        assert_eq!(profile.score_stats.score, 120);
+        assert_eq!(profile.counters.post_count, 12);
+        assert_eq!(profile.counters.comment_count, 340);
+        assert_eq!(profile.registered_at_str().as_deref(), Some("10.03.2015"));
+    }
+
+    #[test]
+    fn test_parse_user_comments() {
+        let json = r#"{
+            "pagesCount": 3,
+            "commentIds": ["2", "1"],
+            "commentRefs": {
+                "1": {
+                    "id": "1",
+                    "timePublished": "2026-01-25T08:09:55+00:00",
+                    "message": "<p>First</p>",
+                    "score": 3,
+                    "publication": {"id": "100", "titleHtml": "Article"}
+                },
+                "2": {
+                    "id": "2",
+                    "timePublished": "2026-01-26T08:09:55+00:00",
+                    "message": "<p>Second</p>",
+                    "score": -1,
+                    "publication": null
+                }
+            }
+        }"#;
+
+        let response: UserCommentsResponse = serde_json::from_str(json).unwrap();
+        let (comments, pages_count) = user_comments_from_response(response);
+
+        assert_eq!(pages_count, 3);
+        assert_eq!(comments[0].id, "2");
+        assert_eq!(comments[0].message, "Second");
+        assert_eq!(comments[1].publication.as_ref().unwrap().title, "Article");
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserSummary {
+    pub alias: String,
+    #[serde(default)]
+    pub fullname: Option<String>,
+    #[serde(alias = "avatarUrl", default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub speciality: Option<String>,
+    #[serde(default)]
+    pub rating: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UsersResponse {
+    #[serde(rename(deserialize = "pagesCount"))]
+    pub pages_count: usize,
+    #[serde(rename(deserialize = "authorRefs"), default)]
+    pub users: HashMap<String, UserSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ScoreStats {
+    #[serde(default)]
+    pub score: isize,
+    #[serde(alias = "votesCount", default)]
+    pub votes_count: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UserCounters {
+    #[serde(alias = "postCount", default)]
+    pub post_count: usize,
+    #[serde(alias = "commentCount", default)]
+    pub comment_count: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserProfile {
+    pub alias: String,
+    #[serde(default)]
+    pub fullname: Option<String>,
+    #[serde(alias = "avatarUrl", default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub speciality: Option<String>,
+    #[serde(default)]
+    pub rating: f32,
+    #[serde(alias = "ratingPos", default)]
+    pub rating_position: Option<usize>,
+    /// Karma of the user.
+    #[serde(alias = "scoreStats", default)]
+    pub score_stats: ScoreStats,
+    #[serde(alias = "registerDateTime", default)]
+    pub registered_at: Option<String>,
+    #[serde(alias = "counterStats", default)]
+    pub counters: UserCounters,
+    #[serde(skip)]
+    pub about_html: String,
+    #[serde(skip)]
+    pub hubs: Vec<Hub>,
+}
+
+impl UserProfile {
+    pub fn registered_at_str(&self) -> Option<String> {
+        let registered_at: DateTime<Local> = self.registered_at.as_ref()?.parse().ok()?;
+        Some(registered_at.format("%d.%m.%Y").to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct UserWhois {
+    #[serde(alias = "aboutHtml", default)]
+    about_html: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommentPublication {
+    pub id: String,
+    #[serde(alias = "titleHtml")]
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserComment {
+    pub id: String,
+    #[serde(alias = "timePublished")]
+    pub published_at: String,
+    pub message: String,
+    #[serde(default)]
+    pub score: isize,
+    #[serde(default)]
+    pub publication: Option<CommentPublication>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserCommentsResponse {
+    #[serde(rename(deserialize = "pagesCount"))]
+    pub pages_count: usize,
+    #[serde(rename(deserialize = "commentRefs"), default)]
+    pub comments: HashMap<String, UserComment>,
+}
+
+impl HabrClient {
+    pub async fn search_users(
+        &self,
+        text: &str,
+        page: u8,
+    ) -> Result<(Vec<UserSummary>, usize), Error> {
+        let resp = self
+            .setup_request(Method::GET, "https://habr.com/kek/v2/users/search")
+            .query(&[("q", text), ("page", page.to_string().as_str())])
+            .send()
+            .await?;
+
+        Ok(users_from_response(resp.error_for_status()?.json().await?))
+    }
+
+    /// Profile card of the user with the bio and the hubs the user writes in.
+    /// Only the card is required: bio and hubs errors are logged and leave them empty.
+    pub async fn get_user_profile(&self, alias: &str) -> Result<UserProfile, Error> {
+        let url = format!("https://habr.com/kek/v2/users/{}/card", alias);
+        let resp = self.setup_request(Method::GET, url.as_str()).send().await?;
+        let mut profile: UserProfile = resp.error_for_status()?.json().await?;
+
+        let url = format!("https://habr.com/kek/v2/users/{}/whois", alias);
+        match self.get_json::<UserWhois>(&url).await {
+            Ok(whois) => profile.about_html = whois.about_html,
+            Err(e) => log::warn!("Failed to get bio of {}: {}", alias, e),
+        }
+
+        let url = format!("https://habr.com/kek/v2/users/{}/hubs", alias);
+        match self.get_json::<HubsResponse>(&url).await {
+            Ok(hubs) => {
+                profile.hubs = hubs.hub_refs.into_values().collect();
+                profile
+                    .hubs
+                    .iter_mut()
+                    .for_each(|h| h.title = extract_text_from_html(&h.title));
+                profile.hubs.sort_by(|a, b| a.title.cmp(&b.title));
+            }
+            Err(e) => log::warn!("Failed to get hubs of {}: {}", alias, e),
+        }
+
+        Ok(profile)
+    }
+
+    pub async fn get_user_articles(
+        &self,
+        alias: &str,
+        page: u8,
+    ) -> Result<(Vec<ArticleData>, usize), Error> {
+        self.get_articles_by(("user", alias), page).await
+    }
+
+    pub async fn get_user_comments(
+        &self,
+        alias: &str,
+        page: u8,
+    ) -> Result<(Vec<UserComment>, usize), Error> {
+        let url = format!("https://habr.com/kek/v2/users/{}/comments", alias);
+        let resp = self
+            .setup_request(Method::GET, url.as_str())
+            .query(&[("page", page.to_string())])
+            .send()
+            .await?;
+
+        let resp_parsed: UserCommentsResponse = resp.error_for_status()?.json().await?;
+
+        Ok(user_comments_from_response(resp_parsed))
+    }
+
+    /// The most active authors of the hub.
+    pub async fn get_hub_authors(&self, hub_alias: &str) -> Result<Vec<UserSummary>, Error> {
+        let url = format!("https://habr.com/kek/v2/hubs/{}/authors", hub_alias);
+        let resp = self
+            .setup_request(Method::GET, url.as_str())
+            .query(&[("page", "1")])
+            .send()
+            .await?;
+
+        Ok(users_from_response(resp.error_for_status()?.json().await?).0)
+    }
+}
+
+fn users_from_response(resp_parsed: UsersResponse) -> (Vec<UserSummary>, usize) {
+    let mut users: Vec<UserSummary> = resp_parsed.users.into_values().collect();
+    users.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+
+    (users, resp_parsed.pages_count)
+}
+
+fn user_comments_from_response(resp_parsed: UserCommentsResponse) -> (Vec<UserComment>, usize) {
+    let mut comments: Vec<UserComment> = resp_parsed
+        .comments
+        .into_values()
+        .map(|mut c| {
+            c.message = extract_text_from_html(&c.message).trim().to_string();
+            if let Some(publication) = c.publication.as_mut() {
+                publication.title = extract_text_from_html(&publication.title)
+                    .trim()
+                    .to_string();
+            }
+            c
+        })
+        .collect();
+    comments.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+
+    (comments, resp_parsed.pages_count)
+}
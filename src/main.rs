@@ -1,25 +1,82 @@
 mod app;
+mod dbus_service;
 mod habr_client;
+mod notifications;
 mod storage;
 mod view_stack;
 mod views;
 mod widgets;
 
-use app::MyApp;
+use app::{AppCommand, CommandSender, Integration, MyApp, SharedReadingState};
+use dbus_service::Instance;
+use habr_client::links::HabrLink;
+use notifications::Notifier;
 
 fn main() {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+    let startup_link = startup_link();
+    let (commands, commands_receiver) = app::command_channel();
+    let reading_state = SharedReadingState::default();
+
+    match Instance::connect() {
+        Ok(Instance::Primary(conn)) => {
+            dbus_service::serve(conn, commands.clone(), reading_state.clone())
+        }
+        Ok(Instance::Secondary(conn)) => {
+            let Some(link) = startup_link.as_ref() else {
+                log::info!("Haboost is already running");
+                return;
+            };
+            match dbus_service::open_url_in_running(&conn, &link.url()) {
+                Ok(()) => return,
+                Err(e) => log::warn!("Failed to pass the link to the running app: {}", e),
+            }
+        }
+        Err(e) => log::warn!("Session bus is not available: {}", e),
+    }
+
+    if let Some(link) = startup_link {
+        commands.send(AppCommand::OpenUrl(link.url()));
+    }
+
+    let notifier = match Notifier::start(commands.clone()) {
+        Ok(notifier) => Some(notifier),
+        Err(e) => {
+            log::warn!("Notifications are not available: {}", e);
+            None
+        }
+    };
+    let integration = Integration {
+        commands: commands_receiver,
+        reading_state,
+        notifier,
+    };
+
     let viewport = egui::ViewportBuilder::default();
 
     #[cfg(feature = "aurora")]
-    run_aurora_app(viewport);
+    run_aurora_app(viewport, commands, integration);
 
     #[cfg(not(feature = "aurora"))]
-    run_desktop_app(viewport)
+    run_desktop_app(viewport, commands, integration)
+}
+
+/// Habr url or article id the app was started with.
+fn startup_link() -> Option<HabrLink> {
+    let arg = std::env::args().nth(1)?;
+    let link = HabrLink::from_arg(&arg);
+    if link.is_none() {
+        log::warn!("Unsupported link: {}", arg);
+    }
+    link
 }
 
 #[cfg(feature = "aurora")]
-fn run_aurora_app(viewport: egui::ViewportBuilder) {
+fn run_aurora_app(
+    viewport: egui::ViewportBuilder,
+    commands: CommandSender,
+    integration: Integration,
+) {
     let options = aurora_egui::NativeOptions {
         viewport: viewport,
         ..Default::default()
@@ -28,9 +85,10 @@ fn run_aurora_app(viewport: egui::ViewportBuilder) {
     let _ = aurora_egui::run_native(
         "Haboost",
         options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Box::<MyApp>::default()
+            commands.set_context(&cc.egui_ctx);
+            Box::new(MyApp::new(integration))
         }),
     );
 }
@@ -56,7 +114,11 @@ impl aurora_egui::App for MyApp {
                     .set_theme(self.state.borrow().settings.borrow().theme());
                 ui.spacing_mut().item_spacing = egui::Vec2::new(15., 15.);
 
+                self.handle_commands(ui);
                 self.view_stack.ui(ui);
+                self.popups_ui(ui);
+                self.update_reading_state();
+                self.update_poller();
             });
     }
     fn cover_ui(&mut self, ui: &mut egui::Ui) {
@@ -74,7 +136,11 @@ impl aurora_egui::App for MyApp {
 }
 
 #[cfg(not(feature = "aurora"))]
-fn run_desktop_app(viewport: egui::ViewportBuilder) {
+fn run_desktop_app(
+    viewport: egui::ViewportBuilder,
+    commands: CommandSender,
+    integration: Integration,
+) {
     let options = eframe::NativeOptions {
         viewport,
         ..Default::default()
@@ -82,9 +148,10 @@ fn run_desktop_app(viewport: egui::ViewportBuilder) {
     let _ = eframe::run_native(
         "Haboost",
         options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::<MyApp>::default())
+            commands.set_context(&cc.egui_ctx);
+            Ok(Box::new(MyApp::new(integration)))
         }),
     );
 }
@@ -107,7 +174,11 @@ impl eframe::App for MyApp {
                 .set_theme(self.state.borrow().settings.borrow().theme());
             ui.spacing_mut().item_spacing = egui::Vec2::new(15., 15.);
 
+            self.handle_commands(ui);
             self.view_stack.ui(ui);
+            self.popups_ui(ui);
+            self.update_reading_state();
+            self.update_poller();
         });
     }
 }
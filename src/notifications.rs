@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use dbus::arg::{PropMap, Variant};
+use dbus::blocking::Connection;
+use dbus::message::MatchRule;
+use tokio::task::JoinHandle;
+
+use crate::app::{AppCommand, CommandSender};
+use crate::habr_client::HabrClient;
+use crate::habr_client::article::{
+    ArticleData, ArticlesListFilter, ArticlesListSorting, ArticlesSearchSorting, PublicationType,
+};
+use crate::habr_client::links::HabrLink;
+use crate::habr_client::search::SearchTarget;
+use crate::storage::content_filter::ContentFilter;
+use crate::storage::feeds::Feeds;
+use crate::storage::notifications::KnownArticles;
+use crate::storage::searches::Searches;
+use crate::storage::subscriptions::Subscriptions;
+use crate::views::settings::Settings;
+
+const NOTIFICATIONS_BUS_NAME: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
+const DESKTOP_ENTRY: &str = "com.lmaxyz.Haboost";
+const CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Notifications shown after one check, the other new articles are skipped.
+const MAX_NOTIFICATIONS: usize = 5;
+
+pub struct NewArticle {
+    /// Hub or feed the article was found in.
+    pub source: String,
+    pub article: ArticleData,
+}
+
+/// Shows desktop notifications about new articles, activating one opens the article
+/// in the app.
+#[derive(Clone)]
+pub struct Notifier {
+    sender: mpsc::Sender<NewArticle>,
+}
+
+impl Notifier {
+    pub fn start(commands: CommandSender) -> Result<Notifier, dbus::Error> {
+        let conn = Connection::new_session()?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || run_notifier(conn, receiver, commands));
+        Ok(Notifier { sender })
+    }
+
+    pub fn notify(&self, new_article: NewArticle) {
+        let _ = self.sender.send(new_article);
+    }
+}
+
+fn run_notifier(conn: Connection, receiver: mpsc::Receiver<NewArticle>, commands: CommandSender) {
+    // Article ids by the ids of the shown notifications.
+    let shown: Arc<Mutex<HashMap<u32, String>>> = Default::default();
+
+    let actions_rule = MatchRule::new_signal(NOTIFICATIONS_INTERFACE, "ActionInvoked");
+    let result = conn.add_match(actions_rule, {
+        let shown = shown.clone();
+        move |(id, _action): (u32, String), _: &Connection, _: &dbus::Message| {
+            if let Some(article_id) = shown.lock().unwrap().remove(&id) {
+                commands.send(AppCommand::OpenUrl(HabrLink::Article(article_id).url()));
+            }
+            true
+        }
+    });
+    if let Err(e) = result {
+        log::warn!("Notification actions are not available: {}", e);
+    }
+
+    let closed_rule = MatchRule::new_signal(NOTIFICATIONS_INTERFACE, "NotificationClosed");
+    let result = conn.add_match(closed_rule, {
+        let shown = shown.clone();
+        move |(id, _reason): (u32, u32), _: &Connection, _: &dbus::Message| {
+            shown.lock().unwrap().remove(&id);
+            true
+        }
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to watch closed notifications: {}", e);
+    }
+
+    loop {
+        match receiver.recv_timeout(Duration::from_millis(500)) {
+            Ok(new_article) => match show_notification(&conn, &new_article) {
+                Ok(id) => {
+                    shown.lock().unwrap().insert(id, new_article.article.id);
+                }
+                Err(e) => log::warn!("Failed to show notification: {}", e),
+            },
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if let Err(e) = conn.process(Duration::ZERO) {
+            log::error!("Notifications connection is lost: {}", e);
+            break;
+        }
+    }
+}
+
+fn show_notification(conn: &Connection, new_article: &NewArticle) -> Result<u32, dbus::Error> {
+    let mut hints = PropMap::new();
+    hints.insert(
+        "desktop-entry".to_string(),
+        Variant(Box::new(DESKTOP_ENTRY.to_string())),
+    );
+    let actions = vec!["default", "Открыть"];
+    let summary = format!("Новое в «{}»", new_article.source);
+    let body = escape_markup(&new_article.article.title);
+
+    let (id,): (u32,) = conn
+        .with_proxy(NOTIFICATIONS_BUS_NAME, NOTIFICATIONS_PATH, CALL_TIMEOUT)
+        .method_call(
+            NOTIFICATIONS_INTERFACE,
+            "Notify",
+            (
+                "Haboost",
+                0u32,
+                DESKTOP_ENTRY,
+                summary,
+                body,
+                actions,
+                hints,
+                -1i32,
+            ),
+        )?;
+    Ok(id)
+}
+
+/// Notification bodies may be rendered as markup.
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Checks the subscribed hubs, pinned feeds and saved searches every `interval`
+/// and notifies about the articles which were not there before.
+pub fn spawn_poller(
+    async_handle: &tokio::runtime::Handle,
+    interval: Duration,
+    notifier: Notifier,
+) -> JoinHandle<()> {
+    async_handle.spawn(async move {
+        let client = HabrClient::new();
+        loop {
+            let new_articles = check_new_articles(&client).await;
+            if new_articles.len() > MAX_NOTIFICATIONS {
+                log::info!(
+                    "{} new articles found, only {} are shown",
+                    new_articles.len(),
+                    MAX_NOTIFICATIONS
+                );
+            }
+            for new_article in new_articles.into_iter().take(MAX_NOTIFICATIONS) {
+                notifier.notify(new_article);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+async fn check_new_articles(client: &HabrClient) -> Vec<NewArticle> {
+    let content_filter = Settings::read_from_file()
+        .unwrap_or_default()
+        .content_filter()
+        .clone();
+    let mut known = KnownArticles::load();
+    let mut sources = Vec::new();
+    let mut new_articles = Vec::new();
+
+    for hub in Subscriptions::load().hubs {
+        // Sources failed to load keep their known articles too.
+        let source = format!("hub:{}", hub.alias);
+        sources.push(source.clone());
+        let result = client
+            .get_articles(
+                hub.alias.clone(),
+                PublicationType::Article,
+                ArticlesListSorting::Newest,
+                ArticlesListFilter::ByRating(None),
+                None,
+                1,
+            )
+            .await;
+        match result {
+            Ok((articles, _)) => {
+                for article in known.update(&source, &articles) {
+                    add_new_article(&mut new_articles, &content_filter, &hub.title, article);
+                }
+            }
+            Err(e) => log::warn!("Failed to check hub {}: {}", hub.alias, e),
+        }
+    }
+
+    let feeds = Feeds::load()
+        .feeds
+        .into_iter()
+        .map(|f| (format!("feed:{}", f.id), f.name, f.query));
+    let searches = Searches::load()
+        .saved
+        .into_iter()
+        .map(|s| (format!("search:{}", s.name), s.name, s.query));
+    for (source, name, query) in feeds.chain(searches) {
+        if !matches!(query.target, SearchTarget::Articles | SearchTarget::Tags) {
+            continue;
+        }
+        sources.push(source.clone());
+        let query = query.sorting(ArticlesSearchSorting::Date);
+        match client.search_articles(&query, 1).await {
+            Ok((articles, _)) => {
+                for article in known.update(&source, &articles) {
+                    add_new_article(&mut new_articles, &content_filter, &name, article);
+                }
+            }
+            Err(e) => log::warn!("Failed to check feed {}: {}", name, e),
+        }
+    }
+
+    known.retain_sources(&sources);
+    known.save();
+    // Articles which weren't remembered would be notified again on every check.
+    if known.save_error.is_some() {
+        return vec![];
+    }
+    new_articles
+}
+
+fn add_new_article(
+    new_articles: &mut Vec<NewArticle>,
+    content_filter: &ContentFilter,
+    source: &str,
+    article: &ArticleData,
+) {
+    if content_filter.matched_rule(article).is_some()
+        || new_articles.iter().any(|n| n.article.id == article.id)
+    {
+        return;
+    }
+    new_articles.push(NewArticle {
+        source: source.to_string(),
+        article: article.clone(),
+    });
+}
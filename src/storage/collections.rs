@@ -0,0 +1,175 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ArticleStorage, read_json, write_json};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collections_and_tags() {
+        let mut collections = SavedCollections::default();
+        assert!(collections.add_collection(" Rust "));
+        assert!(!collections.add_collection("Rust"));
+        assert!(!collections.add_collection("  "));
+        assert!(collections.add_collection("Прочитать"));
+
+        collections.set_in_collection("1", "Rust", true);
+        collections.set_in_collection("2", "Rust", true);
+        collections.set_in_collection("2", "Прочитать", true);
+        collections.add_tag("1", " async ");
+        collections.add_tag("2", "Async");
+        collections.add_tag("2", "tokio");
+
+        assert!(collections.matches("1", &LabelFilter::All));
+        assert!(collections.matches("3", &LabelFilter::All));
+        assert!(collections.matches("2", &LabelFilter::Collection("Прочитать".to_string())));
+        assert!(!collections.matches("1", &LabelFilter::Collection("Прочитать".to_string())));
+        assert!(collections.matches("2", &LabelFilter::Tag("async".to_string())));
+        assert_eq!(
+            collections.all_tags().into_iter().collect::<Vec<_>>(),
+            ["async", "tokio"]
+        );
+
+        collections.remove_collection("Rust");
+        collections.remove_tag("2", "tokio");
+        collections.set_in_collection("2", "Прочитать", false);
+        assert_eq!(collections.collections, ["Прочитать"]);
+        assert!(collections.labels("2").collections.is_empty());
+        assert_eq!(collections.all_tags().len(), 1);
+
+        collections.remove_tag("1", "async");
+        collections.remove_tag("2", "async");
+        assert!(collections.articles.is_empty());
+    }
+}
+
+/// Collections and personal tags of a saved article.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleLabels {
+    #[serde(default)]
+    pub collections: BTreeSet<String>,
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+}
+
+impl ArticleLabels {
+    fn is_empty(&self) -> bool {
+        self.collections.is_empty() && self.tags.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum LabelFilter {
+    #[default]
+    All,
+    Collection(String),
+    Tag(String),
+}
+
+/// User defined collections and tags of the saved articles. Labels are kept when
+/// the article is deleted, so it gets them back if saved again.
+/// Stored in `collections.json` next to the saved articles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedCollections {
+    /// Names of the collections in the order they were created.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Labels by article ids.
+    #[serde(default)]
+    pub articles: BTreeMap<String, ArticleLabels>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl SavedCollections {
+    fn path() -> PathBuf {
+        ArticleStorage::base_path().join("collections.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save collections: {}", e);
+        }
+    }
+
+    /// Returns false if the name is empty or the collection already exists.
+    pub fn add_collection(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.collections.iter().any(|c| c == name) {
+            return false;
+        }
+        self.collections.push(name.to_string());
+        true
+    }
+
+    pub fn remove_collection(&mut self, name: &str) {
+        self.collections.retain(|c| c != name);
+        for labels in self.articles.values_mut() {
+            labels.collections.remove(name);
+        }
+        self.articles.retain(|_, labels| !labels.is_empty());
+    }
+
+    pub fn labels(&self, article_id: &str) -> ArticleLabels {
+        self.articles.get(article_id).cloned().unwrap_or_default()
+    }
+
+    pub fn set_in_collection(&mut self, article_id: &str, name: &str, included: bool) {
+        self.update_labels(article_id, |labels| {
+            if included {
+                labels.collections.insert(name.to_string());
+            } else {
+                labels.collections.remove(name);
+            }
+        });
+    }
+
+    /// Tags are trimmed and lowercased, so the same tag is not added twice.
+    pub fn add_tag(&mut self, article_id: &str, tag: &str) {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() {
+            self.update_labels(article_id, |labels| {
+                labels.tags.insert(tag);
+            });
+        }
+    }
+
+    pub fn remove_tag(&mut self, article_id: &str, tag: &str) {
+        self.update_labels(article_id, |labels| {
+            labels.tags.remove(tag);
+        });
+    }
+
+    pub fn all_tags(&self) -> BTreeSet<String> {
+        self.articles
+            .values()
+            .flat_map(|labels| labels.tags.iter().cloned())
+            .collect()
+    }
+
+    pub fn matches(&self, article_id: &str, filter: &LabelFilter) -> bool {
+        let labels = self.articles.get(article_id);
+        match filter {
+            LabelFilter::All => true,
+            LabelFilter::Collection(name) => labels.is_some_and(|l| l.collections.contains(name)),
+            LabelFilter::Tag(tag) => labels.is_some_and(|l| l.tags.contains(tag)),
+        }
+    }
+
+    fn update_labels(&mut self, article_id: &str, f: impl FnOnce(&mut ArticleLabels)) {
+        let labels = self.articles.entry(article_id.to_string()).or_default();
+        f(labels);
+        if labels.is_empty() {
+            self.articles.remove(article_id);
+        }
+    }
+}
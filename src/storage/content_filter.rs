@@ -0,0 +1,204 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::habr_client::article::ArticleData;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article() -> ArticleData {
+        ArticleData {
+            title: "Как мы переписали сервис на Rust".to_string(),
+            tags: vec!["Rust".to_string(), "Бэкенд".to_string()],
+            author: "SomeAuthor".to_string(),
+            hubs: vec!["rust".to_string()],
+            company: Some("big_company".to_string()),
+            ..test_article("1")
+        }
+    }
+
+    #[test]
+    fn test_rules_match_article() {
+        let article = article();
+        let rule = |kind, value: &str| {
+            let mut filter = ContentFilter::default();
+            filter.add_rule(kind, value).unwrap();
+            filter.matched_rule(&article).is_some()
+        };
+
+        assert!(rule(FilterKind::Author, "someauthor"));
+        assert!(rule(FilterKind::Hub, "Rust"));
+        assert!(rule(FilterKind::Tag, "бэкенд"));
+        assert!(rule(FilterKind::Company, "big_company"));
+        assert!(rule(FilterKind::Keyword, "ПЕРЕПИСАЛИ"));
+        assert!(rule(FilterKind::Regex, r"^как\s+мы"));
+
+        assert!(!rule(FilterKind::Author, "Some"));
+        assert!(!rule(FilterKind::Hub, "go"));
+        assert!(!rule(FilterKind::Keyword, "python"));
+        assert!(!rule(FilterKind::Regex, r"^rust"));
+    }
+
+    #[test]
+    fn test_invalid_and_duplicate_rules() {
+        let mut filter = ContentFilter::default();
+
+        assert!(filter.add_rule(FilterKind::Regex, "(").is_err());
+        assert!(filter.add_rule(FilterKind::Keyword, "  ").is_err());
+        assert!(filter.add_rule(FilterKind::Author, "user").is_ok());
+        assert!(filter.add_rule(FilterKind::Author, "User").is_ok());
+        assert_eq!(filter.rules().len(), 1);
+
+        filter.remove_rule(0);
+        assert!(filter.rules().is_empty());
+    }
+
+    #[test]
+    fn test_regexes_are_compiled_after_loading() {
+        let mut filter = ContentFilter::default();
+        filter.add_rule(FilterKind::Regex, "rust$").unwrap();
+
+        let json = serde_json::to_string(&filter).unwrap();
+        let mut loaded: ContentFilter = serde_json::from_str(&json).unwrap();
+        loaded.compile();
+
+        let mut article = article();
+        article.title = "Почему не Rust".to_string();
+        assert!(loaded.matched_rule(&article).is_some());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilterKind {
+    Author,
+    Hub,
+    Tag,
+    Company,
+    Keyword,
+    Regex,
+}
+
+impl FilterKind {
+    pub const ALL: [FilterKind; 6] = [
+        FilterKind::Author,
+        FilterKind::Hub,
+        FilterKind::Tag,
+        FilterKind::Company,
+        FilterKind::Keyword,
+        FilterKind::Regex,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            FilterKind::Author => "Автор",
+            FilterKind::Hub => "Хаб",
+            FilterKind::Tag => "Тег",
+            FilterKind::Company => "Блог компании",
+            FilterKind::Keyword => "Слово в заголовке",
+            FilterKind::Regex => "Регулярное выражение",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterRule {
+    pub kind: FilterKind,
+    pub value: String,
+}
+
+impl FilterRule {
+    pub fn description(&self) -> String {
+        format!("{}: {}", self.kind.title(), self.value)
+    }
+}
+
+/// Rules hiding articles from the lists. All text rules are case insensitive,
+/// regular expressions are matched against the title.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentFilter {
+    #[serde(default)]
+    rules: Vec<FilterRule>,
+    /// Show matched articles collapsed instead of hiding them.
+    #[serde(default)]
+    pub collapse: bool,
+    #[serde(skip)]
+    regexes: Vec<Option<Regex>>,
+}
+
+impl ContentFilter {
+    pub fn rules(&self) -> &[FilterRule] {
+        &self.rules
+    }
+
+    /// Has to be called after deserialization.
+    pub fn compile(&mut self) {
+        self.regexes = self
+            .rules
+            .iter()
+            .map(|rule| match rule.kind {
+                FilterKind::Regex => build_regex(&rule.value).ok(),
+                _ => None,
+            })
+            .collect();
+    }
+
+    pub fn add_rule(&mut self, kind: FilterKind, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("Пустое значение".to_string());
+        }
+        let regex = match kind {
+            FilterKind::Regex => Some(build_regex(value).map_err(|e| e.to_string())?),
+            _ => None,
+        };
+        let is_duplicate = self.rules.iter().any(|rule| {
+            rule.kind == kind
+                && (rule.value == value
+                    || (kind != FilterKind::Regex
+                        && rule.value.to_lowercase() == value.to_lowercase()))
+        });
+        if is_duplicate {
+            return Ok(());
+        }
+
+        self.rules.push(FilterRule {
+            kind,
+            value: value.to_string(),
+        });
+        self.regexes.push(regex);
+        Ok(())
+    }
+
+    pub fn remove_rule(&mut self, index: usize) {
+        if index < self.rules.len() {
+            self.rules.remove(index);
+            self.compile();
+        }
+    }
+
+    pub fn matched_rule(&self, article: &ArticleData) -> Option<&FilterRule> {
+        let equals = |a: &str, b: &str| a.to_lowercase() == b.to_lowercase();
+        self.rules.iter().enumerate().find_map(|(index, rule)| {
+            let value = rule.value.as_str();
+            let is_matched = match rule.kind {
+                FilterKind::Author => equals(&article.author, value),
+                FilterKind::Hub => article.hubs.iter().any(|h| equals(h, value)),
+                FilterKind::Tag => article.tags.iter().any(|t| equals(t, value)),
+                FilterKind::Company => article.company.as_deref().is_some_and(|c| equals(c, value)),
+                FilterKind::Keyword => article.title.to_lowercase().contains(&value.to_lowercase()),
+                FilterKind::Regex => self
+                    .regexes
+                    .get(index)
+                    .and_then(Option::as_ref)
+                    .is_some_and(|r| r.is_match(&article.title)),
+            };
+            is_matched.then_some(rule)
+        })
+    }
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
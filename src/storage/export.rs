@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ArticleStorage, documents_dir, write_atomic};
+use crate::habr_client::article::{ArticleContent, ArticleData};
+use crate::habr_client::html_parse::TypedText;
+use crate::habr_client::links::{HabrLink, absolute_url};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article(title: &str) -> ArticleData {
+        ArticleData {
+            title: title.to_string(),
+            tags: vec!["Rust".to_string(), "C++".to_string()],
+            author: "some_author".to_string(),
+            published_at: "25.01.2026 08:00".to_string(),
+            ..test_article("42")
+        }
+    }
+
+    fn markdown(block: ArticleContent) -> String {
+        block_to_markdown(&block, &HashMap::new())
+    }
+
+    fn html(block: ArticleContent) -> String {
+        block_to_html(&block, &HashMap::new())
+    }
+
+    fn paragraph(text: &str) -> ArticleContent {
+        ArticleContent::Paragraph(vec![TypedText::Common(text.to_string())])
+    }
+
+    #[test]
+    fn test_header() {
+        let header = || ArticleContent::Header(2, "Введение в <Rust>".to_string());
+        assert_eq!(markdown(header()), "## Введение в \\<Rust\\>");
+        assert_eq!(html(header()), "<h2>Введение в &lt;Rust&gt;</h2>");
+
+        let header = || ArticleContent::Header(9, "Глубоко".to_string());
+        assert_eq!(markdown(header()), "###### Глубоко");
+        assert_eq!(html(header()), "<h6>Глубоко</h6>");
+    }
+
+    #[test]
+    fn test_paragraph() {
+        let paragraph = || {
+            ArticleContent::Paragraph(vec![
+                TypedText::Common("Вызов ".to_string()),
+                TypedText::Code("a`b".to_string()),
+                TypedText::Common(", ".to_string()),
+                TypedText::Italic("курсив ".to_string()),
+                TypedText::Strong("жирный".to_string()),
+                TypedText::Common(" и ".to_string()),
+                TypedText::Link {
+                    url: "https://example.com/a b".to_string(),
+                    value: "ссылка [1]".to_string(),
+                },
+                TypedText::Common(" snake_case & *".to_string()),
+            ])
+        };
+        assert_eq!(
+            markdown(paragraph()),
+            "Вызов ``a`b``, *курсив* **жирный** и \
+             [ссылка \\[1\\]](https://example.com/a%20b) snake\\_case & \\*"
+        );
+        assert_eq!(
+            html(paragraph()),
+            "<p>Вызов <code>a`b</code>, <em>курсив </em><strong>жирный</strong> и \
+             <a href=\"https://example.com/a%20b\">ссылка [1]</a> snake_case &amp; *</p>"
+        );
+    }
+
+    #[test]
+    fn test_line_start_markers() {
+        assert_eq!(markdown(paragraph("# не заголовок")), "\\# не заголовок");
+        assert_eq!(markdown(paragraph("- не список")), "\\- не список");
+        assert_eq!(markdown(paragraph("+1")), "\\+1");
+        assert_eq!(markdown(paragraph("2024. Итоги")), "2024\\. Итоги");
+        assert_eq!(markdown(paragraph("a - b, 1.5")), "a - b, 1.5");
+        assert_eq!(
+            markdown(ArticleContent::Blockquote("1) раз\n  - два".to_string())),
+            "> 1\\) раз\n> \\- два"
+        );
+    }
+
+    #[test]
+    fn test_relative_links() {
+        let link = || {
+            ArticleContent::Paragraph(vec![TypedText::Link {
+                url: "/ru/articles/1/".to_string(),
+                value: "статья".to_string(),
+            }])
+        };
+        assert_eq!(
+            markdown(link()),
+            "[статья](https://habr.com/ru/articles/1/)"
+        );
+        assert_eq!(
+            html(link()),
+            "<p><a href=\"https://habr.com/ru/articles/1/\">статья</a></p>"
+        );
+    }
+
+    #[test]
+    fn test_code() {
+        let code = || ArticleContent::Code {
+            lang: "rust".to_string(),
+            content: "let s = \"```\";\nif a < b {}\n".to_string(),
+        };
+        assert_eq!(
+            markdown(code()),
+            "````rust\nlet s = \"```\";\nif a < b {}\n````"
+        );
+        assert_eq!(
+            html(code()),
+            "<pre><code class=\"language-rust\">let s = &quot;```&quot;;\nif a &lt; b {}</code></pre>"
+        );
+
+        let code = || ArticleContent::Code {
+            lang: String::new(),
+            content: "ls -la".to_string(),
+        };
+        assert_eq!(markdown(code()), "```\nls -la\n```");
+        assert_eq!(html(code()), "<pre><code>ls -la</code></pre>");
+    }
+
+    #[test]
+    fn test_blockquote() {
+        let quote = || ArticleContent::Blockquote("Первая строка\n\nВторая *строка*".to_string());
+        assert_eq!(
+            markdown(quote()),
+            "> Первая строка\n>\n> Вторая \\*строка\\*"
+        );
+        assert_eq!(
+            html(quote()),
+            "<blockquote>Первая строка<br>\n<br>\nВторая *строка*</blockquote>"
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let unordered = || {
+            ArticleContent::UnorderedList(vec![
+                paragraph("Первый"),
+                ArticleContent::OrderedList(vec![paragraph("Вложенный"), paragraph("Ещё")]),
+            ])
+        };
+        assert_eq!(markdown(unordered()), "- Первый\n- 1. Вложенный\n  2. Ещё");
+        assert_eq!(
+            html(unordered()),
+            "<ul>\n<li>Первый</li>\n<li><ol>\n<li>Вложенный</li>\n<li>Ещё</li>\n</ol></li>\n</ul>"
+        );
+
+        let ordered = || ArticleContent::OrderedList(vec![paragraph("Один"), paragraph("Два")]);
+        assert_eq!(markdown(ordered()), "1. Один\n2. Два");
+        assert_eq!(html(ordered()), "<ol>\n<li>Один</li>\n<li>Два</li>\n</ol>");
+    }
+
+    #[test]
+    fn test_image() {
+        let images = HashMap::from([(
+            "file:///data/images/abc.png".to_string(),
+            "images/abc.png".to_string(),
+        )]);
+        let local = ArticleContent::Image("file:///data/images/abc.png".to_string());
+        let remote = ArticleContent::Image("https://habrastorage.org/x.png".to_string());
+
+        assert_eq!(block_to_markdown(&local, &images), "![](images/abc.png)");
+        assert_eq!(
+            block_to_markdown(&remote, &images),
+            "![](https://habrastorage.org/x.png)"
+        );
+        assert_eq!(
+            block_to_html(&local, &images),
+            "<p><img src=\"images/abc.png\" alt=\"\"></p>"
+        );
+    }
+
+    #[test]
+    fn test_text_and_br() {
+        let text = || ArticleContent::Text(TypedText::Strong("Важно".to_string()));
+        assert_eq!(markdown(text()), "**Важно**");
+        assert_eq!(html(text()), "<p><strong>Важно</strong></p>");
+        assert_eq!(markdown(ArticleContent::BR), "");
+        assert_eq!(html(ArticleContent::BR), "<br>");
+    }
+
+    #[test]
+    fn test_documents() {
+        let content = [
+            paragraph("Текст"),
+            ArticleContent::BR,
+            ArticleContent::Header(2, "Итог".to_string()),
+        ];
+
+        assert_eq!(
+            to_markdown(&article("Статья про Rust"), &content, &HashMap::new()),
+            "# Статья про Rust\n\n\
+             some\\_author · 25.01.2026 08:00 · [Оригинал](https://habr.com/ru/articles/42/)\n\n\
+             Текст\n\n\
+             ## Итог\n\n\
+             Теги: Rust, C++\n"
+        );
+
+        let html = to_html(&article("A & B"), &content, &HashMap::new());
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"ru\">"));
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert!(html.contains(
+            "<article>\n<h1>A &amp; B</h1>\n\
+             <p class=\"meta\">some_author · 25.01.2026 08:00 · \
+             <a href=\"https://habr.com/ru/articles/42/\">Оригинал</a></p>\n\
+             <p>Текст</p>\n<br>\n<h2>Итог</h2>\n\
+             <p class=\"tags\">Теги: Rust, C++</p>\n</article>"
+        ));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
+
+const HTML_STYLE: &str = "\
+body { max-width: 50em; margin: 0 auto; padding: 1em; font-family: sans-serif; line-height: 1.5; }
+img { max-width: 100%; }
+pre { overflow-x: auto; padding: 0.5em; background: #f4f4f4; }
+blockquote { margin-left: 0; padding-left: 1em; border-left: 3px solid #ccc; color: #555; }
+.meta, .tags { color: #777; }
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Writes the article to `articles/<id>` of the documents dir. The images saved with
+/// the article are copied next to it, the other ones are linked by their urls.
+/// Returns the path of the written file.
+pub fn export_article(
+    article: &ArticleData,
+    content: &[ArticleContent],
+    format: ExportFormat,
+) -> Result<PathBuf, String> {
+    let dir = documents_dir().join("articles").join(&article.id);
+    let images = copy_images(content, &dir.join("images"));
+    let document = match format {
+        ExportFormat::Markdown => to_markdown(article, content, &images),
+        ExportFormat::Html => to_html(article, content, &images),
+    };
+    let path = dir.join(format!("article.{}", format.extension()));
+    write_atomic(&path, document).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Copies the local images of the content, returns the relative paths of the copies
+/// by the image urls.
+fn copy_images(content: &[ArticleContent], images_dir: &Path) -> HashMap<String, String> {
+    let mut links = HashMap::new();
+    for url in ArticleStorage::collect_image_urls(content) {
+        let Some(source) = url.strip_prefix("file://").map(Path::new) else {
+            continue;
+        };
+        let Some(file_name) = source.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let copied = fs::create_dir_all(images_dir)
+            .and_then(|_| fs::copy(source, images_dir.join(&file_name)));
+        match copied {
+            Ok(_) => {
+                links.insert(url, format!("images/{}", file_name));
+            }
+            Err(e) => log::warn!("Failed to copy image {}: {}", url, e),
+        }
+    }
+    links
+}
+
+fn image_link<'a>(url: &'a str, images: &'a HashMap<String, String>) -> &'a str {
+    images.get(url).map_or(url, |link| link.as_str())
+}
+
+pub fn to_markdown(
+    article: &ArticleData,
+    content: &[ArticleContent],
+    images: &HashMap<String, String>,
+) -> String {
+    let mut blocks = Vec::new();
+    // Posts have no title.
+    if !article.title.is_empty() {
+        blocks.push(format!("# {}", escape_markdown(&article.title)));
+    }
+    blocks.push(format!(
+        "{} · {} · [Оригинал]({})",
+        escape_markdown(&article.author),
+        article.published_at,
+        HabrLink::Article(article.id.clone()).url()
+    ));
+    blocks.extend(
+        content
+            .iter()
+            .map(|block| block_to_markdown(block, images))
+            .filter(|block| !block.is_empty()),
+    );
+    if !article.tags.is_empty() {
+        blocks.push(format!(
+            "Теги: {}",
+            escape_markdown(&article.tags.join(", "))
+        ));
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+fn block_to_markdown(block: &ArticleContent, images: &HashMap<String, String>) -> String {
+    match block {
+        ArticleContent::Header(level, text) => {
+            format!(
+                "{} {}",
+                "#".repeat((*level).clamp(1, 6) as usize),
+                escape_markdown(text)
+            )
+        }
+        ArticleContent::Paragraph(texts) => texts.iter().map(inline_to_markdown).collect(),
+        ArticleContent::Text(text) => inline_to_markdown(text),
+        ArticleContent::Code { lang, content } => {
+            let fence = "`".repeat(longest_run(content, '`').max(2) + 1);
+            format!(
+                "{}{}\n{}\n{}",
+                fence,
+                lang,
+                content.trim_end_matches('\n'),
+                fence
+            )
+        }
+        ArticleContent::Blockquote(text) => text
+            .lines()
+            .map(|line| match line.trim() {
+                "" => ">".to_string(),
+                line => format!("> {}", escape_markdown(line)),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ArticleContent::UnorderedList(items) => items
+            .iter()
+            .map(|item| list_item_to_markdown("- ", item, images))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ArticleContent::OrderedList(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| list_item_to_markdown(&format!("{}. ", i + 1), item, images))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ArticleContent::Image(url) => format!("![]({})", image_link(url, images)),
+        ArticleContent::BR => String::new(),
+    }
+}
+
+/// Lines of the item after the first one are indented to stay in the item.
+fn list_item_to_markdown(
+    marker: &str,
+    item: &ArticleContent,
+    images: &HashMap<String, String>,
+) -> String {
+    let indent = " ".repeat(marker.chars().count());
+    let text = block_to_markdown(item, images);
+    let mut lines = text.lines();
+    let mut markdown = format!("{}{}", marker, lines.next().unwrap_or_default());
+    for line in lines {
+        markdown.push('\n');
+        if !line.is_empty() {
+            markdown.push_str(&indent);
+            markdown.push_str(line);
+        }
+    }
+    markdown
+}
+
+fn inline_to_markdown(text: &TypedText) -> String {
+    match text {
+        TypedText::Common(text) => escape_markdown(text),
+        TypedText::Code(code) => {
+            let fence = "`".repeat(longest_run(code, '`') + 1);
+            if code.starts_with('`') || code.ends_with('`') {
+                format!("{} {} {}", fence, code, fence)
+            } else {
+                format!("{}{}{}", fence, code, fence)
+            }
+        }
+        TypedText::Link { url, value } => format!(
+            "[{}]({})",
+            escape_markdown(value),
+            link_url(url).replace(' ', "%20").replace(')', "%29")
+        ),
+        TypedText::Italic(text) => emphasize(text, "*"),
+        TypedText::Strong(text) => emphasize(text, "**"),
+    }
+}
+
+/// Spaces around the emphasized text are kept outside the markers, otherwise
+/// the emphasis is not recognized.
+fn emphasize(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + trimmed.len();
+    format!(
+        "{}{}{}{}{}",
+        &text[..start],
+        marker,
+        escape_markdown(trimmed),
+        marker,
+        &text[end..]
+    )
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.split('\n')
+        .map(escape_markdown_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Headers and lists markers have a meaning only at the line start, like `# `, `- ` or `1. `.
+fn escape_markdown_line(line: &str) -> String {
+    let text = line.trim_start();
+    let mut escaped = line[..line.len() - text.len()].to_string();
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let marker_at = if text.starts_with(['#', '-', '+']) {
+        Some(0)
+    } else if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        Some(digits)
+    } else {
+        None
+    };
+    for (i, c) in text.char_indices() {
+        if Some(i) == marker_at || matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Links inside the articles may be relative to habr.com.
+fn link_url(url: &str) -> String {
+    absolute_url(url).unwrap_or_else(|| url.to_string())
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|ch| ch != c)
+        .map(|run| run.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Standalone HTML page with the article.
+pub fn to_html(
+    article: &ArticleData,
+    content: &[ArticleContent],
+    images: &HashMap<String, String>,
+) -> String {
+    let mut body = String::new();
+    if !article.title.is_empty() {
+        body.push_str(&format!("<h1>{}</h1>\n", escape_html(&article.title)));
+    }
+    body.push_str(&format!(
+        "<p class=\"meta\">{} · {} · <a href=\"{}\">Оригинал</a></p>\n",
+        escape_html(&article.author),
+        escape_html(&article.published_at),
+        HabrLink::Article(article.id.clone()).url()
+    ));
+    for block in content {
+        body.push_str(&block_to_html(block, images));
+        body.push('\n');
+    }
+    if !article.tags.is_empty() {
+        body.push_str(&format!(
+            "<p class=\"tags\">Теги: {}</p>\n",
+            escape_html(&article.tags.join(", "))
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"ru\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>\n{}</style>\n</head>\n\
+         <body>\n<article>\n{}</article>\n</body>\n</html>\n",
+        escape_html(&article.title),
+        HTML_STYLE,
+        body
+    )
+}
+
+fn block_to_html(block: &ArticleContent, images: &HashMap<String, String>) -> String {
+    match block {
+        ArticleContent::Header(level, text) => {
+            let level = (*level).clamp(1, 6);
+            format!("<h{}>{}</h{}>", level, escape_html(text), level)
+        }
+        ArticleContent::Paragraph(texts) => {
+            let text: String = texts.iter().map(inline_to_html).collect();
+            format!("<p>{}</p>", text)
+        }
+        ArticleContent::Text(text) => format!("<p>{}</p>", inline_to_html(text)),
+        ArticleContent::Code { lang, content } => {
+            let class = if lang.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(lang))
+            };
+            format!(
+                "<pre><code{}>{}</code></pre>",
+                class,
+                escape_html(content.trim_end_matches('\n'))
+            )
+        }
+        ArticleContent::Blockquote(text) => format!(
+            "<blockquote>{}</blockquote>",
+            text.lines()
+                .map(escape_html)
+                .collect::<Vec<_>>()
+                .join("<br>\n")
+        ),
+        ArticleContent::UnorderedList(items) => {
+            format!("<ul>\n{}</ul>", list_items_to_html(items, images))
+        }
+        ArticleContent::OrderedList(items) => {
+            format!("<ol>\n{}</ol>", list_items_to_html(items, images))
+        }
+        ArticleContent::Image(url) => format!(
+            "<p><img src=\"{}\" alt=\"\"></p>",
+            escape_html(image_link(url, images))
+        ),
+        ArticleContent::BR => "<br>".to_string(),
+    }
+}
+
+/// Paragraphs are not wrapped into `<p>` inside the items.
+fn list_items_to_html(items: &[ArticleContent], images: &HashMap<String, String>) -> String {
+    let mut html = String::new();
+    for item in items {
+        let item = match item {
+            ArticleContent::Paragraph(texts) => texts.iter().map(inline_to_html).collect(),
+            ArticleContent::Text(text) => inline_to_html(text),
+            block => block_to_html(block, images),
+        };
+        html.push_str(&format!("<li>{}</li>\n", item));
+    }
+    html
+}
+
+fn inline_to_html(text: &TypedText) -> String {
+    match text {
+        TypedText::Common(text) => escape_html(text),
+        TypedText::Code(code) => format!("<code>{}</code>", escape_html(code)),
+        TypedText::Link { url, value } => {
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&link_url(url)),
+                escape_html(value)
+            )
+        }
+        TypedText::Italic(text) => format!("<em>{}</em>", escape_html(text)),
+        TypedText::Strong(text) => format!("<strong>{}</strong>", escape_html(text)),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
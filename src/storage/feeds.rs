@@ -0,0 +1,121 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::search::SearchQuery;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unseen_articles_count() {
+        let mut feeds = Feeds::default();
+        let id = feeds.add("Rust".to_string(), SearchQuery::new("rust"));
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(feeds.get(&id).unwrap().unseen_count(&ids(&["1", "2"])), 2);
+
+        feeds.mark_seen(&id, ids(&["1", "2"]));
+        let feed = feeds.get(&id).unwrap();
+        assert_eq!(feed.unseen_count(&ids(&["3", "1", "2"])), 1);
+        assert_eq!(feed.unseen_count(&ids(&["1"])), 0);
+    }
+
+    #[test]
+    fn test_same_query_is_pinned_once() {
+        let mut feeds = Feeds::default();
+        let first = feeds.add("Rust".to_string(), SearchQuery::new("rust"));
+        let second = feeds.add("Rust again".to_string(), SearchQuery::new("rust"));
+
+        assert_eq!(first, second);
+        assert_eq!(feeds.feeds.len(), 1);
+    }
+}
+
+/// Search pinned on the home screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Feed {
+    pub id: String,
+    pub name: String,
+    pub query: SearchQuery,
+    /// Ids of the first page of results the last time the feed was opened.
+    #[serde(default)]
+    pub seen_ids: BTreeSet<String>,
+}
+
+impl Feed {
+    pub fn unseen_count(&self, article_ids: &[String]) -> usize {
+        article_ids
+            .iter()
+            .filter(|id| !self.seen_ids.contains(*id))
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Feeds {
+    #[serde(default)]
+    pub feeds: Vec<Feed>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl Feeds {
+    fn path() -> PathBuf {
+        app_data_dir().join("feeds.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save feeds: {}", e);
+        }
+    }
+
+    pub fn get(&self, feed_id: &str) -> Option<&Feed> {
+        self.feeds.iter().find(|f| f.id == feed_id)
+    }
+
+    pub fn find_by_query(&self, query: &SearchQuery) -> Option<&Feed> {
+        self.feeds.iter().find(|f| f.query == *query)
+    }
+
+    /// Pins the query and returns id of its feed.
+    pub fn add(&mut self, name: String, query: SearchQuery) -> String {
+        if let Some(feed) = self.find_by_query(&query) {
+            return feed.id.clone();
+        }
+        let id = chrono::Local::now().timestamp_millis().to_string();
+        self.feeds.push(Feed {
+            id: id.clone(),
+            name,
+            query,
+            seen_ids: BTreeSet::new(),
+        });
+        id
+    }
+
+    pub fn remove(&mut self, feed_id: &str) {
+        self.feeds.retain(|f| f.id != feed_id);
+    }
+
+    /// Returns true if the seen ids were changed.
+    pub fn mark_seen(&mut self, feed_id: &str, article_ids: Vec<String>) -> bool {
+        let Some(feed) = self.feeds.iter_mut().find(|f| f.id == feed_id) else {
+            return false;
+        };
+        let seen_ids = article_ids.into_iter().collect();
+        if feed.seen_ids == seen_ids {
+            return false;
+        }
+        feed.seen_ids = seen_ids;
+        true
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::habr_client::article::{ArticleContent, ArticleData};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+    use crate::habr_client::html_parse::TypedText;
+
+    fn article(id: &str, title: &str, tags: &[&str], author: &str) -> ArticleData {
+        ArticleData {
+            title: title.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            author: author.to_string(),
+            ..test_article(id)
+        }
+    }
+
+    fn paragraph(text: &str) -> ArticleContent {
+        ArticleContent::Paragraph(vec![TypedText::Common(text.to_string())])
+    }
+
+    fn ids(results: Vec<SearchResult>) -> Vec<String> {
+        results.into_iter().map(|r| r.article_id).collect()
+    }
+
+    fn test_index() -> FullTextIndex {
+        let mut index = FullTextIndex::default();
+        index.add(
+            &article("1", "Асинхронный Rust", &["Rust", "Tokio"], "ferris"),
+            &[
+                paragraph("Разберём, как работают футуры и исполнители."),
+                ArticleContent::Code {
+                    lang: "rust".to_string(),
+                    content: "tokio::spawn(async { handle_request().await });".to_string(),
+                },
+            ],
+        );
+        index.add(
+            &article("2", "Ёлки на Python", &["Python"], "guido"),
+            &[
+                paragraph("Статья о том, как рисовать ёлки. Немного про Rust в конце."),
+                ArticleContent::UnorderedList(vec![paragraph("Первая ёлка"), paragraph("Вторая")]),
+            ],
+        );
+        index.add(
+            &article("3", "Заметки о статьях", &[], "author"),
+            &[
+                ArticleContent::Header(2, "Статьи про базы данных".to_string()),
+                ArticleContent::Blockquote("Хорошие статьи пишут редко".to_string()),
+            ],
+        );
+        index
+    }
+
+    #[test]
+    fn test_russian_stemming() {
+        for word in ["статья", "статьи", "статей", "статьям", "Статьях"]
+        {
+            assert_eq!(stem(&normalize(word)), "стат", "{}", word);
+        }
+        assert_eq!(stem("программирование"), "программирован");
+        assert_eq!(stem("программирования"), "программирован");
+        assert_eq!(stem("быстрый"), stem("быстрая"));
+        assert_eq!(stem("работает"), stem("работать"));
+        assert_eq!(stem(&normalize("ёлки")), stem(&normalize("ёлка")));
+        assert_eq!(normalize("ЁЛКИ"), "елки");
+    }
+
+    #[test]
+    fn test_english_stemming() {
+        assert_eq!(stem("requests"), "request");
+        assert_eq!(stem("testing"), "test");
+        assert_eq!(stem("tested"), "test");
+        assert_eq!(stem("libraries"), "library");
+        assert_eq!(stem("class"), "class");
+        assert_eq!(stem("42"), "42");
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens: Vec<String> = tokenize("Tokio::spawn(handle_request) и Ёжик, v2").collect();
+        assert_eq!(
+            tokens,
+            ["tokio", "spawn", "handle", "request", "ежик", "v2"]
+        );
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let index = test_index();
+
+        // Title and tags weigh more than a mention in the text.
+        assert_eq!(ids(index.search("rust")), ["1", "2"]);
+        // Different forms of the same word.
+        assert_eq!(ids(index.search("статья")), ["3", "2"]);
+        assert_eq!(ids(index.search("ЕЛКА")), ["2"]);
+        // Code blocks and authors are indexed.
+        assert_eq!(ids(index.search("spawn")), ["1"]);
+        assert_eq!(ids(index.search("guido")), ["2"]);
+        // All words of the query must be found.
+        assert_eq!(ids(index.search("rust ёлки")), ["2"]);
+        assert!(index.search("rust базы").is_empty());
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn test_search_by_prefix() {
+        let index = test_index();
+        assert_eq!(ids(index.search("асинх")), ["1"]);
+        // Only the last word is a prefix.
+        assert!(index.search("асинх rust").is_empty());
+        // Too short prefixes match nothing but whole words.
+        assert!(index.search("ас").is_empty());
+    }
+
+    #[test]
+    fn test_remove_and_update() {
+        let mut index = test_index();
+        index.remove("2");
+        assert_eq!(ids(index.search("rust")), ["1"]);
+        assert!(index.search("ёлки").is_empty());
+        assert!(!index.postings.values().any(|p| p.contains_key("2")));
+
+        index.add(&article("1", "Новое название", &[], "ferris"), &[]);
+        assert!(index.search("асинхронный").is_empty());
+        assert_eq!(ids(index.search("названия")), ["1"]);
+        assert_eq!(index.documents.len(), 2);
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = "Сначала длинное вступление про что-то другое. \
+                    Потом рассказ о статьях на Хабре и\nвсё остальное.";
+        let found = snippet(text, "статья", 20).unwrap();
+        assert_eq!(found.matched, "статьях");
+        assert_eq!(found.before, "…ое. Потом рассказ о ");
+        assert_eq!(found.after, " на Хабре и всё остальное.");
+
+        let found = snippet("Хабр", "хабр", 20).unwrap();
+        assert_eq!((found.before.as_str(), found.after.as_str()), ("", ""));
+        assert!(snippet(text, "python", 20).is_none());
+
+        let index = test_index();
+        let found = index.snippet("2", "вторая").unwrap();
+        assert_eq!(found.matched, "Вторая");
+        assert!(found.before.ends_with("Первая ёлка "));
+        assert!(index.snippet("2", "футуры").is_none());
+    }
+
+    #[test]
+    fn test_failed_articles() {
+        let mut index = test_index();
+        index.add_failed("4");
+        index.add_failed("1");
+        let known: Vec<&String> = index.known_article_ids().collect();
+        assert_eq!(known, ["2", "3", "1", "4"]);
+        assert!(index.search("rust").iter().all(|r| r.article_id != "1"));
+        assert!(index.snippet("1", "футуры").is_none());
+
+        index.add(&article("4", "Исправленная", &[], "author"), &[]);
+        index.remove("1");
+        let known: Vec<&String> = index.known_article_ids().collect();
+        assert_eq!(known, ["2", "3", "4"]);
+    }
+}
+
+const FULLTEXT_INDEX_VERSION: u32 = 2;
+
+/// Words longer than this are not indexed, usually they are hashes or encoded data.
+const MAX_WORD_CHARS: usize = 40;
+/// The last word of the query matches the words starting with it, if it's not shorter.
+const MIN_PREFIX_CHARS: usize = 3;
+/// Characters of the snippets before the matched word, twice more are after it.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+const TITLE_WEIGHT: f32 = 5.;
+const TAGS_WEIGHT: f32 = 3.;
+const AUTHOR_WEIGHT: f32 = 3.;
+const CONTENT_WEIGHT: f32 = 1.;
+
+// BM25 parameters.
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub article_id: String,
+    pub score: f32,
+}
+
+/// Text around the first word matching the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+}
+
+/// Inverted index of the saved articles titles, tags, authors and content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullTextIndex {
+    #[serde(default)]
+    version: u32,
+    /// Lengths of the indexed articles in words, by article ids.
+    #[serde(default)]
+    documents: BTreeMap<String, u32>,
+    /// Weighted frequencies of the stems by article ids.
+    #[serde(default)]
+    postings: BTreeMap<String, BTreeMap<String, f32>>,
+    /// Plain text of the articles content for the snippets.
+    #[serde(default)]
+    texts: BTreeMap<String, String>,
+    /// Saved articles which failed to be read, they are not retried until saved again.
+    #[serde(default)]
+    failed: BTreeSet<String>,
+}
+
+impl Default for FullTextIndex {
+    fn default() -> Self {
+        Self {
+            version: FULLTEXT_INDEX_VERSION,
+            documents: BTreeMap::new(),
+            postings: BTreeMap::new(),
+            texts: BTreeMap::new(),
+            failed: BTreeSet::new(),
+        }
+    }
+}
+
+impl FullTextIndex {
+    /// False if the index was made by another version of the app.
+    pub fn is_current(&self) -> bool {
+        self.version == FULLTEXT_INDEX_VERSION
+    }
+
+    /// Ids of the indexed articles and of the ones which failed to be indexed.
+    pub fn known_article_ids(&self) -> impl Iterator<Item = &String> {
+        self.documents.keys().chain(self.failed.iter())
+    }
+
+    /// Replaces the indexed article if it's indexed already.
+    pub fn add(&mut self, article: &ArticleData, content: &[ArticleContent]) {
+        self.remove(&article.id);
+
+        let mut frequencies: HashMap<String, f32> = HashMap::new();
+        let mut length = 0;
+        let mut add_text = |text: &str, weight: f32| {
+            for word in tokenize(text) {
+                *frequencies.entry(stem(&word)).or_default() += weight;
+                length += 1;
+            }
+        };
+        add_text(&article.title, TITLE_WEIGHT);
+        add_text(&article.author, AUTHOR_WEIGHT);
+        for tag in article.tags.iter() {
+            add_text(tag, TAGS_WEIGHT);
+        }
+        let text = content
+            .iter()
+            .map(|block| block.plain_text())
+            .collect::<Vec<_>>()
+            .join("\n");
+        add_text(&text, CONTENT_WEIGHT);
+
+        for (term, frequency) in frequencies {
+            self.postings
+                .entry(term)
+                .or_default()
+                .insert(article.id.clone(), frequency);
+        }
+        self.documents.insert(article.id.clone(), length);
+        self.texts.insert(article.id.clone(), text);
+    }
+
+    pub fn add_failed(&mut self, article_id: &str) {
+        self.remove(article_id);
+        self.failed.insert(article_id.to_string());
+    }
+
+    pub fn remove(&mut self, article_id: &str) {
+        self.failed.remove(article_id);
+        self.texts.remove(article_id);
+        if self.documents.remove(article_id).is_none() {
+            return;
+        }
+        self.postings.retain(|_, articles| {
+            articles.remove(article_id);
+            !articles.is_empty()
+        });
+    }
+
+    /// Text of the indexed article around the first word matching the query.
+    pub fn snippet(&self, article_id: &str, query: &str) -> Option<Snippet> {
+        snippet(self.texts.get(article_id)?, query, SNIPPET_CONTEXT_CHARS)
+    }
+
+    /// Articles containing all words of the query, the most relevant first.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let terms = QueryTerm::parse(query);
+        if terms.is_empty() || self.documents.is_empty() {
+            return vec![];
+        }
+
+        let documents_count = self.documents.len() as f32;
+        let average_length =
+            self.documents.values().map(|l| *l as f32).sum::<f32>() / documents_count;
+
+        let mut scores: HashMap<&str, f32> = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            // Prefixes may match several stems, the best one counts.
+            let mut term_scores: HashMap<&str, f32> = HashMap::new();
+            for articles in self.matching_postings(term) {
+                let frequency = articles.len() as f32;
+                let idf = (1. + (documents_count - frequency + 0.5) / (frequency + 0.5)).ln();
+                for (article_id, tf) in articles {
+                    let length = self.documents.get(article_id).copied().unwrap_or(0) as f32;
+                    let norm = K1 * (1. - B + B * length / average_length.max(1.));
+                    let score = idf * tf * (K1 + 1.) / (tf + norm);
+                    let best = term_scores.entry(article_id.as_str()).or_default();
+                    *best = best.max(score);
+                }
+            }
+
+            if i == 0 {
+                scores = term_scores;
+            } else {
+                scores.retain(|article_id, score| match term_scores.get(article_id) {
+                    Some(term_score) => {
+                        *score += term_score;
+                        true
+                    }
+                    None => false,
+                });
+            }
+        }
+
+        let mut results: Vec<SearchResult> = scores
+            .into_iter()
+            .map(|(article_id, score)| SearchResult {
+                article_id: article_id.to_string(),
+                score,
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.article_id.cmp(&b.article_id))
+        });
+        results
+    }
+
+    fn matching_postings<'a>(
+        &'a self,
+        term: &'a QueryTerm,
+    ) -> impl Iterator<Item = &'a BTreeMap<String, f32>> {
+        let exact = self.postings.get(&term.stem);
+        let prefixed = term.prefix.iter().flat_map(move |prefix| {
+            self.postings
+                .range(prefix.clone()..)
+                .take_while(move |(stem, _)| stem.starts_with(prefix.as_str()))
+                .filter(move |(stem, _)| **stem != term.stem)
+                .map(|(_, articles)| articles)
+        });
+        exact.into_iter().chain(prefixed)
+    }
+}
+
+struct QueryTerm {
+    stem: String,
+    /// Set for the last word, which may be not typed completely.
+    prefix: Option<String>,
+}
+
+impl QueryTerm {
+    fn parse(query: &str) -> Vec<QueryTerm> {
+        let words: Vec<String> = tokenize(query).collect();
+        let last = words.len().saturating_sub(1);
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| QueryTerm {
+                stem: stem(&word),
+                prefix: (i == last && word.chars().count() >= MIN_PREFIX_CHARS).then_some(word),
+            })
+            .collect()
+    }
+
+    fn matches(&self, word: &str) -> bool {
+        stem(word) == self.stem || self.prefix.as_ref().is_some_and(|p| word.starts_with(p))
+    }
+}
+
+/// Returns the text around the first word matching the query, `context` is the
+/// number of characters kept before the word, twice more is kept after it.
+fn snippet(text: &str, query: &str, context: usize) -> Option<Snippet> {
+    let terms = QueryTerm::parse(query);
+    let (start, end) = words(text)
+        .find(|(_, _, word)| {
+            let word = normalize(word);
+            terms.iter().any(|term| term.matches(&word))
+        })
+        .map(|(start, end, _)| (start, end))?;
+
+    let flatten = |s: &str| s.replace(['\n', '\t'], " ");
+    let before_chars = text[..start].chars().count();
+    let mut before: String = text[..start]
+        .chars()
+        .skip(before_chars.saturating_sub(context))
+        .collect();
+    if before_chars > context {
+        before.insert(0, '…');
+    }
+    let after_chars = text[end..].chars().count();
+    let mut after: String = text[end..].chars().take(context * 2).collect();
+    if after_chars > context * 2 {
+        after.push('…');
+    }
+
+    Some(Snippet {
+        before: flatten(&before),
+        matched: text[start..end].to_string(),
+        after: flatten(&after),
+    })
+}
+
+/// Byte ranges of the words of the text with the words themselves.
+fn words(text: &str) -> impl Iterator<Item = (usize, usize, &str)> {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+        let (start, _) = *chars.peek()?;
+        let mut end = start;
+        while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric()) {
+            end = i + c.len_utf8();
+        }
+        Some((start, end, &text[start..end]))
+    })
+}
+
+/// Normalized words of the text which are worth indexing.
+fn tokenize(text: &str) -> impl Iterator<Item = String> {
+    words(text)
+        .map(|(_, _, word)| normalize(word))
+        .filter(|word| {
+            let chars = word.chars().count();
+            (chars > 1 || word.chars().all(|c| c.is_ascii_digit())) && chars <= MAX_WORD_CHARS
+        })
+}
+
+fn normalize(word: &str) -> String {
+    word.to_lowercase().replace('ё', "е")
+}
+
+/// Stem of the normalized word.
+fn stem(word: &str) -> String {
+    if word.chars().any(|c| matches!(c, 'а'..='я')) {
+        russian::stem(word)
+    } else if word.chars().all(|c| c.is_ascii_lowercase()) {
+        english_stem(word)
+    } else {
+        word.to_string()
+    }
+}
+
+/// Strips the most common English suffixes.
+fn english_stem(word: &str) -> String {
+    let len = word.len();
+    if len > 5 && word.ends_with("ing") {
+        word[..len - 3].to_string()
+    } else if len > 4 && word.ends_with("ed") {
+        word[..len - 2].to_string()
+    } else if len > 4 && word.ends_with("ies") {
+        format!("{}y", &word[..len - 3])
+    } else if len > 3
+        && ["ses", "xes", "ches", "shes"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+    {
+        word[..len - 2].to_string()
+    } else if len > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..len - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Snowball stemmer for Russian, see
+/// https://snowballstem.org/algorithms/russian/stemmer.html
+mod russian {
+    const VOWELS: &[char] = &['а', 'е', 'и', 'о', 'у', 'ы', 'э', 'ю', 'я'];
+
+    const PERFECTIVE_GERUND_1: &[&str] = &["в", "вши", "вшись"];
+    const PERFECTIVE_GERUND_2: &[&str] = &["ив", "ивши", "ившись", "ыв", "ывши", "ывшись"];
+    const ADJECTIVE: &[&str] = &[
+        "ее", "ие", "ые", "ое", "ими", "ыми", "ей", "ий", "ый", "ой", "ем", "им", "ым", "ом",
+        "его", "ого", "ему", "ому", "их", "ых", "ую", "юю", "ая", "яя", "ою", "ею",
+    ];
+    const PARTICIPLE_1: &[&str] = &["ем", "нн", "вш", "ющ", "щ"];
+    const PARTICIPLE_2: &[&str] = &["ивш", "ывш", "ующ"];
+    const REFLEXIVE: &[&str] = &["ся", "сь"];
+    const VERB_1: &[&str] = &[
+        "ла", "на", "ете", "йте", "ли", "й", "л", "ем", "н", "ло", "но", "ет", "ют", "ны", "ть",
+        "ешь", "нно",
+    ];
+    const VERB_2: &[&str] = &[
+        "ила", "ыла", "ена", "ейте", "уйте", "ите", "или", "ыли", "ей", "уй", "ил", "ыл", "им",
+        "ым", "ен", "ило", "ыло", "ено", "ят", "ует", "уют", "ит", "ыт", "ены", "ить", "ыть",
+        "ишь", "ую", "ю",
+    ];
+    const NOUN: &[&str] = &[
+        "а", "ев", "ов", "ие", "ье", "е", "иями", "ями", "ами", "еи", "ии", "и", "ией", "ей", "ой",
+        "ий", "й", "иям", "ям", "ием", "ем", "ам", "ом", "о", "у", "ах", "иях", "ях", "ы", "ь",
+        "ию", "ью", "ю", "ия", "ья", "я",
+    ];
+    const SUPERLATIVE: &[&str] = &["ейше", "ейш"];
+    const DERIVATIONAL: &[&str] = &["ость", "ост"];
+
+    pub fn stem(word: &str) -> String {
+        let mut word: Vec<char> = word.chars().collect();
+        let Some(rv) = word.iter().position(|c| VOWELS.contains(c)).map(|i| i + 1) else {
+            return word.into_iter().collect();
+        };
+        let r2 = region_after_syllable(&word, region_after_syllable(&word, 0));
+
+        // Step 1.
+        if !strip(&mut word, rv, PERFECTIVE_GERUND_1, PERFECTIVE_GERUND_2) {
+            strip(&mut word, rv, &[], REFLEXIVE);
+            if strip(&mut word, rv, &[], ADJECTIVE) {
+                strip(&mut word, rv, PARTICIPLE_1, PARTICIPLE_2);
+            } else if !strip(&mut word, rv, VERB_1, VERB_2) {
+                strip(&mut word, rv, &[], NOUN);
+            }
+        }
+
+        // Step 2.
+        strip(&mut word, rv, &[], &["и"]);
+
+        // Step 3.
+        strip(&mut word, r2.max(rv), &[], DERIVATIONAL);
+
+        // Step 4.
+        if strip(&mut word, rv, &[], &["нн"]) {
+            word.push('н');
+        } else if strip(&mut word, rv, &[], SUPERLATIVE) {
+            if strip(&mut word, rv, &[], &["нн"]) {
+                word.push('н');
+            }
+        } else {
+            strip(&mut word, rv, &[], &["ь"]);
+        }
+
+        word.into_iter().collect()
+    }
+
+    /// Start of the region after the first non-vowel following a vowel.
+    fn region_after_syllable(word: &[char], from: usize) -> usize {
+        (from + 1..word.len())
+            .find(|&i| VOWELS.contains(&word[i - 1]) && !VOWELS.contains(&word[i]))
+            .map_or(word.len(), |i| i + 1)
+    }
+
+    /// Removes the longest ending found in the region starting at `region`. Endings
+    /// of the first group must follow 'а' or 'я'.
+    fn strip(word: &mut Vec<char>, region: usize, after_a: &[&str], other: &[&str]) -> bool {
+        let ends_with = |ending: &str| {
+            let len = ending.chars().count();
+            word.len() >= region + len
+                && word[word.len() - len..].iter().copied().eq(ending.chars())
+        };
+        let longest = after_a
+            .iter()
+            .filter(|ending| {
+                let len = ending.chars().count();
+                ends_with(ending)
+                    && word.len() > region + len
+                    && matches!(word[word.len() - len - 1], 'а' | 'я')
+            })
+            .chain(other.iter().filter(|ending| ends_with(ending)))
+            .map(|ending| ending.chars().count())
+            .max();
+        match longest {
+            Some(len) => {
+                word.truncate(word.len() - len);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::links::HabrLink;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_relocate_highlights() {
+        let mut highlights = ArticleHighlights::default();
+        highlights.add(1, 8, 12, "Тест".to_string(), String::new(), 0);
+        highlights.add(2, 0, 4, "Rust".to_string(), "Заметка".to_string(), 0);
+        highlights.add(2, 5, 8, "и C".to_string(), String::new(), 0);
+
+        assert!(!highlights.relocate(&blocks(&["Заголовок", "Простой Тест", "Rust и C++"])));
+        assert!(highlights.highlights.iter().all(|h| !h.detached));
+
+        // A block is added before, the second one is changed.
+        let changed = highlights.relocate(&blocks(&[
+            "Вступление",
+            "Заголовок",
+            "Простой Тест",
+            "Rust и Go",
+        ]));
+
+        assert!(changed);
+        let anchors: Vec<(usize, usize, usize, bool)> = highlights
+            .highlights
+            .iter()
+            .map(|h| (h.block, h.start, h.end, h.detached))
+            .collect();
+        assert_eq!(
+            anchors,
+            [(2, 5, 8, true), (2, 8, 12, false), (3, 0, 4, false)]
+        );
+        assert_eq!(highlights.block_ranges(3), [(0, 4)]);
+        assert_eq!(highlights.block_ranges(2), [(8, 12)]);
+    }
+
+    #[test]
+    fn test_split_highlighted() {
+        assert_eq!(
+            split_highlighted("Привет, мир", 10, &[(8, 12), (18, 20)]),
+            [("Пр", true), ("ивет, ", false), ("ми", true), ("р", false)]
+        );
+        assert_eq!(split_highlighted("текст", 0, &[]), [("текст", false)]);
+        assert!(split_highlighted("", 0, &[(0, 1)]).is_empty());
+    }
+
+    #[test]
+    fn test_markdown() {
+        let mut highlights = ArticleHighlights {
+            article_id: "42".to_string(),
+            title: "Статья".to_string(),
+            ..Default::default()
+        };
+        highlights.add(3, 0, 4, "Вторая\nстрока".to_string(), String::new(), 0);
+        highlights.add(1, 0, 4, "Первая".to_string(), "Важно".to_string(), 0);
+
+        assert_eq!(
+            highlights.to_markdown(),
+            "# Заметки к статье «Статья»\n\n\
+             https://habr.com/ru/articles/42/\n\n\
+             > Первая\n\nВажно\n\n\
+             > Вторая\n> строка\n"
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Highlight {
+    pub id: u64,
+    /// Index of the content block of the article.
+    pub block: usize,
+    /// Character range in the plain text of the block.
+    pub start: usize,
+    pub end: usize,
+    /// Highlighted text, the highlight is searched by it if the article was changed.
+    pub quote: String,
+    #[serde(default)]
+    pub note: String,
+    pub created_at: i64,
+    /// The quote is not found in the current article content.
+    #[serde(skip)]
+    pub detached: bool,
+}
+
+impl Highlight {
+    pub fn created_at_str(&self) -> String {
+        chrono::DateTime::from_timestamp(self.created_at, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%d.%m.%Y %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Finds the quote in the blocks, the nearest to the previous position first.
+    fn locate(&self, blocks: &[String]) -> Option<(usize, usize, usize)> {
+        if self.quote.is_empty() {
+            return None;
+        }
+        if let Some(text) = blocks.get(self.block)
+            && char_slice(text, self.start, self.end) == self.quote
+        {
+            return Some((self.block, self.start, self.end));
+        }
+
+        let mut indexes: Vec<usize> = (0..blocks.len()).collect();
+        indexes.sort_by_key(|i| i.abs_diff(self.block));
+        indexes.into_iter().find_map(|i| {
+            let byte_start = blocks[i].find(&self.quote)?;
+            let start = blocks[i][..byte_start].chars().count();
+            Some((i, start, start + self.quote.chars().count()))
+        })
+    }
+}
+
+/// Highlights and notes of one article, the ones on the same block are sorted by position.
+/// Stored in `highlights/<article id>.json` of the app data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArticleHighlights {
+    pub article_id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl ArticleHighlights {
+    fn path(article_id: &str) -> PathBuf {
+        app_data_dir()
+            .join("highlights")
+            .join(format!("{}.json", article_id))
+    }
+
+    pub fn load(article_id: &str) -> Self {
+        let mut highlights: Self = read_json(&Self::path(article_id));
+        highlights.article_id = article_id.to_string();
+        highlights
+    }
+
+    pub fn save(&mut self) {
+        let path = Self::path(&self.article_id);
+        let result = if self.highlights.is_empty() {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            }
+        } else {
+            write_json(&path, self)
+        };
+        self.save_error = result.err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save highlights of {}: {}", self.article_id, e);
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        block: usize,
+        start: usize,
+        end: usize,
+        quote: String,
+        note: String,
+        now: i64,
+    ) -> u64 {
+        let id = self.highlights.iter().map(|h| h.id + 1).max().unwrap_or(0);
+        self.highlights.push(Highlight {
+            id,
+            block,
+            start,
+            end,
+            quote,
+            note,
+            created_at: now,
+            detached: false,
+        });
+        self.sort();
+        id
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.highlights.retain(|h| h.id != id);
+    }
+
+    pub fn set_note(&mut self, id: u64, note: String) {
+        if let Some(highlight) = self.highlights.iter_mut().find(|h| h.id == id) {
+            highlight.note = note;
+        }
+    }
+
+    /// Anchors the highlights to the current plain texts of the article blocks, the
+    /// content may be changed since the highlights were made. Returns true if any
+    /// highlight was moved.
+    pub fn relocate(&mut self, blocks: &[String]) -> bool {
+        let mut moved = false;
+        for highlight in self.highlights.iter_mut() {
+            match highlight.locate(blocks) {
+                Some((block, start, end)) => {
+                    moved |=
+                        (block, start, end) != (highlight.block, highlight.start, highlight.end);
+                    highlight.block = block;
+                    highlight.start = start;
+                    highlight.end = end;
+                    highlight.detached = false;
+                }
+                None => highlight.detached = true,
+            }
+        }
+        if moved {
+            self.sort();
+        }
+        moved
+    }
+
+    /// Character ranges of the highlights shown on the block.
+    pub fn block_ranges(&self, block: usize) -> Vec<(usize, usize)> {
+        self.highlights
+            .iter()
+            .filter(|h| h.block == block && !h.detached)
+            .map(|h| (h.start, h.end))
+            .collect()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# Заметки к статье «{}»\n\n{}\n",
+            self.title,
+            HabrLink::Article(self.article_id.clone()).url()
+        );
+        for highlight in self.highlights.iter() {
+            markdown.push('\n');
+            for line in highlight.quote.lines() {
+                markdown.push_str(&format!("> {}\n", line));
+            }
+            if !highlight.note.is_empty() {
+                markdown.push_str(&format!("\n{}\n", highlight.note));
+            }
+        }
+        markdown
+    }
+
+    fn sort(&mut self) {
+        self.highlights.sort_by_key(|h| (h.block, h.start));
+    }
+}
+
+fn char_slice(text: &str, start: usize, end: usize) -> String {
+    text.chars()
+        .skip(start)
+        .take(end.saturating_sub(start))
+        .collect()
+}
+
+/// Splits the text into the parts with the flags whether they are highlighted.
+/// `offset` is the position of the text in its block.
+pub fn split_highlighted<'a>(
+    text: &'a str,
+    offset: usize,
+    ranges: &[(usize, usize)],
+) -> Vec<(&'a str, bool)> {
+    let mut parts: Vec<(&str, bool)> = Vec::new();
+    let mut part_start = 0;
+    let mut part_highlighted = false;
+    for (i, (byte_pos, _)) in text.char_indices().enumerate() {
+        let position = offset + i;
+        let highlighted = ranges
+            .iter()
+            .any(|(start, end)| (*start..*end).contains(&position));
+        if i > 0 && highlighted != part_highlighted {
+            parts.push((&text[part_start..byte_pos], part_highlighted));
+            part_start = byte_pos;
+        }
+        part_highlighted = highlighted;
+    }
+    if part_start < text.len() {
+        parts.push((&text[part_start..], part_highlighted));
+    }
+    parts
+}
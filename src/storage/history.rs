@@ -0,0 +1,169 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::article::ArticleData;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn article(id: &str) -> ArticleData {
+        ArticleData {
+            title: format!("Article {}", id),
+            author: "author".to_string(),
+            ..test_article(id)
+        }
+    }
+
+    #[test]
+    fn test_reopened_article_keeps_position() {
+        let mut history = ReadingHistory::default();
+        history.record_open(&article("1"), 100);
+        history.record_open(&article("2"), 200);
+        assert!(history.update_position("1", 500., 0.95));
+        assert!(!history.update_position("1", 500., 0.95));
+        assert!(history.update_position("1", 50., 0.1));
+
+        history.record_open(&article("1"), 300);
+
+        let ids: Vec<&str> = history
+            .entries
+            .iter()
+            .map(|e| e.metadata.id.as_str())
+            .collect();
+        assert_eq!(ids, ["1", "2"]);
+        let entry = history.get("1").unwrap();
+        assert_eq!(entry.opened_at, 300);
+        assert_eq!(entry.scroll_offset, 50.);
+        assert!(entry.finished);
+        assert!(!history.get("2").unwrap().finished);
+    }
+
+    #[test]
+    fn test_history_is_limited() {
+        let mut history = ReadingHistory::default();
+        for i in 0..MAX_HISTORY_ENTRIES + 10 {
+            history.record_open(&article(&i.to_string()), i as i64);
+        }
+
+        assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
+        assert!(history.get("0").is_none());
+        assert_eq!(
+            history.entries[0].metadata.id,
+            (MAX_HISTORY_ENTRIES + 9).to_string()
+        );
+    }
+}
+
+const MAX_HISTORY_ENTRIES: usize = 300;
+
+/// Article is marked as read once it's scrolled this far.
+const FINISHED_PROGRESS: f32 = 0.9;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub metadata: ArticleData,
+    pub opened_at: i64,
+    #[serde(default)]
+    pub scroll_offset: f32,
+    /// Scrolled part of the article, from 0 to 1.
+    #[serde(default)]
+    pub progress: f32,
+    /// The article was read to the end at least once.
+    #[serde(default)]
+    pub finished: bool,
+}
+
+impl HistoryEntry {
+    pub fn opened_at_str(&self) -> String {
+        chrono::DateTime::from_timestamp(self.opened_at, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%d.%m.%Y %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Opened articles with their reading positions, the last opened first.
+/// Stored in `history.json` of the app data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadingHistory {
+    #[serde(default)]
+    pub entries: Vec<HistoryEntry>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl ReadingHistory {
+    fn path() -> PathBuf {
+        app_data_dir().join("history.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save reading history: {}", e);
+        }
+    }
+
+    pub fn get(&self, article_id: &str) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|e| e.metadata.id == article_id)
+    }
+
+    /// Moves the article to the top of the history, its reading position is kept.
+    pub fn record_open(&mut self, article: &ArticleData, now: i64) {
+        let position = self
+            .entries
+            .iter()
+            .position(|e| e.metadata.id == article.id);
+        let mut entry = match position {
+            Some(index) => self.entries.remove(index),
+            None => HistoryEntry {
+                metadata: article.clone(),
+                opened_at: now,
+                scroll_offset: 0.,
+                progress: 0.,
+                finished: false,
+            },
+        };
+        entry.metadata = article.clone();
+        entry.opened_at = now;
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_HISTORY_ENTRIES);
+    }
+
+    /// Returns true if the position was changed.
+    pub fn update_position(&mut self, article_id: &str, scroll_offset: f32, progress: f32) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.metadata.id == article_id)
+        else {
+            return false;
+        };
+        if entry.scroll_offset == scroll_offset && entry.progress == progress {
+            return false;
+        }
+        entry.scroll_offset = scroll_offset;
+        entry.progress = progress;
+        entry.finished |= progress >= FINISHED_PROGRESS;
+        true
+    }
+
+    pub fn remove(&mut self, article_id: &str) {
+        self.entries.retain(|e| e.metadata.id != article_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
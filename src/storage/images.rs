@@ -0,0 +1,226 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use super::{app_data_dir, write_atomic};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TestDataDir;
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(b""), "6c62272e07bb014262b821756295c58d");
+        assert_eq!(content_hash(b"image"), content_hash(b"image"));
+        assert_ne!(content_hash(b"image"), content_hash(b"imagf"));
+    }
+
+    #[test]
+    fn test_fresh_image_is_kept_after_release() {
+        let _data_dir = TestDataDir::new("fresh-image");
+        let file_name = ImageStore::store(b"fresh image", ".png").unwrap();
+        let files = BTreeSet::from([file_name.clone()]);
+        ImageStore::set_article_refs("release-1", &files).unwrap();
+
+        // Another article may be saving the same image right now.
+        ImageStore::release_article("release-1").unwrap();
+
+        assert!(ImageStore::base_path().join(&file_name).exists());
+    }
+}
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// FNV-1a 128-bit hash of the image bytes. Unlike `DefaultHasher` it gives the same
+/// result on every Rust version, so file names in the store stay valid.
+pub fn content_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u128).wrapping_mul(FNV_PRIME)
+    });
+    format!("{:032x}", hash)
+}
+
+type ImageRefs = HashMap<String, BTreeSet<String>>;
+
+static REFS_LOCK: Mutex<()> = Mutex::new(());
+
+// Images of an article being saved right now are not referenced yet, so fresh files are kept
+// both by the garbage collection and when other articles release them.
+const GC_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GarbageCollectionStats {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+/// Images of saved articles shared between all of them and addressed by content hash.
+/// `refs.json` keeps the ids of the articles which use every image.
+pub struct ImageStore;
+
+impl ImageStore {
+    pub fn base_path() -> PathBuf {
+        app_data_dir().join("images")
+    }
+
+    fn refs_path() -> PathBuf {
+        Self::base_path().join("refs.json")
+    }
+
+    pub fn store(bytes: &[u8], ext: &str) -> std::io::Result<String> {
+        let file_name = format!("{}{}", content_hash(bytes), ext);
+        let path = Self::base_path().join(&file_name);
+        // Under the lock the file can't be released between the check and the touch.
+        let _guard = REFS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if path.exists() {
+            // Reused file becomes fresh, so it's kept until the article saved now refers to it.
+            fs::File::options()
+                .append(true)
+                .open(&path)?
+                .set_modified(SystemTime::now())?;
+        } else {
+            write_atomic(&path, bytes)?;
+        }
+        Ok(file_name)
+    }
+
+    pub fn file_url(file_name: &str) -> String {
+        format!(
+            "file://{}",
+            Self::base_path().join(file_name).to_string_lossy()
+        )
+    }
+
+    /// Returns the store file name if the url points into the store.
+    pub fn file_name_from_url(url: &str) -> Option<String> {
+        let path = Path::new(url.strip_prefix("file://")?);
+        if path.parent()? != Self::base_path() {
+            return None;
+        }
+        Some(path.file_name()?.to_string_lossy().to_string())
+    }
+
+    pub fn files_size(file_names: &BTreeSet<String>) -> u64 {
+        file_names
+            .iter()
+            .filter_map(|name| fs::metadata(Self::base_path().join(name)).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+
+    /// Replaces the set of images used by the article and removes images nobody uses anymore.
+    pub fn set_article_refs(article_id: &str, file_names: &BTreeSet<String>) -> Result<(), String> {
+        let _guard = REFS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut refs = Self::read_refs();
+        let mut released = Vec::new();
+
+        for (file_name, articles) in refs.iter_mut() {
+            if articles.remove(article_id) && !file_names.contains(file_name) {
+                released.push(file_name.clone());
+            }
+        }
+        for file_name in file_names {
+            refs.entry(file_name.clone())
+                .or_default()
+                .insert(article_id.to_string());
+        }
+        for file_name in released {
+            if refs
+                .get(&file_name)
+                .is_some_and(|articles| articles.is_empty())
+            {
+                refs.remove(&file_name);
+                if !Self::is_fresh(&file_name) {
+                    Self::remove_file(&file_name);
+                }
+            }
+        }
+
+        Self::write_refs(&refs)
+    }
+
+    pub fn release_article(article_id: &str) -> Result<(), String> {
+        Self::set_article_refs(article_id, &BTreeSet::new())
+    }
+
+    /// Rebuilds the reference counts from the images actually used by saved articles
+    /// and removes every file which is not used by any of them. Known references of
+    /// `unreadable_articles` are kept as is, since their images can't be collected.
+    pub fn collect_garbage(
+        mut used: ImageRefs,
+        unreadable_articles: &[String],
+    ) -> Result<GarbageCollectionStats, String> {
+        let _guard = REFS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats = GarbageCollectionStats::default();
+
+        for (file_name, articles) in Self::read_refs() {
+            for article_id in articles {
+                if unreadable_articles.contains(&article_id) {
+                    used.entry(file_name.clone())
+                        .or_default()
+                        .insert(article_id);
+                }
+            }
+        }
+
+        if let Ok(entries) = fs::read_dir(Self::base_path()) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if file_name == "refs.json" || used.contains_key(&file_name) {
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if Self::is_fresh(&file_name) {
+                    continue;
+                }
+                let size = metadata.len();
+                if Self::remove_file(&file_name) {
+                    stats.removed_files += 1;
+                    stats.freed_bytes += size;
+                }
+            }
+        }
+
+        Self::write_refs(&used)?;
+        Ok(stats)
+    }
+
+    fn is_fresh(file_name: &str) -> bool {
+        fs::metadata(Self::base_path().join(file_name))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|age| age < GC_GRACE_PERIOD)
+    }
+
+    fn remove_file(file_name: &str) -> bool {
+        match fs::remove_file(Self::base_path().join(file_name)) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to remove image {}: {}", file_name, e);
+                false
+            }
+        }
+    }
+
+    fn read_refs() -> ImageRefs {
+        match fs::read_to_string(Self::refs_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Image references are corrupted: {}", e);
+                ImageRefs::new()
+            }),
+            Err(_) => ImageRefs::new(),
+        }
+    }
+
+    fn write_refs(refs: &ImageRefs) -> Result<(), String> {
+        let json = serde_json::to_string(refs).map_err(|e| e.to_string())?;
+        write_atomic(&Self::refs_path(), json).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,1110 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::habr_client::article::{ArticleContent, ArticleData};
+
+pub mod collections;
+pub mod content_filter;
+pub mod export;
+pub mod feeds;
+pub mod fulltext;
+pub mod highlights;
+pub mod history;
+pub mod images;
+pub mod notifications;
+pub mod searches;
+pub mod subscriptions;
+
+use fulltext::FullTextIndex;
+use images::{GarbageCollectionStats, ImageStore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_v1_saved_article() {
+        let json = r#"{
+            "metadata": {
+                "id": "123",
+                "title": "Article",
+                "tags": ["Rust"],
+                "complexity": "low",
+                "author": "Author1",
+                "published_at": "25.01.2026 08:00",
+                "reading_time": 10,
+                "image_url": "",
+                "score": 50,
+                "comments_count": 42
+            },
+            "content": [{"Header": [2, "Header"]}, "BR"],
+            "saved_at": "26.01.2026 10:30"
+        }"#;
+
+        let (saved, migrated) = SavedArticle::from_json(json).unwrap();
+
+        assert!(migrated);
+        assert_eq!(saved.version, SAVED_ARTICLE_VERSION);
+        assert_eq!(saved.metadata.id, "123");
+        assert_eq!(saved.content.len(), 2);
+        let expected = chrono::NaiveDateTime::parse_from_str("26.01.2026 10:30", "%d.%m.%Y %H:%M")
+            .unwrap()
+            .and_local_timezone(chrono::Local)
+            .unwrap()
+            .timestamp();
+        assert_eq!(saved.saved_at, expected);
+    }
+
+    #[test]
+    fn test_cleanup_restores_interrupted_commit() {
+        let _data_dir = TestDataDir::new("cleanup");
+        // Moved aside, but the new copy was not moved in.
+        let backup = ArticleStorage::backup_path("cleanup-1");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("article.json"), "{}").unwrap();
+        // Committed, but the backup was not removed.
+        let stale_backup = ArticleStorage::backup_path("cleanup-2");
+        fs::create_dir_all(&stale_backup).unwrap();
+        fs::create_dir_all(ArticleStorage::article_path("cleanup-2")).unwrap();
+
+        ArticleStorage::cleanup_partial_saves();
+
+        let restored = ArticleStorage::article_path("cleanup-1");
+        assert!(restored.join("article.json").exists());
+        assert!(!backup.exists());
+        assert!(!stale_backup.exists());
+        assert!(ArticleStorage::article_path("cleanup-2").exists());
+    }
+
+    #[test]
+    fn test_legacy_images_are_moved_into_store() {
+        let _data_dir = TestDataDir::new("legacy-images");
+        let article_path = ArticleStorage::article_path("legacy-1");
+        let images_path = article_path.join("images");
+        fs::create_dir_all(&images_path).unwrap();
+        fs::write(images_path.join("1.png"), b"legacy image").unwrap();
+        let image_url = format!("file://{}", images_path.join("1.png").to_string_lossy());
+        let json = format!(
+            r#"{{
+                "version": 2,
+                "metadata": {{
+                    "id": "legacy-1",
+                    "title": "Article",
+                    "tags": [],
+                    "complexity": "",
+                    "author": "",
+                    "published_at": "",
+                    "reading_time": 1,
+                    "image_url": "",
+                    "score": 0,
+                    "comments_count": 0
+                }},
+                "content": [{{"Image": "{}"}}],
+                "saved_at": 1769423400
+            }}"#,
+            image_url
+        );
+        fs::write(article_path.join("article.json"), json).unwrap();
+
+        ArticleStorage::migrate_article_images("legacy-1").unwrap();
+
+        let saved = ArticleStorage::read_saved_article("legacy-1").unwrap();
+        let urls = ArticleStorage::collect_image_urls(&saved.content);
+        let file_name = ImageStore::file_name_from_url(&urls[0]).unwrap();
+        assert_eq!(
+            fs::read(ImageStore::base_path().join(file_name)).unwrap(),
+            b"legacy image"
+        );
+        assert!(!images_path.exists());
+    }
+
+    #[test]
+    fn test_current_version_is_not_migrated() {
+        let json = r#"{
+            "version": 2,
+            "metadata": {
+                "id": "123",
+                "title": "Article",
+                "tags": [],
+                "complexity": "",
+                "author": "",
+                "published_at": "",
+                "reading_time": 1,
+                "image_url": "",
+                "score": 0,
+                "comments_count": 0
+            },
+            "content": [],
+            "saved_at": 1769423400
+        }"#;
+
+        let (saved, migrated) = SavedArticle::from_json(json).unwrap();
+
+        assert!(!migrated);
+        assert_eq!(saved.saved_at, 1769423400);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let json = r#"{"version": 999, "metadata": {}, "content": [], "saved_at": 0}"#;
+        assert!(SavedArticle::from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_guess_extension_of_short_bodies() {
+        assert_eq!(
+            ArticleStorage::guess_extension("a.webp", b"RIFF\0\0\0\0WE"),
+            ".webp"
+        );
+        assert_eq!(
+            ArticleStorage::guess_extension("a", b"RIFF\0\0\0\0WEBP"),
+            ".webp"
+        );
+        assert_eq!(ArticleStorage::guess_extension("a", b"RIFF"), ".bin");
+    }
+}
+
+const SAVED_ARTICLE_VERSION: u32 = 2;
+
+type Migration = fn(&mut serde_json::Value) -> Result<(), String>;
+
+// Migrations from version `i + 1` to version `i + 2`.
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+
+#[derive(Serialize, Deserialize)]
+struct SavedArticle {
+    version: u32,
+    metadata: ArticleData,
+    content: Vec<ArticleContent>,
+    saved_at: i64,
+}
+
+impl SavedArticle {
+    fn from_json(json: &str) -> Result<(Self, bool), String> {
+        let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let version = value
+            .get("version")
+            .map_or(Some(1), |v| v.as_u64())
+            .ok_or("Некорректная версия формата")? as u32;
+
+        if version == 0 || version > SAVED_ARTICLE_VERSION {
+            return Err(format!("Неподдерживаемая версия формата: {}", version));
+        }
+
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(&mut value)?;
+        }
+
+        let saved = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        Ok((saved, version != SAVED_ARTICLE_VERSION))
+    }
+}
+
+fn migrate_v1_to_v2(value: &mut serde_json::Value) -> Result<(), String> {
+    let object = value
+        .as_object_mut()
+        .ok_or("Сохранённая статья не является объектом")?;
+    let saved_at = object
+        .get("saved_at")
+        .and_then(|v| v.as_str())
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%d.%m.%Y %H:%M").ok())
+        .and_then(|dt| dt.and_local_timezone(chrono::Local).single())
+        .map_or(0, |dt| dt.timestamp());
+
+    object.insert("saved_at".to_string(), saved_at.into());
+    object.insert("version".to_string(), 2.into());
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedArticleEntry {
+    pub metadata: ArticleData,
+    pub saved_at: i64,
+    pub size: u64,
+    #[serde(default)]
+    pub read_progress: f32,
+}
+
+impl SavedArticleEntry {
+    pub fn saved_at_str(&self) -> String {
+        chrono::DateTime::from_timestamp(self.saved_at, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%d.%m.%Y %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BrokenSavedArticle {
+    pub id: String,
+    pub error: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SavedArticlesIndex {
+    articles: Vec<SavedArticleEntry>,
+    #[serde(default)]
+    broken: Vec<BrokenSavedArticle>,
+}
+
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+static FULLTEXT_LOCK: Mutex<()> = Mutex::new(());
+
+const MAX_PARALLEL_DOWNLOADS: usize = 4;
+const SAVE_CANCELLED: &str = "Сохранение отменено";
+
+#[derive(Debug, Default)]
+pub struct SaveProgress {
+    images_total: AtomicUsize,
+    images_done: AtomicUsize,
+    bytes: AtomicU64,
+    cancelled: AtomicBool,
+    failed_images: RwLock<Vec<String>>,
+}
+
+impl SaveProgress {
+    pub fn images_total(&self) -> usize {
+        self.images_total.load(Ordering::Relaxed)
+    }
+
+    pub fn images_done(&self) -> usize {
+        self.images_done.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn fraction(&self) -> f32 {
+        let total = self.images_total();
+        if total == 0 {
+            0.
+        } else {
+            self.images_done() as f32 / total as f32
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn failed_images(&self) -> Vec<String> {
+        self.failed_images.read().unwrap().clone()
+    }
+}
+
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap()
+    })
+}
+
+#[cfg(not(test))]
+pub fn app_data_dir() -> PathBuf {
+    let home_dir = std::env::home_dir().unwrap();
+    home_dir.join(".local/share/com.lmaxyz/Haboost")
+}
+
+/// Tests must not read or rewrite the data of the installed app, nor the data of each
+/// other, so every test touching the files works in its own [`TestDataDir`].
+#[cfg(test)]
+pub fn app_data_dir() -> PathBuf {
+    TEST_DATA_DIR
+        .with(|dir| dir.borrow().clone())
+        .expect("the test must create its TestDataDir first")
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_DATA_DIR: std::cell::RefCell<Option<PathBuf>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// App data dir of the test running on the current thread, removed when dropped.
+#[cfg(test)]
+pub(crate) struct TestDataDir(PathBuf);
+
+#[cfg(test)]
+impl TestDataDir {
+    pub(crate) fn new(test_name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("haboost-test-{}-{}", std::process::id(), test_name));
+        let _ = fs::remove_dir_all(&path);
+        TEST_DATA_DIR.with(|dir| *dir.borrow_mut() = Some(path.clone()));
+        Self(path)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDataDir {
+    fn drop(&mut self) {
+        TEST_DATA_DIR.with(|dir| *dir.borrow_mut() = None);
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Directory for the files exported by the user.
+pub fn documents_dir() -> PathBuf {
+    if cfg!(test) {
+        return app_data_dir().join("Documents");
+    }
+    let home_dir = std::env::home_dir().unwrap();
+    home_dir.join("Documents/Haboost")
+}
+
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Reads a small JSON file of the app data, falling back to the default value
+/// if the file is missing or can't be parsed.
+pub fn read_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("{} is corrupted: {}", path.to_string_lossy(), e);
+            T::default()
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+        Err(e) => {
+            log::warn!("Failed to read {}: {}", path.to_string_lossy(), e);
+            T::default()
+        }
+    }
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    write_atomic(path, json).map_err(|e| e.to_string())
+}
+
+pub struct ArticleStorage;
+
+impl ArticleStorage {
+    fn base_path() -> PathBuf {
+        app_data_dir().join("saved_articles")
+    }
+
+    fn article_path(article_id: &str) -> PathBuf {
+        Self::base_path().join(article_id)
+    }
+
+    fn staging_path(article_id: &str) -> PathBuf {
+        Self::base_path().join(format!(".partial-{}", article_id))
+    }
+
+    fn backup_path(article_id: &str) -> PathBuf {
+        Self::base_path().join(format!(".old-{}", article_id))
+    }
+
+    pub fn cleanup_partial_saves() {
+        let Ok(entries) = fs::read_dir(Self::base_path()) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            let result = if let Some(article_id) = name.strip_prefix(".old-") {
+                // The save could be interrupted between moving the article aside and moving
+                // its new copy in, then the backup is the only copy.
+                let article_path = Self::article_path(article_id);
+                if article_path.exists() {
+                    fs::remove_dir_all(&path)
+                } else {
+                    match fs::rename(&path, &article_path) {
+                        Ok(()) => {
+                            log::info!("Restored article {} from interrupted save", article_id);
+                            continue;
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+            } else if name.starts_with(".partial-") {
+                fs::remove_dir_all(&path)
+            } else if name.starts_with('.') && name.ends_with(".tmp") {
+                fs::remove_file(&path)
+            } else {
+                continue;
+            };
+            match result {
+                Ok(()) => log::info!("Removed leftovers of interrupted save: {}", name),
+                Err(e) => log::warn!("Failed to remove {}: {}", path.to_string_lossy(), e),
+            }
+        }
+    }
+
+    pub fn is_article_saved(article_id: &str) -> bool {
+        Self::article_path(article_id).join("article.json").exists()
+    }
+
+    fn index_path() -> PathBuf {
+        Self::base_path().join("index.json")
+    }
+
+    pub fn list_saved_articles() -> Vec<SavedArticleEntry> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self::read_index()
+            .articles
+            .into_iter()
+            .filter(|entry| Self::is_article_saved(&entry.metadata.id))
+            .collect()
+    }
+
+    pub fn list_broken_articles() -> Vec<BrokenSavedArticle> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self::read_index()
+            .broken
+            .into_iter()
+            .filter(|broken| Self::is_article_saved(&broken.id))
+            .collect()
+    }
+
+    fn read_index() -> SavedArticlesIndex {
+        let path = Self::index_path();
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<SavedArticlesIndex>(&content) {
+                Ok(index) => return index,
+                Err(e) => log::warn!("Saved articles index is corrupted, rebuilding: {}", e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to read saved articles index, rebuilding: {}", e),
+        }
+
+        let index = Self::rebuild_index();
+        if let Err(e) = Self::write_index(&index) {
+            log::warn!("Failed to write saved articles index: {}", e);
+        }
+        index
+    }
+
+    fn write_index(index: &SavedArticlesIndex) -> Result<(), String> {
+        let json = serde_json::to_string(index).map_err(|e| e.to_string())?;
+        write_atomic(&Self::index_path(), json).map_err(|e| e.to_string())
+    }
+
+    fn update_index(f: impl FnOnce(&mut SavedArticlesIndex)) -> Result<(), String> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = Self::read_index();
+        f(&mut index);
+        Self::write_index(&index)
+    }
+
+    fn rebuild_index() -> SavedArticlesIndex {
+        let mut index = SavedArticlesIndex::default();
+        let Ok(entries) = fs::read_dir(Self::base_path()) else {
+            return index;
+        };
+
+        for entry in entries.flatten() {
+            let article_dir = entry.path();
+            let article_id = entry.file_name().to_string_lossy().to_string();
+            if article_id.starts_with('.') || !article_dir.join("article.json").exists() {
+                continue;
+            }
+
+            match Self::read_saved_article(&article_id) {
+                Ok(saved) => {
+                    let saved_at = if saved.saved_at > 0 {
+                        saved.saved_at
+                    } else {
+                        Self::modified_at(&article_dir)
+                    };
+                    index.articles.push(SavedArticleEntry {
+                        metadata: saved.metadata,
+                        saved_at,
+                        size: Self::dir_size(&article_dir),
+                        read_progress: 0.,
+                    });
+                }
+                Err(error) => {
+                    log::warn!("Failed to read saved article {}: {}", article_id, error);
+                    index.broken.push(BrokenSavedArticle {
+                        id: article_id,
+                        error,
+                    });
+                }
+            }
+        }
+        index
+    }
+
+    fn read_saved_article(article_id: &str) -> Result<SavedArticle, String> {
+        let path = Self::article_path(article_id).join("article.json");
+        let json = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let (saved, migrated) = SavedArticle::from_json(&json)?;
+
+        if migrated {
+            match serde_json::to_string_pretty(&saved) {
+                Ok(json) => {
+                    if let Err(e) = write_atomic(&path, json) {
+                        log::warn!("Failed to write migrated article {}: {}", article_id, e);
+                    }
+                }
+                Err(e) => log::warn!("Failed to serialize migrated article {}: {}", article_id, e),
+            }
+        }
+
+        Ok(saved)
+    }
+
+    fn modified_at(path: &Path) -> i64 {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map(|t| chrono::DateTime::<chrono::Local>::from(t).timestamp())
+            .unwrap_or_default()
+    }
+
+    fn dir_size(path: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(path) else {
+            return 0;
+        };
+        entries
+            .flatten()
+            .map(|entry| match entry.metadata() {
+                Ok(m) if m.is_dir() => Self::dir_size(&entry.path()),
+                Ok(m) => m.len(),
+                Err(_) => 0,
+            })
+            .sum()
+    }
+
+    pub fn load_article(article_id: &str) -> Result<(ArticleData, Vec<ArticleContent>), String> {
+        match Self::read_saved_article(article_id) {
+            Ok(saved) => Ok((saved.metadata, saved.content)),
+            Err(error) => {
+                let broken = BrokenSavedArticle {
+                    id: article_id.to_string(),
+                    error: error.clone(),
+                };
+                let _ = Self::update_index(move |index| {
+                    index.articles.retain(|e| e.metadata.id != broken.id);
+                    index.broken.retain(|b| b.id != broken.id);
+                    index.broken.push(broken);
+                });
+                Err(error)
+            }
+        }
+    }
+
+    pub async fn save_article(
+        data: &ArticleData,
+        content: &[ArticleContent],
+        progress: &Arc<SaveProgress>,
+    ) -> Result<(), String> {
+        let staging_path = Self::staging_path(&data.id);
+        if staging_path.exists() {
+            fs::remove_dir_all(&staging_path).map_err(|e| e.to_string())?;
+        }
+
+        let result = match Self::save_to_staging(data, content, &staging_path, progress).await {
+            Ok(saved) => Self::commit_staging(&data.id, &staging_path).map(|_| saved),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((metadata, image_files)) => {
+                Self::update_fulltext_index(|index| index.add(&metadata, content));
+                Self::index_saved_article(metadata, &image_files)
+            }
+            Err(e) => {
+                if let Err(remove_err) = fs::remove_dir_all(&staging_path)
+                    && remove_err.kind() != std::io::ErrorKind::NotFound
+                {
+                    log::warn!("Failed to clean up partial save: {}", remove_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Downloads the images which failed to download while saving the article.
+    pub async fn retry_failed_images(
+        article_id: &str,
+        progress: &Arc<SaveProgress>,
+    ) -> Result<(), String> {
+        let mut saved = Self::read_saved_article(article_id)?;
+
+        let mut urls = Self::collect_image_urls(&saved.content);
+        urls.push(saved.metadata.image_url.clone());
+        let remote_urls = urls
+            .into_iter()
+            .filter(|url| !url.is_empty() && !url.starts_with("file://"))
+            .collect();
+
+        let url_map = Self::download_images(remote_urls, progress).await?;
+        Self::replace_image_urls(&mut saved.content, &url_map);
+        if let Some(local_path) = url_map.get(&saved.metadata.image_url) {
+            saved.metadata.image_url = local_path.clone();
+        }
+
+        let json = serde_json::to_string_pretty(&saved).map_err(|e| e.to_string())?;
+        write_atomic(&Self::article_path(article_id).join("article.json"), json)
+            .map_err(|e| e.to_string())?;
+
+        let mut urls = Self::collect_image_urls(&saved.content);
+        urls.push(saved.metadata.image_url.clone());
+        let image_files = urls
+            .iter()
+            .filter_map(|url| ImageStore::file_name_from_url(url))
+            .collect();
+        Self::index_saved_article(saved.metadata, &image_files)
+    }
+
+    fn index_saved_article(
+        metadata: ArticleData,
+        image_files: &BTreeSet<String>,
+    ) -> Result<(), String> {
+        ImageStore::set_article_refs(&metadata.id, image_files)?;
+
+        let entry = SavedArticleEntry {
+            size: Self::dir_size(&Self::article_path(&metadata.id))
+                + ImageStore::files_size(image_files),
+            metadata,
+            saved_at: chrono::Local::now().timestamp(),
+            read_progress: 0.,
+        };
+        Self::update_index(move |index| {
+            let previous = index
+                .articles
+                .iter()
+                .position(|e| e.metadata.id == entry.metadata.id)
+                .map(|i| index.articles.remove(i));
+            index.broken.retain(|b| b.id != entry.metadata.id);
+            index.articles.push(match previous {
+                Some(previous) => SavedArticleEntry {
+                    saved_at: previous.saved_at,
+                    read_progress: previous.read_progress,
+                    ..entry
+                },
+                None => entry,
+            });
+        })
+    }
+
+    async fn save_to_staging(
+        data: &ArticleData,
+        content: &[ArticleContent],
+        staging_path: &Path,
+        progress: &Arc<SaveProgress>,
+    ) -> Result<(ArticleData, BTreeSet<String>), String> {
+        fs::create_dir_all(staging_path).map_err(|e| e.to_string())?;
+
+        let mut image_urls: BTreeSet<String> =
+            Self::collect_image_urls(content).into_iter().collect();
+        if !data.image_url.is_empty() {
+            image_urls.insert(data.image_url.clone());
+        }
+        let url_map = Self::download_images(image_urls, progress).await?;
+
+        let image_files = url_map
+            .values()
+            .filter_map(|url| ImageStore::file_name_from_url(url))
+            .collect();
+
+        let mut saved_content = content.to_vec();
+        Self::replace_image_urls(&mut saved_content, &url_map);
+
+        let mut saved_metadata = data.clone();
+        if let Some(local_path) = url_map.get(&data.image_url) {
+            saved_metadata.image_url = local_path.clone();
+        }
+
+        let saved_article = SavedArticle {
+            version: SAVED_ARTICLE_VERSION,
+            metadata: saved_metadata,
+            content: saved_content,
+            saved_at: chrono::Local::now().timestamp(),
+        };
+
+        let json = serde_json::to_string_pretty(&saved_article).map_err(|e| e.to_string())?;
+        write_atomic(&staging_path.join("article.json"), json).map_err(|e| e.to_string())?;
+
+        Ok((saved_article.metadata, image_files))
+    }
+
+    fn commit_staging(article_id: &str, staging_path: &Path) -> Result<(), String> {
+        let article_path = Self::article_path(article_id);
+        let backup_path = Self::backup_path(article_id);
+
+        if backup_path.exists() {
+            fs::remove_dir_all(&backup_path).map_err(|e| e.to_string())?;
+        }
+        if article_path.exists() {
+            fs::rename(&article_path, &backup_path).map_err(|e| e.to_string())?;
+        }
+
+        if let Err(e) = fs::rename(staging_path, &article_path) {
+            if backup_path.exists() {
+                let _ = fs::rename(&backup_path, &article_path);
+            }
+            return Err(e.to_string());
+        }
+
+        if backup_path.exists()
+            && let Err(e) = fs::remove_dir_all(&backup_path)
+        {
+            log::warn!(
+                "Failed to remove previous copy of article {}: {}",
+                article_id,
+                e
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_read_progress(article_id: &str, progress: f32) -> Result<(), String> {
+        Self::update_index(|index| {
+            if let Some(entry) = index
+                .articles
+                .iter_mut()
+                .find(|e| e.metadata.id == article_id)
+            {
+                entry.read_progress = progress;
+            }
+        })
+    }
+
+    pub fn delete_article(article_id: &str) -> Result<(), String> {
+        let path = Self::article_path(article_id);
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+        }
+        ImageStore::release_article(article_id)?;
+        Self::update_fulltext_index(|index| index.remove(article_id));
+        Self::update_index(|index| {
+            index.articles.retain(|e| e.metadata.id != article_id);
+            index.broken.retain(|b| b.id != article_id);
+        })
+    }
+
+    fn fulltext_index_path() -> PathBuf {
+        Self::base_path().join("fulltext.json")
+    }
+
+    fn read_fulltext_index() -> FullTextIndex {
+        let index: FullTextIndex = read_json(&Self::fulltext_index_path());
+        if index.is_current() {
+            index
+        } else {
+            FullTextIndex::default()
+        }
+    }
+
+    /// Failures are only logged, the index is synced with the saved articles before searching.
+    fn update_fulltext_index(f: impl FnOnce(&mut FullTextIndex)) {
+        let _guard = FULLTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = Self::read_fulltext_index();
+        f(&mut index);
+        if let Err(e) = write_json(&Self::fulltext_index_path(), &index) {
+            log::warn!("Failed to write full-text index: {}", e);
+        }
+    }
+
+    /// Full-text index of the saved articles. The articles saved before the index
+    /// existed are indexed and the deleted ones are removed first.
+    pub fn fulltext_index() -> FullTextIndex {
+        let saved: BTreeSet<String> = Self::list_saved_articles()
+            .into_iter()
+            .map(|entry| entry.metadata.id)
+            .collect();
+
+        let _guard = FULLTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = Self::read_fulltext_index();
+        let known: BTreeSet<String> = index.known_article_ids().cloned().collect();
+        if known == saved {
+            return index;
+        }
+
+        for article_id in known.difference(&saved) {
+            index.remove(article_id);
+        }
+        for article_id in saved.difference(&known) {
+            match Self::read_saved_article(article_id) {
+                Ok(saved) => index.add(&saved.metadata, &saved.content),
+                Err(e) => {
+                    log::warn!("Failed to index saved article {}: {}", article_id, e);
+                    index.add_failed(article_id);
+                }
+            }
+        }
+        if let Err(e) = write_json(&Self::fulltext_index_path(), &index) {
+            log::warn!("Failed to write full-text index: {}", e);
+        }
+        index
+    }
+
+    pub fn collect_image_garbage() -> Result<GarbageCollectionStats, String> {
+        let mut unreadable: Vec<String> = Self::list_broken_articles()
+            .into_iter()
+            .map(|broken| broken.id)
+            .collect();
+
+        let mut used: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for entry in Self::list_saved_articles() {
+            let article_id = entry.metadata.id;
+            let saved = match Self::read_saved_article(&article_id) {
+                Ok(saved) => saved,
+                Err(e) => {
+                    log::warn!("Failed to read saved article {}: {}", article_id, e);
+                    unreadable.push(article_id);
+                    continue;
+                }
+            };
+            let mut urls = Self::collect_image_urls(&saved.content);
+            urls.push(saved.metadata.image_url);
+
+            for file_name in urls
+                .iter()
+                .filter_map(|url| ImageStore::file_name_from_url(url))
+            {
+                used.entry(file_name)
+                    .or_default()
+                    .insert(article_id.clone());
+            }
+        }
+
+        ImageStore::collect_garbage(used, &unreadable)
+    }
+
+    /// Moves images of the articles saved before the image store from their own
+    /// `images` directories into the store.
+    pub fn migrate_legacy_images() {
+        for entry in Self::list_saved_articles() {
+            let article_id = entry.metadata.id;
+            if !Self::article_path(&article_id).join("images").is_dir() {
+                continue;
+            }
+            match Self::migrate_article_images(&article_id) {
+                Ok(()) => log::info!("Moved images of article {} into the store", article_id),
+                Err(e) => log::warn!("Failed to move images of article {}: {}", article_id, e),
+            }
+        }
+    }
+
+    fn migrate_article_images(article_id: &str) -> Result<(), String> {
+        let images_path = Self::article_path(article_id).join("images");
+        let mut saved = Self::read_saved_article(article_id)?;
+
+        let mut urls = Self::collect_image_urls(&saved.content);
+        urls.push(saved.metadata.image_url.clone());
+        let mut url_map = HashMap::new();
+        for url in urls {
+            let Some(path) = url.strip_prefix("file://").map(Path::new) else {
+                continue;
+            };
+            if path.parent() != Some(images_path.as_path()) {
+                continue;
+            }
+            let bytes = fs::read(path).map_err(|e| e.to_string())?;
+            let ext = path
+                .extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .unwrap_or_default();
+            let file_name = ImageStore::store(&bytes, &ext).map_err(|e| e.to_string())?;
+            url_map.insert(url, ImageStore::file_url(&file_name));
+        }
+
+        Self::replace_image_urls(&mut saved.content, &url_map);
+        if let Some(local_path) = url_map.get(&saved.metadata.image_url) {
+            saved.metadata.image_url = local_path.clone();
+        }
+        let json = serde_json::to_string_pretty(&saved).map_err(|e| e.to_string())?;
+        write_atomic(&Self::article_path(article_id).join("article.json"), json)
+            .map_err(|e| e.to_string())?;
+
+        let mut urls = Self::collect_image_urls(&saved.content);
+        urls.push(saved.metadata.image_url.clone());
+        let image_files = urls
+            .iter()
+            .filter_map(|url| ImageStore::file_name_from_url(url))
+            .collect();
+        Self::index_saved_article(saved.metadata, &image_files)?;
+
+        fs::remove_dir_all(&images_path).map_err(|e| e.to_string())
+    }
+
+    fn collect_image_urls(content: &[ArticleContent]) -> Vec<String> {
+        let mut urls = Vec::new();
+        for item in content {
+            match item {
+                ArticleContent::Image(url) => urls.push(url.clone()),
+                ArticleContent::UnorderedList(list) | ArticleContent::OrderedList(list) => {
+                    urls.extend(Self::collect_image_urls(list));
+                }
+                _ => {}
+            }
+        }
+        urls
+    }
+
+    fn replace_image_urls(content: &mut [ArticleContent], url_map: &HashMap<String, String>) {
+        for item in content {
+            match item {
+                ArticleContent::Image(url) => {
+                    if let Some(local) = url_map.get(url) {
+                        *url = local.clone();
+                    }
+                }
+                ArticleContent::UnorderedList(list) | ArticleContent::OrderedList(list) => {
+                    Self::replace_image_urls(list, url_map);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Downloads images into the image store, at most `MAX_PARALLEL_DOWNLOADS` at once.
+    /// Returns local urls of the downloaded images, failed ones are put into `progress`.
+    async fn download_images(
+        urls: BTreeSet<String>,
+        progress: &Arc<SaveProgress>,
+    ) -> Result<HashMap<String, String>, String> {
+        let mut url_map = HashMap::new();
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(MAX_PARALLEL_DOWNLOADS));
+        let mut tasks = tokio::task::JoinSet::new();
+        // Urls of the tasks, to report the ones which panicked.
+        let mut task_urls = HashMap::new();
+
+        progress.failed_images.write().unwrap().clear();
+        progress
+            .images_total
+            .fetch_add(urls.len(), Ordering::Relaxed);
+
+        for url in urls {
+            if url.starts_with("file://") {
+                progress.images_done.fetch_add(1, Ordering::Relaxed);
+                url_map.insert(url.clone(), url);
+                continue;
+            }
+
+            let semaphore = semaphore.clone();
+            let progress = progress.clone();
+            let task_url = url.clone();
+            let task = tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = if progress.is_cancelled() {
+                    Err(SAVE_CANCELLED.to_string())
+                } else {
+                    Self::download_image(&url, &progress).await
+                };
+                progress.images_done.fetch_add(1, Ordering::Relaxed);
+                (url, result)
+            });
+            task_urls.insert(task.id(), task_url);
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            let (url, result) = match joined {
+                Ok(downloaded) => downloaded,
+                Err(e) => {
+                    let url = task_urls.remove(&e.id()).unwrap_or_default();
+                    log::warn!("Failed to download image {}: {}", url, e);
+                    progress.failed_images.write().unwrap().push(url);
+                    continue;
+                }
+            };
+            match result {
+                Ok(local_path) => {
+                    url_map.insert(url, local_path);
+                }
+                Err(e) => {
+                    if !progress.is_cancelled() {
+                        log::warn!("Failed to download image {}: {}", url, e);
+                    }
+                    progress.failed_images.write().unwrap().push(url);
+                }
+            }
+        }
+
+        if progress.is_cancelled() {
+            return Err(SAVE_CANCELLED.to_string());
+        }
+        Ok(url_map)
+    }
+
+    async fn download_image(url: &str, progress: &SaveProgress) -> Result<String, String> {
+        let mut response = http_client()
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?;
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if progress.is_cancelled() {
+                return Err(SAVE_CANCELLED.to_string());
+            }
+            progress
+                .bytes
+                .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let ext = Self::guess_extension(url, &bytes);
+        let file_name = ImageStore::store(&bytes, ext).map_err(|e| e.to_string())?;
+        Ok(ImageStore::file_url(&file_name))
+    }
+
+    fn guess_extension(url: &str, bytes: &[u8]) -> &'static str {
+        if bytes.starts_with(b"\x89PNG") {
+            return ".png";
+        }
+        if bytes.starts_with(b"\xFF\xD8\xFF") {
+            return ".jpg";
+        }
+        if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            return ".gif";
+        }
+        if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP".as_slice()) {
+            return ".webp";
+        }
+
+        if let Some(ext) = url.split('.').next_back() {
+            match ext.to_lowercase().as_str() {
+                "png" => return ".png",
+                "jpg" | "jpeg" => return ".jpg",
+                "gif" => return ".gif",
+                "webp" => return ".webp",
+                "bmp" => return ".bmp",
+                _ => {}
+            }
+        }
+
+        ".bin"
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::article::ArticleData;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::article::test_article;
+
+    fn articles(ids: &[&str]) -> Vec<ArticleData> {
+        ids.iter()
+            .map(|id| ArticleData {
+                title: "Title".to_string(),
+                author: "author".to_string(),
+                ..test_article(id)
+            })
+            .collect()
+    }
+
+    fn ids(articles: Vec<&ArticleData>) -> Vec<&str> {
+        articles.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_new_articles() {
+        let mut known = KnownArticles::default();
+
+        assert!(known.update("hub:rust", &articles(&["2", "1"])).is_empty());
+        assert_eq!(
+            ids(known.update("hub:rust", &articles(&["4", "3", "2"]))),
+            ["4", "3"]
+        );
+        assert!(
+            known
+                .update("hub:rust", &articles(&["4", "3", "1"]))
+                .is_empty()
+        );
+        assert!(known.update("hub:go", &articles(&["4", "5"])).is_empty());
+
+        known.retain_sources(&["hub:go".to_string()]);
+        let json = serde_json::to_string(&known).unwrap();
+        let mut restored: KnownArticles = serde_json::from_str(&json).unwrap();
+
+        assert!(!restored.sources.contains_key("hub:rust"));
+        assert_eq!(
+            ids(restored.update("hub:go", &articles(&["6", "5"]))),
+            ["6"]
+        );
+    }
+}
+
+/// Known ids of one source are reset to its current articles above this limit.
+const MAX_KNOWN_IDS: usize = 300;
+
+/// Articles the new articles poller already saw, by the sources they were found in.
+/// Stored in `notifications.json` of the app data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KnownArticles {
+    #[serde(default)]
+    pub sources: BTreeMap<String, BTreeSet<String>>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl KnownArticles {
+    fn path() -> PathBuf {
+        app_data_dir().join("notifications.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save known articles: {}", e);
+        }
+    }
+
+    /// Remembers the current articles of the source and returns the ones seen for the
+    /// first time. The first check of a source only remembers its articles.
+    pub fn update<'a>(
+        &mut self,
+        source: &str,
+        articles: &'a [ArticleData],
+    ) -> Vec<&'a ArticleData> {
+        let Some(known) = self.sources.get_mut(source) else {
+            let ids = articles.iter().map(|a| a.id.clone()).collect();
+            self.sources.insert(source.to_string(), ids);
+            return vec![];
+        };
+
+        let new_articles: Vec<&ArticleData> =
+            articles.iter().filter(|a| !known.contains(&a.id)).collect();
+        known.extend(articles.iter().map(|a| a.id.clone()));
+        if known.len() > MAX_KNOWN_IDS {
+            *known = articles.iter().map(|a| a.id.clone()).collect();
+        }
+        new_articles
+    }
+
+    /// Forgets the sources which are not followed anymore.
+    pub fn retain_sources(&mut self, sources: &[String]) {
+        self.sources.retain(|source, _| sources.contains(source));
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::search::SearchQuery;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_searches_are_deduplicated() {
+        let mut searches = Searches::default();
+        searches.add_recent(SearchQuery::new("rust"));
+        searches.add_recent(SearchQuery::new("egui"));
+        searches.add_recent(SearchQuery::new("rust"));
+
+        assert_eq!(searches.recent.len(), 2);
+        assert_eq!(searches.recent[0].text, "rust");
+
+        for i in 0..MAX_RECENT_SEARCHES + 5 {
+            searches.add_recent(SearchQuery::new(i.to_string()));
+        }
+        assert_eq!(searches.recent.len(), MAX_RECENT_SEARCHES);
+    }
+}
+
+const MAX_RECENT_SEARCHES: usize = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: SearchQuery,
+}
+
+/// Recent and saved searches, stored in `searches.json` of the app data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Searches {
+    #[serde(default)]
+    pub recent: Vec<SearchQuery>,
+    #[serde(default)]
+    pub saved: Vec<SavedSearch>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl Searches {
+    fn path() -> PathBuf {
+        app_data_dir().join("searches.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save searches: {}", e);
+        }
+    }
+
+    pub fn add_recent(&mut self, query: SearchQuery) {
+        self.recent.retain(|q| *q != query);
+        self.recent.insert(0, query);
+        self.recent.truncate(MAX_RECENT_SEARCHES);
+    }
+
+    pub fn clear_recent(&mut self) {
+        self.recent.clear();
+    }
+
+    pub fn is_saved(&self, query: &SearchQuery) -> bool {
+        self.saved.iter().any(|s| s.query == *query)
+    }
+
+    pub fn save_search(&mut self, name: String, query: SearchQuery) {
+        self.saved.retain(|s| s.query != query);
+        self.saved.push(SavedSearch { name, query });
+    }
+
+    pub fn remove_saved(&mut self, index: usize) {
+        if index < self.saved.len() {
+            self.saved.remove(index);
+        }
+    }
+}
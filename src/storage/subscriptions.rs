@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app_data_dir, read_json, write_json};
+use crate::habr_client::hub::Hub;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habr_client::hub::HubStatistics;
+
+    fn hub(alias: &str, title: &str) -> Hub {
+        Hub {
+            id: alias.to_string(),
+            alias: alias.to_string(),
+            title: title.to_string(),
+            description_html: String::new(),
+            common_tags: vec![],
+            image_url: String::new(),
+            statistics: HubStatistics {
+                subscribers_count: 1,
+                rating: 1.,
+            },
+        }
+    }
+
+    #[test]
+    fn test_subscriptions_round_trip() {
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.toggle(&hub("rust", "Rust"));
+        subscriptions.toggle(&hub("go", "Go"));
+        subscriptions.toggle(&hub("python", "Python"));
+        subscriptions.toggle(&hub("go", "Go"));
+
+        let json = serde_json::to_string(&subscriptions).unwrap();
+        let restored: Subscriptions = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.aliases(), ["python", "rust"]);
+        assert!(restored.is_subscribed("rust"));
+        assert!(!restored.is_subscribed("go"));
+    }
+}
+
+/// Hubs the user subscribed to, stored in `subscriptions.json` of the app data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subscriptions {
+    #[serde(default)]
+    pub hubs: Vec<Hub>,
+    #[serde(skip)]
+    pub save_error: Option<String>,
+}
+
+impl Subscriptions {
+    fn path() -> PathBuf {
+        app_data_dir().join("subscriptions.json")
+    }
+
+    pub fn load() -> Self {
+        read_json(&Self::path())
+    }
+
+    pub fn save(&mut self) {
+        self.save_error = write_json(&Self::path(), self).err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save subscriptions: {}", e);
+        }
+    }
+
+    pub fn is_subscribed(&self, hub_alias: &str) -> bool {
+        self.hubs.iter().any(|h| h.alias == hub_alias)
+    }
+
+    pub fn toggle(&mut self, hub: &Hub) {
+        if self.is_subscribed(&hub.alias) {
+            self.hubs.retain(|h| h.alias != hub.alias);
+        } else {
+            self.hubs.push(hub.clone());
+            self.hubs.sort_by(|a, b| a.title.cmp(&b.title));
+        }
+    }
+
+    pub fn aliases(&self) -> Vec<String> {
+        self.hubs.iter().map(|h| h.alias.clone()).collect()
+    }
+}
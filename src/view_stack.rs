@@ -2,6 +2,8 @@ use egui::{Pos2, Rect, TouchPhase, Vec2};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::app::ReadingState;
+
 pub struct ViewStack {
     backwarder: Backward,
     views: Vec<Rc<RefCell<dyn UiView>>>,
@@ -20,7 +22,9 @@ impl ViewStack {
     }
 
     pub fn pop(&mut self) -> Option<Rc<RefCell<dyn UiView>>> {
-        self.views.pop();
+        if let Some(view) = self.views.pop() {
+            view.borrow_mut().on_leave();
+        }
         None
     }
 
@@ -40,10 +44,22 @@ impl ViewStack {
             }
         }
     }
+
+    /// What is read in the top view, if it shows an article.
+    pub fn reading_state(&self) -> Option<ReadingState> {
+        self.views.last()?.borrow().reading_state()
+    }
 }
 
 pub trait UiView {
     fn ui(&mut self, ui: &mut egui::Ui, view_stack: &mut ViewStack);
+
+    fn reading_state(&self) -> Option<ReadingState> {
+        None
+    }
+
+    /// Called when the view is closed by going back.
+    fn on_leave(&mut self) {}
 }
 
 struct Backward {
@@ -2,31 +2,74 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
-#[cfg(not(feature = "aurora"))]
-use egui::OpenUrl;
 use egui::{
-    self, Color32, Image, Label, Layout, RichText, ScrollArea, Spinner, Ui, Widget,
-    scroll_area::ScrollSource,
+    self, Button, FontId, Label, Layout, ProgressBar, RichText, ScrollArea, Sense, Spinner,
+    TextEdit, Widget,
 };
-use egui::{FontId, Vec2};
 
-use crate::app::HabreState;
-use crate::habr_client::article::ArticleContent;
-use crate::habr_client::{HabrClient, html_parse::TypedText};
+use crate::app::{HabreState, ReadingState};
+use crate::habr_client::HabrClient;
+use crate::habr_client::article::{ArticleContent, ArticleData, PublicationType, minutes_left};
 use crate::storage::ArticleStorage;
+use crate::storage::export::{ExportFormat, export_article};
+use crate::storage::highlights::ArticleHighlights;
 use crate::view_stack::UiView;
+use crate::views::article_notes::ArticleNotes;
+use crate::views::article_renderer::ArticleRenderer;
+use crate::views::articles_list::ArticlesList;
+use crate::views::author_profile::AuthorProfile;
 use crate::views::comments::Comments;
+use crate::views::link_router::open_link;
+
+/// How often the reading position is written to the history while reading.
+const HISTORY_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+/// Frames to wait for the content to grow enough to restore the reading position.
+const RESTORE_FRAMES: u8 = 30;
+
+/// Highlight being created in the popup.
+struct HighlightDraft {
+    block: usize,
+    /// Plain text of the block.
+    text: String,
+    /// Selected characters of the text.
+    range: Option<(usize, usize)>,
+    note: String,
+}
 
 pub struct ArticleDetails {
     pub habre_state: Rc<RefCell<HabreState>>,
     is_loading: Arc<AtomicBool>,
     habr_client: HabrClient,
-    selected_code_scroll_id: Option<usize>,
+    article_id: Option<String>,
+    /// Card of the shown article, the selected one in the state may be another by now.
+    article: Option<ArticleData>,
+    /// Checked on load and when saving of the article finishes, not on every frame.
+    is_saved: bool,
+    was_saving: bool,
     article_title: Arc<RwLock<String>>,
+    article_author: Option<String>,
+    article_tags: Vec<String>,
+    publication_type: PublicationType,
+    reading_time: usize,
     article_content: Arc<RwLock<Vec<ArticleContent>>>,
     go_top: Arc<AtomicBool>,
-    image_viewer: ImageViewer,
+    read_progress: f32,
+    /// Reading position from the history to scroll to.
+    restore_offset: Option<f32>,
+    restore_frames: u8,
+    /// Offset where the restore gave up, the saved position is kept until the user scrolls.
+    restore_stopped_at: Option<f32>,
+    history_changed: bool,
+    history_saved_at: Instant,
+    renderer: ArticleRenderer,
+    highlights: Rc<RefCell<ArticleHighlights>>,
+    highlight_draft: Option<HighlightDraft>,
+    export_status: Arc<RwLock<Option<String>>>,
+    load_error: Option<String>,
+    /// Card of the article opened by id, it's loaded before the content.
+    fetched_article: Arc<RwLock<Option<Result<ArticleData, String>>>>,
 }
 
 impl ArticleDetails {
@@ -35,32 +78,103 @@ impl ArticleDetails {
             habre_state,
             is_loading: Default::default(),
             habr_client: HabrClient::new(),
+            article_id: None,
+            article: None,
+            is_saved: false,
+            was_saving: false,
             article_title: Default::default(),
+            article_author: None,
+            article_tags: Vec::new(),
+            publication_type: PublicationType::Article,
+            reading_time: 0,
             article_content: Default::default(),
-            selected_code_scroll_id: None,
             go_top: Default::default(),
-            image_viewer: ImageViewer::new(),
+            read_progress: 0.,
+            restore_offset: None,
+            restore_frames: 0,
+            restore_stopped_at: None,
+            history_changed: false,
+            history_saved_at: Instant::now(),
+            renderer: ArticleRenderer::new(),
+            highlights: Default::default(),
+            highlight_draft: None,
+            export_status: Default::default(),
+            load_error: None,
+            fetched_article: Default::default(),
         }
     }
 
     pub fn load_saved(&mut self, article_id: &str) {
-        if let Some((data, content)) = ArticleStorage::load_article(article_id) {
-            *self.article_title.write().unwrap() = data.title;
-            *self.article_content.write().unwrap() = content;
-            self.go_top.store(true, Ordering::Relaxed);
+        match ArticleStorage::load_article(article_id) {
+            Ok((data, content)) => {
+                self.record_open(&data);
+                self.load_highlights(&data);
+                *self.export_status.write().unwrap() = None;
+                self.article = Some(data.clone());
+                self.is_saved = true;
+                self.article_id = Some(data.id.clone());
+                self.article_author = Some(data.author.clone());
+                self.article_tags = data.tags.clone();
+                self.publication_type = data.publication_type;
+                self.reading_time = data.reading_time;
+                *self.article_title.write().unwrap() = data.title;
+                *self.article_content.write().unwrap() = content;
+                self.load_error = None;
+                self.go_top.store(true, Ordering::Relaxed);
+            }
+            Err(e) => {
+                log::warn!("Failed to load saved article {}: {}", article_id, e);
+                self.article_id = None;
+                self.article = None;
+                self.is_saved = false;
+                self.article_title.write().unwrap().clear();
+                self.article_author = None;
+                self.article_tags.clear();
+                self.article_content.write().unwrap().clear();
+                self.load_error = Some(e);
+            }
         }
     }
 
+    /// Opens the article not present in any list, e.g. from a link.
+    pub fn load_by_id(&mut self, article_id: &str) {
+        self.load_error = None;
+        self.is_loading.store(true, Ordering::Relaxed);
+
+        let client = self.habr_client.clone();
+        let fetched_article = self.fetched_article.clone();
+        let article_id = article_id.to_string();
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let result = client
+                .get_article(&article_id)
+                .await
+                .map_err(|e| e.to_string());
+            *fetched_article.write().unwrap() = Some(result);
+        });
+    }
+
     pub fn load_data(&mut self) {
+        self.load_error = None;
         self.is_loading.store(true, Ordering::Relaxed);
-        let article_id = self
-            .habre_state
-            .borrow()
-            .selected_article
-            .as_ref()
-            .unwrap()
-            .id
-            .clone();
+        let article = self.habre_state.borrow().selected_article.clone().unwrap();
+        self.record_open(&article);
+        self.load_highlights(&article);
+        *self.export_status.write().unwrap() = None;
+        self.article = Some(article.clone());
+        self.is_saved = ArticleStorage::is_article_saved(&article.id);
+        let ArticleData {
+            id: article_id,
+            author: article_author,
+            tags: article_tags,
+            publication_type,
+            reading_time,
+            ..
+        } = article;
+        self.article_id = Some(article_id.clone());
+        self.article_author = Some(article_author);
+        self.article_tags = article_tags;
+        self.publication_type = publication_type;
+        self.reading_time = reading_time;
         let client = self.habr_client.clone();
         let current_content = self.article_content.clone();
         let is_loading = self.is_loading.clone();
@@ -80,173 +194,384 @@ impl ArticleDetails {
             }
         });
     }
+
+    fn record_open(&mut self, article: &ArticleData) {
+        let now = chrono::Local::now().timestamp();
+        self.habre_state
+            .borrow()
+            .history
+            .borrow_mut()
+            .record_open(article, now);
+        self.history_changed = true;
+        self.save_history();
+    }
+
+    /// Reading position of the article saved in the history.
+    fn saved_offset(&self) -> f32 {
+        let Some(article_id) = self.article_id.as_ref() else {
+            return 0.;
+        };
+        let state = self.habre_state.borrow();
+        let history = state.history.borrow();
+        history.get(article_id).map_or(0., |e| e.scroll_offset)
+    }
+
+    fn update_position(&mut self, scroll_offset: f32) {
+        if let Some(article_id) = self.article_id.as_ref() {
+            let state = self.habre_state.borrow();
+            self.history_changed |= state.history.borrow_mut().update_position(
+                article_id,
+                scroll_offset,
+                self.read_progress,
+            );
+        }
+        if self.history_saved_at.elapsed() >= HISTORY_SAVE_INTERVAL {
+            self.save_history();
+        }
+    }
+
+    fn save_history(&mut self) {
+        if self.history_changed {
+            self.habre_state.borrow().history.borrow_mut().save();
+            self.history_changed = false;
+            if let Some(article_id) = self.article_id.as_ref()
+                && self.is_saved
+                && let Err(e) = ArticleStorage::set_read_progress(article_id, self.read_progress)
+            {
+                log::warn!("Failed to save reading progress of {}: {}", article_id, e);
+            }
+        }
+        self.history_saved_at = Instant::now();
+    }
+
+    fn load_highlights(&mut self, article: &ArticleData) {
+        let mut highlights = ArticleHighlights::load(&article.id);
+        highlights.title = article.title.clone();
+        self.highlights = Rc::new(RefCell::new(highlights));
+        self.highlight_draft = None;
+    }
+
+    /// Anchors the highlights to the loaded content.
+    fn relocate_highlights(&self) {
+        let blocks: Vec<String> = self
+            .article_content
+            .read()
+            .unwrap()
+            .iter()
+            .map(|content| content.plain_text())
+            .collect();
+        let mut highlights = self.highlights.borrow_mut();
+        if highlights.relocate(&blocks) {
+            highlights.save();
+        }
+    }
+
+    fn start_highlight(&mut self, block: usize) {
+        let text = self.article_content.read().unwrap()[block].plain_text();
+        self.highlight_draft = Some(HighlightDraft {
+            block,
+            text,
+            range: None,
+            note: String::new(),
+        });
+    }
+
+    fn highlight_popup_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(draft) = self.highlight_draft.as_mut() else {
+            return;
+        };
+        let screen_rect = ui.ctx().viewport_rect();
+
+        let mut close = egui::Area::new(egui::Id::new("highlight_popup_overlay"))
+            .fixed_pos(screen_rect.min)
+            .interactable(true)
+            .show(ui.ctx(), |ui| {
+                let (resp, painter) = ui.allocate_painter(screen_rect.size(), Sense::click());
+                painter.add(egui::Shape::rect_filled(
+                    screen_rect,
+                    egui::CornerRadius::ZERO,
+                    egui::Color32::from_black_alpha(150),
+                ));
+                resp
+            })
+            .inner
+            .clicked();
+        let mut save = false;
+
+        egui::Window::new("Выделение")
+            .default_rect(screen_rect)
+            .collapsible(false)
+            .resizable(false)
+            .movable(false)
+            .title_bar(true)
+            .order(egui::Order::Foreground)
+            .show(ui.ctx(), |ui| {
+                ui.label(RichText::new("Выделите фрагмент текста").size(25.).weak());
+                ScrollArea::vertical()
+                    .max_height(screen_rect.height() / 2.)
+                    .show(ui, |ui| {
+                        let output = TextEdit::multiline(&mut draft.text.as_str())
+                            .font(FontId::proportional(29.))
+                            .desired_width(f32::INFINITY)
+                            .show(ui);
+                        if let Some(range) = output.cursor_range {
+                            let range = range.as_sorted_char_range();
+                            if !range.is_empty() {
+                                draft.range = Some((range.start, range.end));
+                            }
+                        }
+                    });
+
+                ui.add_space(10.);
+                ui.label(RichText::new("Заметка").size(29.).strong());
+                ui.add(
+                    TextEdit::multiline(&mut draft.note)
+                        .font(FontId::proportional(25.))
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(3),
+                );
+
+                ui.add_space(10.);
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("Отмена").size(32.)).clicked() {
+                        close = true;
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        let save_button =
+                            Button::new(RichText::new("Сохранить").size(32.).strong());
+                        if ui.add_enabled(draft.range.is_some(), save_button).clicked() {
+                            save = true;
+                        }
+                    });
+                });
+            });
+
+        if save && let Some((start, end)) = draft.range {
+            let quote = draft.text.chars().skip(start).take(end - start).collect();
+            let mut highlights = self.highlights.borrow_mut();
+            highlights.add(
+                draft.block,
+                start,
+                end,
+                quote,
+                draft.note.trim().to_string(),
+                chrono::Local::now().timestamp(),
+            );
+            highlights.save();
+            close = true;
+        }
+        if close {
+            self.highlight_draft = None;
+        }
+    }
+
+    /// Images are copied with the saved articles only, the others are linked.
+    fn export_article(&self, format: ExportFormat) {
+        let Some(article) = self.article.clone() else {
+            return;
+        };
+        let content = self.article_content.read().unwrap().clone();
+        let status = self.export_status.clone();
+        *status.write().unwrap() = Some(format!("Экспорт в {}...", format.name()));
+
+        self.habre_state
+            .borrow()
+            .async_handle()
+            .spawn_blocking(move || {
+                let message = match export_article(&article, &content, format) {
+                    Ok(path) => format!("Статья сохранена в {}", path.to_string_lossy()),
+                    Err(e) => format!("Не удалось экспортировать статью: {}", e),
+                };
+                *status.write().unwrap() = Some(message);
+            });
+    }
+
+    /// Returns true if the notes are requested.
+    fn progress_ui(&self, ui: &mut egui::Ui) -> bool {
+        let mut notes_clicked = false;
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                let notes_count = self.highlights.borrow().highlights.len();
+                let notes_text = if notes_count > 0 {
+                    format!("Заметки ({})", notes_count)
+                } else {
+                    "Заметки".to_string()
+                };
+                notes_clicked = ui.button(RichText::new(notes_text).size(22.)).clicked();
+                ui.menu_button(RichText::new("Экспорт").size(22.), |ui| {
+                    ui.spacing_mut().button_padding = [25., 15.].into();
+                    for format in [ExportFormat::Markdown, ExportFormat::Html] {
+                        if ui.button(RichText::new(format.name()).size(29.)).clicked() {
+                            self.export_article(format);
+                        }
+                    }
+                });
+                if let Some(article_id) = self.article_id.as_ref()
+                    && self.is_saved
+                    && ui.button(RichText::new("Коллекции").size(22.)).clicked()
+                {
+                    self.habre_state
+                        .borrow()
+                        .collections_popup
+                        .borrow_mut()
+                        .open(article_id, &self.article_title.read().unwrap());
+                }
+                let left = minutes_left(self.reading_time, self.read_progress);
+                let text = if left > 0 {
+                    format!("Осталось ~{} мин", left)
+                } else {
+                    "Дочитано".to_string()
+                };
+                ui.label(RichText::new(text).size(22.).weak());
+                ProgressBar::new(self.read_progress)
+                    .desired_height(8.)
+                    .ui(ui);
+            });
+        });
+        notes_clicked
+    }
 }
 
 impl UiView for ArticleDetails {
     fn ui(&mut self, ui: &mut egui::Ui, view_stack: &mut crate::view_stack::ViewStack) {
+        if let Some(article_id) = self.article_id.as_ref() {
+            let is_saving = self
+                .habre_state
+                .borrow()
+                .article_saver
+                .is_saving(article_id);
+            if self.was_saving && !is_saving {
+                self.is_saved = ArticleStorage::is_article_saved(article_id);
+            }
+            self.was_saving = is_saving;
+        }
+        let fetched_article = self.fetched_article.write().unwrap().take();
+        match fetched_article {
+            Some(Ok(article)) => {
+                self.habre_state.borrow_mut().selected_article = Some(article);
+                self.load_data();
+            }
+            Some(Err(e)) => {
+                self.load_error = Some(e);
+                self.is_loading.store(false, Ordering::Relaxed);
+            }
+            None => {}
+        }
+
         ui.vertical(|ui| {
             if self.is_loading.load(Ordering::Relaxed) {
                 ui.add_sized(ui.available_size(), Spinner::new().size(100.));
+            } else if let Some(error) = self.load_error.as_ref() {
+                ui.add(
+                    Label::new(
+                        RichText::new(format!("Не удалось открыть статью: {}", error)).size(29.),
+                    )
+                    .wrap(),
+                );
             } else {
+                if self.progress_ui(ui) {
+                    let notes = ArticleNotes::new(self.highlights.clone());
+                    view_stack.push(Rc::new(RefCell::new(notes)));
+                }
+                if let Some(status) = self.export_status.read().unwrap().as_ref() {
+                    ui.add(Label::new(RichText::new(status).size(22.).weak()).wrap());
+                }
+
                 let mut scroll_area = ScrollArea::vertical()
                     // .auto_shrink(false)
                     .max_height(ui.available_height())
-                    .scroll_source(
-                        self.image_viewer
-                            .image_url
-                            .as_ref()
-                            .map_or(egui::scroll_area::ScrollSource::ALL, |_| {
-                                egui::scroll_area::ScrollSource::NONE
-                            }),
-                    );
+                    .scroll_source(if self.renderer.is_viewing_image() {
+                        egui::scroll_area::ScrollSource::NONE
+                    } else {
+                        egui::scroll_area::ScrollSource::ALL
+                    });
 
                 if self.go_top.load(Ordering::Relaxed) {
                     scroll_area = scroll_area.vertical_scroll_offset(0.);
-                    self.go_top.store(false, Ordering::Relaxed)
+                    self.go_top.store(false, Ordering::Relaxed);
+                    self.read_progress = 0.;
+                    let saved_offset = self.saved_offset();
+                    self.restore_offset = (saved_offset > 0.).then_some(saved_offset);
+                    self.restore_frames = RESTORE_FRAMES;
+                    self.restore_stopped_at = None;
+                    self.relocate_highlights();
                 };
+                if let Some(offset) = self.restore_offset {
+                    scroll_area = scroll_area.vertical_scroll_offset(offset);
+                }
 
-                scroll_area.show(ui, |ui| {
-                    ui.horizontal(|ui| {
-                        ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
-                            ui.add(
-                                Label::new(
-                                    RichText::new(self.article_title.read().unwrap().as_str())
-                                        .heading()
-                                        .strong()
-                                        .size(40.),
-                                )
-                                .selectable(false)
-                                .wrap(),
-                            );
-                        });
-                    });
+                let scroll_output = scroll_area.show(ui, |ui| {
+                    if let Some(badge) = self.publication_type.badge() {
+                        ui.label(
+                            RichText::new(badge)
+                                .size(25.)
+                                .strong()
+                                .color(ui.visuals().hyperlink_color),
+                        );
+                    }
 
-                    for (i, content) in self.article_content.read().unwrap().iter().enumerate() {
-                        match content {
-                            ArticleContent::Header(h_lvl, content) => {
-                                ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
-                                    ui.add(
-                                        Label::new(
-                                            RichText::new(content)
-                                                .heading()
-                                                .strong()
-                                                .size(36. - *h_lvl as f32),
-                                        )
-                                        .selectable(false)
-                                        .wrap(),
-                                    );
-                                });
-                            }
-                            ArticleContent::Code { lang, content } => {
-                                ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
-                                    let code_scroll = ScrollArea::horizontal()
-                                        .id_salt(i)
-                                        .scroll_source(self.selected_code_scroll_id.map_or(
-                                            ScrollSource::NONE,
-                                            |current_idx| {
-                                                if current_idx == i {
-                                                    ScrollSource::ALL
-                                                } else {
-                                                    ScrollSource::NONE
-                                                }
-                                            },
-                                        ));
-                                    if code_scroll
-                                        .show(ui, |ui| code_view(ui, content, lang))
-                                        .inner
-                                        .clicked()
-                                    {
-                                        if self
-                                            .selected_code_scroll_id
-                                            .take_if(|current_idx| *current_idx == i)
-                                            .is_none()
-                                        {
-                                            self.selected_code_scroll_id = Some(i);
-                                        };
-                                    };
-                                });
-                            }
-                            ArticleContent::Blockquote(content) => {
-                                ui.horizontal(|ui| {
-                                    egui::Frame::new()
-                                        .inner_margin(egui::Margin::symmetric(25, 15))
-                                        .outer_margin(egui::Margin::symmetric(0, 10))
-                                        .fill(Color32::GRAY)
-                                        .corner_radius(10)
-                                        .show(ui, |ui| {
-                                            ui.vertical(|ui| {
-                                                ui.spacing_mut().item_spacing =
-                                                    egui::Vec2::new(0.0, 5.0);
-                                                egui::Image::from_bytes(
-                                                    "bytes://double-quotes",
-                                                    include_bytes!(
-                                                        "../../assets/double-quotes.png"
-                                                    ),
-                                                )
-                                                .fit_to_exact_size((15., 15.).into())
-                                                .ui(ui);
-                                                egui::Label::new(
-                                                    egui::RichText::new(content)
-                                                        .size(25.)
-                                                        .color(Color32::BLACK)
-                                                        .italics(),
-                                                )
-                                                .wrap()
-                                                .selectable(false)
-                                                .ui(ui);
-                                            });
-                                        });
-                                });
-                            }
-                            ArticleContent::Paragraph(conetnt_stream) => {
-                                ui.horizontal_wrapped(|ui| {
-                                    ui.spacing_mut().item_spacing.x = 0.0;
-                                    for content in conetnt_stream {
-                                        typed_text_ui(ui, &content)
-                                    }
-                                });
-                            }
-                            ArticleContent::Image(src) => {
-                                ui.with_layout(
-                                    Layout::top_down_justified(egui::Align::Center),
-                                    |ui| {
-                                        let img = Image::new(src)
-                                            .max_width(ui.available_width())
-                                            .fit_to_original_size(1.)
-                                            .sense(egui::Sense::click());
-
-                                        if ui.add(img).clicked() {
-                                            self.image_viewer.set_image_url(src.clone());
-                                        }
-                                    },
+                    // Posts have no title, their text starts right after the author.
+                    if self.publication_type != PublicationType::Post {
+                        ui.horizontal(|ui| {
+                            ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                                ui.add(
+                                    Label::new(
+                                        RichText::new(self.article_title.read().unwrap().as_str())
+                                            .heading()
+                                            .strong()
+                                            .size(40.),
+                                    )
+                                    .selectable(false)
+                                    .wrap(),
                                 );
-                            }
-                            ArticleContent::BR => ui.add_space(5.),
-                            ArticleContent::Text(text) => {
-                                match text {
-                                    TypedText::Common(text) => {
-                                        ui.add(
-                                            Label::new(RichText::new(text).size(29.))
-                                                .wrap()
-                                                .selectable(false),
-                                        );
-                                    }
-                                    _ => {}
-                                };
-                            }
-                            ArticleContent::UnorderedList(list)
-                            | ArticleContent::OrderedList(list) => {
-                                for item in list.iter() {
-                                    if let ArticleContent::Paragraph(p_content) = item {
-                                        ui.horizontal_wrapped(|ui| {
-                                            ui.spacing_mut().item_spacing.x = 0.0;
-                                            for typed_text in p_content {
-                                                typed_text_ui(ui, typed_text);
-                                            }
-                                        });
-                                    }
+                            });
+                        });
+                    }
+
+                    if let Some(author) = self.article_author.as_ref() {
+                        let author_label = Label::new(
+                            RichText::new(author.as_str())
+                                .strong()
+                                .size(25.)
+                                .color(ui.visuals().hyperlink_color),
+                        )
+                        .selectable(false)
+                        .sense(Sense::click())
+                        .ui(ui);
+                        if author_label.clicked() {
+                            let mut author_profile = AuthorProfile::new(self.habre_state.clone());
+                            author_profile.load(author);
+                            view_stack.push(Rc::new(RefCell::new(author_profile)));
+                        }
+                    }
+
+                    self.renderer.content_ui(
+                        ui,
+                        &self.article_content.read().unwrap(),
+                        &self.highlights.borrow(),
+                    );
+
+                    if !self.article_tags.is_empty() {
+                        ui.add_space(5.);
+                        let mut selected_tag = None;
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(RichText::new("Теги:").size(22.).weak());
+                            for tag in self.article_tags.iter() {
+                                let tag_label = Label::new(
+                                    RichText::new(tag.as_str())
+                                        .size(22.)
+                                        .color(ui.visuals().hyperlink_color),
+                                )
+                                .selectable(false)
+                                .sense(Sense::click())
+                                .ui(ui);
+                                if tag_label.clicked() {
+                                    selected_tag = Some(tag.clone());
                                 }
                             }
+                        });
+                        if let Some(tag) = selected_tag {
+                            ArticlesList::push_tag_feed(self.habre_state.clone(), &tag, view_stack);
                         }
                     }
 
@@ -289,130 +614,58 @@ impl UiView for ArticleDetails {
                     }
                 });
 
-                if self.image_viewer.image_url.is_some() {
-                    ui.put(ui.ctx().content_rect(), |ui: &mut egui::Ui| {
-                        egui::Frame::NONE
-                            .fill(Color32::from_black_alpha(200))
-                            .outer_margin(0)
-                            .inner_margin(0)
-                            .show(ui, |ui| self.image_viewer.ui(ui))
-                            .response
-                    });
+                let scroll_offset = scroll_output.state.offset.y;
+                let max_offset = scroll_output.content_size.y - scroll_output.inner_rect.height();
+                if max_offset > 0. {
+                    self.read_progress = (scroll_offset / max_offset).clamp(0., 1.);
                 }
-            }
-        });
-    }
-}
-
-struct ImageViewer {
-    image_url: Option<String>,
-    scene_rect: egui::Rect,
-}
-
-impl ImageViewer {
-    fn new() -> Self {
-        Self {
-            image_url: None,
-            scene_rect: egui::Rect::ZERO,
-        }
-    }
-
-    fn set_image_url(&mut self, image_url: String) {
-        self.image_url = Some(image_url);
-    }
 
-    fn ui(&mut self, ui: &mut Ui) {
-        if let Some(image_url) = self.image_url.as_ref() {
-            let image_url = image_url.clone();
-            ui.with_layout(Layout::top_down_justified(egui::Align::Center), |ui| {
-                let cross_rect = egui::Rect::from_center_size(
-                    (ui.available_width() - 25., ui.clip_rect().top() + 25.).into(),
-                    (25., 25.).into(),
-                );
-
-                if ui.allocate_rect(cross_rect, egui::Sense::CLICK).clicked() {
-                    self.image_url = None;
-                    self.scene_rect = egui::Rect::ZERO;
+                // The content grows while images are loading, so the saved position may
+                // be reachable only after a few frames.
+                if let Some(offset) = self.restore_offset {
+                    self.restore_frames = self.restore_frames.saturating_sub(1);
+                    if (scroll_offset - offset).abs() < 1. {
+                        self.restore_offset = None;
+                    } else if self.restore_frames == 0 {
+                        self.restore_offset = None;
+                        self.restore_stopped_at = Some(scroll_offset);
+                    } else {
+                        ui.ctx().request_repaint();
+                    }
+                } else if let Some(stopped_at) = self.restore_stopped_at {
+                    if (scroll_offset - stopped_at).abs() >= 1. {
+                        self.restore_stopped_at = None;
+                        self.update_position(scroll_offset);
+                    }
+                } else {
+                    self.update_position(scroll_offset);
                 }
 
-                let painter = ui.painter_at(cross_rect);
-                painter.line_segment(
-                    [cross_rect.left_top(), cross_rect.right_bottom()],
-                    egui::Stroke::new(3.0, egui::Color32::LIGHT_GRAY),
-                );
-                painter.line_segment(
-                    [cross_rect.right_top(), cross_rect.left_bottom()],
-                    egui::Stroke::new(3.0, egui::Color32::LIGHT_GRAY),
-                );
-
-                let image = Image::new(image_url);
+                self.renderer.image_viewer_ui(ui);
+                if let Some(block) = self.renderer.take_highlight_block() {
+                    self.start_highlight(block);
+                }
+                self.highlight_popup_ui(ui);
+                if let Some(url) = self.renderer.take_clicked_link() {
+                    open_link(ui, self.habre_state.clone(), &url, view_stack);
+                }
+            }
+        });
+    }
 
-                egui::Scene::new()
-                    .max_inner_size(image.size().unwrap_or(Vec2::from([1000.0, 1200.0])))
-                    .zoom_range(0.5..=3.0)
-                    .show(ui, &mut self.scene_rect, |ui| ui.add(image));
-            });
-        }
+    fn on_leave(&mut self) {
+        self.save_history();
     }
-}
 
-fn typed_text_ui(ui: &mut egui::Ui, content: &TypedText) {
-    match content {
-        TypedText::Code(text) => {
-            ui.label(RichText::new(text).code().size(29.));
-        }
-        TypedText::Link { url, value } => {
-            if ui
-                .link(
-                    RichText::new(value)
-                        .size(29.)
-                        .color(ui.ctx().theme().default_visuals().hyperlink_color),
-                )
-                .clicked()
-            {
-                // ToDo: Add Aurora OS url open call
-                #[cfg(feature = "aurora")]
-                aurora_services::open_uri::open_uri(url, |_| {
-                    // Do something with the response
-                });
-                #[cfg(not(feature = "aurora"))]
-                ui.ctx().open_url(OpenUrl::new_tab(url));
-            }
-        }
-        TypedText::Common(text) => {
-            ui.add(
-                Label::new(RichText::new(text).size(29.))
-                    .wrap()
-                    .selectable(false),
-            );
-        }
-        TypedText::Italic(text) => {
-            ui.add(
-                Label::new(RichText::new(text).size(29.).italics())
-                    .wrap()
-                    .selectable(false),
-            );
-        }
-        TypedText::Strong(text) => {
-            ui.add(
-                Label::new(RichText::new(text).size(29.).strong())
-                    .wrap()
-                    .selectable(false),
-            );
+    fn reading_state(&self) -> Option<ReadingState> {
+        if self.is_loading.load(Ordering::Relaxed) || self.load_error.is_some() {
+            return None;
         }
+        Some(ReadingState {
+            article_id: self.article_id.clone()?,
+            title: self.article_title.read().unwrap().clone(),
+            author: self.article_author.clone().unwrap_or_default(),
+            progress: self.read_progress,
+        })
     }
 }
-
-fn code_view(ui: &mut Ui, code: &str, lang: &str) -> egui::Response {
-    ui.ctx().global_style_mut(|style| {
-        style.text_styles.insert(
-            egui::TextStyle::Monospace,
-            FontId {
-                size: 22.,
-                family: egui::FontFamily::Monospace,
-            },
-        );
-    });
-    let theme = egui_extras::syntax_highlighting::CodeTheme::from_memory(ui.ctx(), ui.style());
-    egui_extras::syntax_highlighting::code_view_ui(ui, &theme, code, lang)
-}
@@ -0,0 +1,279 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use egui::{
+    self, FontId, Frame, Label, Layout, RichText, ScrollArea, Sense, TextEdit, Ui, UiBuilder,
+    Widget,
+};
+use egui_flex::Flex;
+
+use crate::storage::highlights::{ArticleHighlights, Highlight};
+use crate::storage::{documents_dir, write_atomic};
+use crate::view_stack::{UiView, ViewStack};
+use crate::widgets::context_menu_button;
+
+enum NoteAction {
+    Edit(u64),
+    Remove(u64),
+}
+
+/// Highlights and notes of the article opened in `ArticleDetails`.
+pub struct ArticleNotes {
+    highlights: Rc<RefCell<ArticleHighlights>>,
+    /// Id of the highlight which note is edited and the new note.
+    editing: Option<(u64, String)>,
+    export_result: Option<Result<PathBuf, String>>,
+}
+
+impl ArticleNotes {
+    pub fn new(highlights: Rc<RefCell<ArticleHighlights>>) -> Self {
+        Self {
+            highlights,
+            editing: None,
+            export_result: None,
+        }
+    }
+
+    /// Saves the notes to the documents dir and copies them to the clipboard.
+    fn export_markdown(&mut self, ctx: &egui::Context) {
+        let highlights = self.highlights.borrow();
+        let markdown = highlights.to_markdown();
+        ctx.copy_text(markdown.clone());
+        let path = documents_dir().join(format!("notes-{}.md", highlights.article_id));
+        self.export_result = Some(
+            write_atomic(&path, markdown)
+                .map(|_| path)
+                .map_err(|e| e.to_string()),
+        );
+    }
+
+    fn editing_ui(&mut self, ui: &mut Ui) {
+        let Some((id, note)) = self.editing.as_mut() else {
+            return;
+        };
+        let mut save = false;
+        let mut cancel = false;
+        ui.add(
+            TextEdit::multiline(note)
+                .font(FontId::proportional(25.))
+                .desired_width(f32::INFINITY)
+                .desired_rows(3),
+        );
+        ui.horizontal(|ui| {
+            if ui.button(RichText::new("Отмена").size(29.)).clicked() {
+                cancel = true;
+            }
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("Сохранить").size(29.).strong())
+                    .clicked()
+                {
+                    save = true;
+                }
+            });
+        });
+
+        if save {
+            let mut highlights = self.highlights.borrow_mut();
+            highlights.set_note(*id, note.trim().to_string());
+            highlights.save();
+        }
+        if save || cancel {
+            self.editing = None;
+        }
+    }
+}
+
+impl UiView for ArticleNotes {
+    fn ui(&mut self, ui: &mut egui::Ui, _view_stack: &mut ViewStack) {
+        let highlights = self.highlights.clone();
+        let mut export = false;
+
+        Flex::vertical()
+            .align_items(egui_flex::FlexAlign::Start)
+            .justify(egui_flex::FlexJustify::Start)
+            .grow_items(0.)
+            .h_full()
+            .w_full()
+            .show(ui, |f_ui| {
+                f_ui.add_flex(
+                    egui_flex::item(),
+                    Flex::horizontal()
+                        .w_full()
+                        .justify(egui_flex::FlexJustify::SpaceBetween),
+                    |f_ui| {
+                        f_ui.add(
+                            egui_flex::item(),
+                            Label::new(RichText::new("Заметки").size(40.).strong()),
+                        );
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            context_menu_button(ui, |ui| {
+                                ui.spacing_mut().button_padding = [25., 15.].into();
+                                if ui
+                                    .button(RichText::new("Экспорт в Markdown").size(29.))
+                                    .clicked()
+                                {
+                                    export = true;
+                                }
+                            });
+                        });
+                    },
+                );
+
+                f_ui.add_ui(egui_flex::item(), |ui| {
+                    Label::new(RichText::new(highlights.borrow().title.as_str()).size(29.))
+                        .wrap()
+                        .ui(ui);
+
+                    let error = match self.export_result.as_ref() {
+                        Some(Ok(path)) => {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Заметки скопированы и сохранены в {}",
+                                    path.to_string_lossy()
+                                ))
+                                .size(22.)
+                                .weak(),
+                            );
+                            None
+                        }
+                        Some(Err(e)) => Some(format!("Не удалось экспортировать заметки: {}", e)),
+                        None => None,
+                    };
+                    let error = error.or_else(|| {
+                        let save_error = highlights.borrow().save_error.clone();
+                        save_error.map(|e| format!("Не удалось сохранить заметки: {}", e))
+                    });
+                    if let Some(error) = error {
+                        Label::new(
+                            RichText::new(error)
+                                .size(22.)
+                                .color(ui.visuals().error_fg_color),
+                        )
+                        .wrap()
+                        .ui(ui);
+                    }
+                });
+
+                f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
+
+                f_ui.add_ui(egui_flex::item().shrink(), |ui| {
+                    if highlights.borrow().highlights.is_empty() {
+                        ui.centered_and_justified(|ui| {
+                            ui.label(
+                                RichText::new(
+                                    "Нет заметок. Чтобы выделить текст, удерживайте абзац статьи",
+                                )
+                                .size(29.),
+                            );
+                        });
+                        return;
+                    }
+
+                    let mut action = None;
+                    ScrollArea::vertical()
+                        .max_width(ui.available_width())
+                        .hscroll(false)
+                        .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysHidden)
+                        .show(ui, |ui| {
+                            let items = highlights.borrow().highlights.clone();
+                            for highlight in items.iter() {
+                                if self
+                                    .editing
+                                    .as_ref()
+                                    .is_some_and(|(id, _)| *id == highlight.id)
+                                {
+                                    NoteItem::ui(ui, highlight);
+                                    self.editing_ui(ui);
+                                    continue;
+                                }
+
+                                NoteItem::ui(ui, highlight).context_menu(|ui| {
+                                    ui.spacing_mut().button_padding = [25., 15.].into();
+                                    if ui
+                                        .button(RichText::new("Изменить заметку").size(29.))
+                                        .clicked()
+                                    {
+                                        action = Some(NoteAction::Edit(highlight.id));
+                                    }
+                                    if ui.button(RichText::new("Удалить").size(29.)).clicked()
+                                    {
+                                        action = Some(NoteAction::Remove(highlight.id));
+                                    }
+                                });
+                            }
+                        });
+
+                    match action {
+                        Some(NoteAction::Edit(id)) => {
+                            let note = highlights
+                                .borrow()
+                                .highlights
+                                .iter()
+                                .find(|h| h.id == id)
+                                .map(|h| h.note.clone())
+                                .unwrap_or_default();
+                            self.editing = Some((id, note));
+                        }
+                        Some(NoteAction::Remove(id)) => {
+                            let mut highlights = highlights.borrow_mut();
+                            highlights.remove(id);
+                            highlights.save();
+                        }
+                        None => {}
+                    }
+                });
+            });
+
+        if export {
+            self.export_markdown(ui.ctx());
+        }
+    }
+}
+
+struct NoteItem;
+
+impl NoteItem {
+    fn ui(ui: &mut Ui, highlight: &Highlight) -> egui::Response {
+        let frame = Frame::NONE
+            .corner_radius(5.)
+            .fill(ui.ctx().theme().default_visuals().extreme_bg_color)
+            .inner_margin(10.);
+
+        ui.scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
+            ui.set_max_width(ui.available_width());
+            frame.show(ui, |ui| {
+                ui.with_layout(Layout::top_down_justified(egui::Align::TOP), |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.spacing_mut().item_spacing = egui::Vec2::new(0., 5.);
+
+                    Label::new(
+                        RichText::new(format!("«{}»", highlight.quote))
+                            .size(25.)
+                            .italics(),
+                    )
+                    .wrap()
+                    .selectable(false)
+                    .ui(ui);
+
+                    if !highlight.note.is_empty() {
+                        Label::new(RichText::new(highlight.note.as_str()).size(29.))
+                            .wrap()
+                            .selectable(false)
+                            .ui(ui);
+                    }
+
+                    let mut info = highlight.created_at_str();
+                    if highlight.detached {
+                        info.push_str(" · Фрагмент не найден в статье");
+                    }
+                    Label::new(RichText::new(info).size(22.).weak())
+                        .selectable(false)
+                        .ui(ui);
+                });
+            });
+        })
+        .response
+    }
+}
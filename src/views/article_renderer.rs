@@ -0,0 +1,366 @@
+#[cfg(not(feature = "aurora"))]
+use egui::OpenUrl;
+use egui::{
+    self, Color32, FontId, FontSelection, Image, Label, Layout, RichText, ScrollArea, Sense, Ui,
+    UiBuilder, Vec2, Widget, scroll_area::ScrollSource, text::LayoutJob,
+};
+
+use crate::habr_client::article::ArticleContent;
+use crate::habr_client::html_parse::TypedText;
+use crate::storage::highlights::{ArticleHighlights, split_highlighted};
+
+const HIGHLIGHT_COLOR: Color32 = Color32::from_rgba_premultiplied(120, 100, 0, 120);
+
+/// Renders parsed article content. Keeps the state of the code blocks scrolling
+/// and of the full screen image viewer between frames.
+pub struct ArticleRenderer {
+    selected_code_scroll_id: Option<usize>,
+    image_viewer: ImageViewer,
+    clicked_link: Option<String>,
+    highlight_block: Option<usize>,
+}
+
+impl ArticleRenderer {
+    pub fn new() -> Self {
+        Self {
+            selected_code_scroll_id: None,
+            image_viewer: ImageViewer::new(),
+            clicked_link: None,
+            highlight_block: None,
+        }
+    }
+
+    pub fn is_viewing_image(&self) -> bool {
+        self.image_viewer.image_url.is_some()
+    }
+
+    /// Link clicked in the content, the view decides where to open it.
+    pub fn take_clicked_link(&mut self) -> Option<String> {
+        self.clicked_link.take()
+    }
+
+    /// Text block the user wants to highlight a part of.
+    pub fn take_highlight_block(&mut self) -> Option<usize> {
+        self.highlight_block.take()
+    }
+
+    pub fn content_ui(
+        &mut self,
+        ui: &mut Ui,
+        content: &[ArticleContent],
+        highlights: &ArticleHighlights,
+    ) {
+        for (i, content) in content.iter().enumerate() {
+            if matches!(
+                content,
+                ArticleContent::Image(_) | ArticleContent::Code { .. } | ArticleContent::BR
+            ) {
+                self.block_ui(ui, i, content, &[]);
+                continue;
+            }
+
+            let ranges = highlights.block_ranges(i);
+            let response = ui
+                .scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
+                    self.block_ui(ui, i, content, &ranges)
+                })
+                .response;
+            response.context_menu(|ui| {
+                ui.spacing_mut().button_padding = [25., 15.].into();
+                if ui
+                    .button(RichText::new("Выделить фрагмент").size(29.))
+                    .clicked()
+                {
+                    self.highlight_block = Some(i);
+                }
+            });
+
+            for highlight in highlights.highlights.iter() {
+                if highlight.block == i && !highlight.detached && !highlight.note.is_empty() {
+                    Label::new(
+                        RichText::new(format!("Заметка: {}", highlight.note))
+                            .size(22.)
+                            .italics()
+                            .weak(),
+                    )
+                    .wrap()
+                    .selectable(false)
+                    .ui(ui);
+                }
+            }
+        }
+    }
+
+    /// `ranges` are the highlighted characters of the block plain text.
+    fn block_ui(
+        &mut self,
+        ui: &mut Ui,
+        i: usize,
+        content: &ArticleContent,
+        ranges: &[(usize, usize)],
+    ) {
+        match content {
+            ArticleContent::Header(h_lvl, content) => {
+                ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
+                    let text = highlighted_text(ui, content, 0, ranges, |text| {
+                        text.heading().strong().size(36. - *h_lvl as f32)
+                    });
+                    ui.add(Label::new(text).selectable(false).wrap());
+                });
+            }
+            ArticleContent::Code { lang, content } => {
+                ui.with_layout(Layout::left_to_right(egui::Align::Min), |ui| {
+                    let code_scroll = ScrollArea::horizontal().id_salt(i).scroll_source(
+                        self.selected_code_scroll_id
+                            .map_or(ScrollSource::NONE, |current_idx| {
+                                if current_idx == i {
+                                    ScrollSource::ALL
+                                } else {
+                                    ScrollSource::NONE
+                                }
+                            }),
+                    );
+                    if code_scroll
+                        .show(ui, |ui| code_view(ui, content, lang))
+                        .inner
+                        .clicked()
+                    {
+                        if self
+                            .selected_code_scroll_id
+                            .take_if(|current_idx| *current_idx == i)
+                            .is_none()
+                        {
+                            self.selected_code_scroll_id = Some(i);
+                        };
+                    };
+                });
+            }
+            ArticleContent::Blockquote(content) => {
+                ui.horizontal(|ui| {
+                    egui::Frame::new()
+                        .inner_margin(egui::Margin::symmetric(25, 15))
+                        .outer_margin(egui::Margin::symmetric(0, 10))
+                        .fill(Color32::GRAY)
+                        .corner_radius(10)
+                        .show(ui, |ui| {
+                            ui.vertical(|ui| {
+                                ui.spacing_mut().item_spacing = egui::Vec2::new(0.0, 5.0);
+                                egui::Image::from_bytes(
+                                    "bytes://double-quotes",
+                                    include_bytes!("../../assets/double-quotes.png"),
+                                )
+                                .fit_to_exact_size((15., 15.).into())
+                                .ui(ui);
+                                let text = highlighted_text(ui, content, 0, ranges, |text| {
+                                    text.size(25.).color(Color32::BLACK).italics()
+                                });
+                                egui::Label::new(text).wrap().selectable(false).ui(ui);
+                            });
+                        });
+                });
+            }
+            ArticleContent::Paragraph(conetnt_stream) => {
+                self.paragraph_ui(ui, conetnt_stream, 0, ranges);
+            }
+            ArticleContent::Image(src) => {
+                ui.with_layout(Layout::top_down_justified(egui::Align::Center), |ui| {
+                    let img = Image::new(src)
+                        .max_width(ui.available_width())
+                        .fit_to_original_size(1.)
+                        .sense(egui::Sense::click());
+
+                    if ui.add(img).clicked() {
+                        self.image_viewer.set_image_url(src.clone());
+                    }
+                });
+            }
+            ArticleContent::BR => ui.add_space(5.),
+            ArticleContent::Text(text) => {
+                match text {
+                    TypedText::Common(text) => {
+                        let text = highlighted_text(ui, text, 0, ranges, |text| text.size(29.));
+                        ui.add(Label::new(text).wrap().selectable(false));
+                    }
+                    _ => {}
+                };
+            }
+            ArticleContent::UnorderedList(list) | ArticleContent::OrderedList(list) => {
+                // Items are separated by new lines in the plain text of the list.
+                let mut offset = 0;
+                for item in list.iter() {
+                    if let ArticleContent::Paragraph(p_content) = item {
+                        self.paragraph_ui(ui, p_content, offset, ranges);
+                    }
+                    offset += item.plain_text().chars().count() + 1;
+                }
+            }
+        }
+    }
+
+    fn paragraph_ui(
+        &mut self,
+        ui: &mut Ui,
+        content: &[TypedText],
+        mut offset: usize,
+        ranges: &[(usize, usize)],
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for typed_text in content {
+                typed_text_ui(ui, typed_text, offset, ranges, &mut self.clicked_link);
+                offset += typed_text.text().chars().count();
+            }
+        });
+    }
+
+    /// Full screen viewer of the clicked image, should be called after the content.
+    pub fn image_viewer_ui(&mut self, ui: &mut Ui) {
+        if self.image_viewer.image_url.is_some() {
+            ui.put(ui.ctx().content_rect(), |ui: &mut egui::Ui| {
+                egui::Frame::NONE
+                    .fill(Color32::from_black_alpha(200))
+                    .outer_margin(0)
+                    .inner_margin(0)
+                    .show(ui, |ui| self.image_viewer.ui(ui))
+                    .response
+            });
+        }
+    }
+}
+
+struct ImageViewer {
+    image_url: Option<String>,
+    scene_rect: egui::Rect,
+}
+
+impl ImageViewer {
+    fn new() -> Self {
+        Self {
+            image_url: None,
+            scene_rect: egui::Rect::ZERO,
+        }
+    }
+
+    fn set_image_url(&mut self, image_url: String) {
+        self.image_url = Some(image_url);
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        if let Some(image_url) = self.image_url.as_ref() {
+            let image_url = image_url.clone();
+            ui.with_layout(Layout::top_down_justified(egui::Align::Center), |ui| {
+                let cross_rect = egui::Rect::from_center_size(
+                    (ui.available_width() - 25., ui.clip_rect().top() + 25.).into(),
+                    (25., 25.).into(),
+                );
+
+                if ui.allocate_rect(cross_rect, egui::Sense::CLICK).clicked() {
+                    self.image_url = None;
+                    self.scene_rect = egui::Rect::ZERO;
+                }
+
+                let painter = ui.painter_at(cross_rect);
+                painter.line_segment(
+                    [cross_rect.left_top(), cross_rect.right_bottom()],
+                    egui::Stroke::new(3.0, egui::Color32::LIGHT_GRAY),
+                );
+                painter.line_segment(
+                    [cross_rect.right_top(), cross_rect.left_bottom()],
+                    egui::Stroke::new(3.0, egui::Color32::LIGHT_GRAY),
+                );
+
+                let image = Image::new(image_url);
+
+                egui::Scene::new()
+                    .max_inner_size(image.size().unwrap_or(Vec2::from([1000.0, 1200.0])))
+                    .zoom_range(0.5..=3.0)
+                    .show(ui, &mut self.scene_rect, |ui| ui.add(image));
+            });
+        }
+    }
+}
+
+/// Opens the url in the system browser.
+pub fn open_url(ui: &egui::Ui, url: &str) {
+    #[cfg(feature = "aurora")]
+    aurora_services::open_uri::open_uri(url, |_| {
+        // Do something with the response
+    });
+    #[cfg(not(feature = "aurora"))]
+    ui.ctx().open_url(OpenUrl::new_tab(url));
+}
+
+/// The text with the highlighted parts, `offset` is the position of the text in its block.
+fn highlighted_text(
+    ui: &Ui,
+    text: &str,
+    offset: usize,
+    ranges: &[(usize, usize)],
+    style: impl Fn(RichText) -> RichText,
+) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    for (part, highlighted) in split_highlighted(text, offset, ranges) {
+        let mut part = style(RichText::new(part));
+        if highlighted {
+            part = part.background_color(HIGHLIGHT_COLOR);
+        }
+        part.append_to(
+            &mut job,
+            ui.style(),
+            FontSelection::Default,
+            egui::Align::Center,
+        );
+    }
+    job
+}
+
+fn typed_text_ui(
+    ui: &mut egui::Ui,
+    content: &TypedText,
+    offset: usize,
+    ranges: &[(usize, usize)],
+    clicked_link: &mut Option<String>,
+) {
+    match content {
+        TypedText::Code(text) => {
+            ui.label(highlighted_text(ui, text, offset, ranges, |text| {
+                text.code().size(29.)
+            }));
+        }
+        TypedText::Link { url, value } => {
+            let hyperlink_color = ui.ctx().theme().default_visuals().hyperlink_color;
+            let text = highlighted_text(ui, value, offset, ranges, |text| {
+                text.size(29.).color(hyperlink_color)
+            });
+            if ui.link(text).clicked() {
+                *clicked_link = Some(url.clone());
+            }
+        }
+        TypedText::Common(text) => {
+            let text = highlighted_text(ui, text, offset, ranges, |text| text.size(29.));
+            ui.add(Label::new(text).wrap().selectable(false));
+        }
+        TypedText::Italic(text) => {
+            let text = highlighted_text(ui, text, offset, ranges, |text| text.size(29.).italics());
+            ui.add(Label::new(text).wrap().selectable(false));
+        }
+        TypedText::Strong(text) => {
+            let text = highlighted_text(ui, text, offset, ranges, |text| text.size(29.).strong());
+            ui.add(Label::new(text).wrap().selectable(false));
+        }
+    }
+}
+
+fn code_view(ui: &mut Ui, code: &str, lang: &str) -> egui::Response {
+    ui.ctx().global_style_mut(|style| {
+        style.text_styles.insert(
+            egui::TextStyle::Monospace,
+            FontId {
+                size: 22.,
+                family: egui::FontFamily::Monospace,
+            },
+        );
+    });
+    let theme = egui_extras::syntax_highlighting::CodeTheme::from_memory(ui.ctx(), ui.style());
+    egui_extras::syntax_highlighting::code_view_ui(ui, &theme, code, lang)
+}
@@ -1,9 +1,9 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::{
     Arc, RwLock,
-    atomic::{AtomicBool, AtomicU8, Ordering},
+    atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
 };
 
 use egui::{
@@ -17,45 +17,66 @@ static SAVE_ICON: &[u8] = include_bytes!("../../assets/save.png");
 static TRASH_ICON: &[u8] = include_bytes!("../../assets/trash.png");
 
 use crate::views::article_details::ArticleDetails;
+use crate::views::author_profile::AuthorProfile;
 use crate::views::comments::Comments;
+use crate::views::company_details::CompanyDetails;
 use crate::{
     app::HabreState,
     habr_client::{
         HabrClient,
         article::{
             ArticleData, ArticlesListFilter, ArticlesListSorting, ArticlesSearchSorting,
-            ComplexityFilter, DateFilter,
+            ComplexityFilter, DateFilter, PublicationType, RATING_THRESHOLDS,
         },
+        search::SearchQuery,
     },
-    storage::ArticleStorage,
+    storage::{ArticleStorage, SaveProgress, feeds::Feed},
     view_stack::{UiView, ViewStack},
     widgets::Pager,
 };
 
+#[derive(Debug, Clone)]
+pub enum ArticlesSource {
+    Hub,
+    Feed(Feed),
+    /// Articles of all subscribed hubs.
+    Subscriptions,
+    Tag(String),
+}
+
 pub struct ArticlesList {
     pub is_loading: Arc<AtomicBool>,
 
     habre_state: Rc<RefCell<HabreState>>,
     reset_scroll: bool,
     articles: Arc<RwLock<Vec<ArticleData>>>,
+    load_error: Arc<RwLock<Option<String>>>,
+    /// Number of the latest request, replies to the older ones are dropped.
+    latest_request: Arc<AtomicUsize>,
     habr_client: HabrClient,
 
     sorting: ArticlesListSorting,
     rating_filter: Option<usize>,
     date_filter: DateFilter,
     complexity_filter: Option<ComplexityFilter>,
+    publication_type: PublicationType,
 
     search_text: String,
     search_was_changed: bool,
     search_sorting: ArticlesSearchSorting,
+    source: ArticlesSource,
+    mark_feed_seen: bool,
 
     current_page: u8,
     max_page: Arc<AtomicU8>,
-    saving_articles: Arc<RwLock<HashSet<String>>>,
+
+    show_hidden: bool,
+    revealed_articles: HashSet<String>,
 
     show_filter_popup: bool,
     temp_sorting: ArticlesListSorting,
     temp_date_filter: DateFilter,
+    temp_rating_filter: Option<usize>,
     temp_complexity_filter: Option<ComplexityFilter>,
     temp_search_sorting: ArticlesSearchSorting,
 }
@@ -67,43 +88,140 @@ impl ArticlesList {
             habr_client: HabrClient::new(),
 
             articles: Default::default(),
+            load_error: Default::default(),
+            latest_request: Default::default(),
 
             reset_scroll: false,
             is_loading: Arc::new(AtomicBool::new(true)),
             current_page: 1,
             max_page: Arc::new(AtomicU8::new(0)),
-            saving_articles: Arc::new(RwLock::new(HashSet::new())),
 
             sorting: ArticlesListSorting::default(),
             rating_filter: None,
             date_filter: DateFilter::Daily,
             complexity_filter: None,
+            publication_type: PublicationType::Article,
 
             search_text: String::new(),
             search_was_changed: false,
             search_sorting: ArticlesSearchSorting::Relevance,
+            source: ArticlesSource::Hub,
+            mark_feed_seen: false,
+
+            show_hidden: false,
+            revealed_articles: HashSet::new(),
 
             show_filter_popup: false,
             temp_sorting: ArticlesListSorting::default(),
             temp_date_filter: DateFilter::Daily,
+            temp_rating_filter: None,
             temp_complexity_filter: None,
             temp_search_sorting: ArticlesSearchSorting::Relevance,
         }
     }
 
-    fn save_article(&self, article: ArticleData) {
-        let saving = self.saving_articles.clone();
-        saving.write().unwrap().insert(article.id.clone());
+    pub fn open_hub(&mut self) {
+        let previous_source = std::mem::replace(&mut self.source, ArticlesSource::Hub);
+        if matches!(previous_source, ArticlesSource::Feed(_)) {
+            self.search_text.clear();
+            self.search_sorting = ArticlesSearchSorting::Relevance;
+            self.rating_filter = None;
+            self.complexity_filter = None;
+        }
+        self.current_page = 1;
+        self.get_articles();
+    }
 
-        let client = self.habr_client.clone();
-        self.habre_state.borrow().async_handle().spawn(async move {
-            if let Ok((_title, content)) = client.get_article_details(&article.id).await {
-                if let Err(e) = ArticleStorage::save_article(&article, &content).await {
-                    log::warn!("Failed to save article: {}", e);
-                }
-            }
-            saving.write().unwrap().remove(&article.id);
-        });
+    pub fn open_feed(&mut self, feed: &Feed) {
+        self.habre_state.borrow_mut().selected_hub = None;
+        self.search_text = feed.query.text.clone();
+        self.search_sorting = feed.query.sorting;
+        self.rating_filter = feed.query.min_score;
+        self.complexity_filter = feed.query.complexity;
+        self.source = ArticlesSource::Feed(feed.clone());
+        self.mark_feed_seen = true;
+        self.current_page = 1;
+        self.get_articles();
+    }
+
+    pub fn open_my_feed(&mut self) {
+        self.habre_state.borrow_mut().selected_hub = None;
+        self.search_text.clear();
+        self.source = ArticlesSource::Subscriptions;
+        self.current_page = 1;
+        self.get_articles();
+    }
+
+    pub fn open_tag(&mut self, tag: &str) {
+        self.search_text.clear();
+        self.source = ArticlesSource::Tag(tag.to_string());
+        self.current_page = 1;
+        self.get_articles();
+    }
+
+    /// Opens the feed of the tag on top of the current view.
+    pub fn push_tag_feed(state: Rc<RefCell<HabreState>>, tag: &str, view_stack: &mut ViewStack) {
+        let mut tag_feed = ArticlesList::new(state);
+        tag_feed.open_tag(tag);
+        view_stack.push(Rc::new(RefCell::new(tag_feed)));
+    }
+
+    /// Query of the current search. Filters of the opened feed the list doesn't show,
+    /// like hub or dates, are kept while the search text is the same.
+    fn search_query(&self) -> SearchQuery {
+        let query = match &self.source {
+            ArticlesSource::Feed(feed) if feed.query.text == self.search_text => feed.query.clone(),
+            _ => SearchQuery::new(self.search_text.trim()),
+        };
+        query
+            .sorting(self.search_sorting)
+            .complexity(self.complexity_filter)
+            .min_score(self.rating_filter)
+    }
+
+    fn toggle_pinned_search(&mut self) {
+        let query = self.search_query();
+        let state = self.habre_state.borrow();
+        let mut feeds = state.feeds.borrow_mut();
+        if let Some(feed_id) = feeds.find_by_query(&query).map(|f| f.id.clone()) {
+            feeds.remove(&feed_id);
+        } else {
+            let articles_ids = self
+                .articles
+                .read()
+                .unwrap()
+                .iter()
+                .map(|a| a.id.clone())
+                .collect();
+            let feed_id = feeds.add(self.search_text.trim().to_string(), query);
+            feeds.mark_seen(&feed_id, articles_ids);
+        }
+        feeds.save();
+    }
+
+    fn update_feed_seen(&mut self) {
+        if !self.mark_feed_seen || self.is_loading.load(Ordering::Relaxed) {
+            return;
+        }
+        self.mark_feed_seen = false;
+        let ArticlesSource::Feed(feed) = &self.source else {
+            return;
+        };
+        if self.load_error.read().unwrap().is_some() {
+            return;
+        }
+        let articles_ids = self
+            .articles
+            .read()
+            .unwrap()
+            .iter()
+            .map(|a| a.id.clone())
+            .collect();
+        let state = self.habre_state.borrow();
+        let mut feeds = state.feeds.borrow_mut();
+        if feeds.mark_seen(&feed.id, articles_ids) {
+            feeds.save();
+        }
     }
 
     pub fn get_articles(&mut self) {
@@ -118,6 +236,9 @@ impl ArticlesList {
             .as_ref()
             .map_or(String::new(), |hub| hub.alias.to_string());
         let articles = self.articles.clone();
+        let load_error = self.load_error.clone();
+        let latest_request = self.latest_request.clone();
+        let request = latest_request.fetch_add(1, Ordering::Relaxed) + 1;
         let max_page = self.max_page.clone();
         let is_loading = self.is_loading.clone();
         let current_page = self.current_page;
@@ -128,25 +249,62 @@ impl ArticlesList {
             ArticlesListSorting::Newest => ArticlesListFilter::ByRating(self.rating_filter),
         };
 
-        let search_sorting = self.search_sorting;
+        let complexity = self.complexity_filter;
+        let publication_type = self.publication_type;
         let search_text = self.search_text.clone();
+        let query = self.search_query();
+        let subscribed_hubs = match self.source {
+            ArticlesSource::Subscriptions => {
+                Some(self.habre_state.borrow().subscriptions.borrow().aliases())
+            }
+            _ => None,
+        };
+        let tag = match &self.source {
+            ArticlesSource::Tag(tag) => Some(tag.clone()),
+            _ => None,
+        };
 
         self.habre_state.borrow().async_handle().spawn(async move {
-            let (new_articles, new_max_page) = if search_text.is_empty() {
+            let result = if !search_text.is_empty() {
+                client.search_articles(&query, current_page).await
+            } else if let Some(tag) = tag {
+                client
+                    .get_tag_articles(&tag, sorting, filter, complexity, current_page)
+                    .await
+            } else if let Some(hubs) = subscribed_hubs {
                 client
-                    .get_articles(hub_id, sorting, filter, current_page)
+                    .get_hubs_articles(
+                        hubs,
+                        publication_type,
+                        sorting,
+                        filter,
+                        complexity,
+                        current_page,
+                    )
                     .await
-                    .unwrap()
             } else {
                 client
-                    .search_articles(&search_text, search_sorting, current_page)
+                    .get_articles(
+                        hub_id,
+                        publication_type,
+                        sorting,
+                        filter,
+                        complexity,
+                        current_page,
+                    )
                     .await
-                    .unwrap()
             };
 
-            max_page.store(new_max_page as u8, Ordering::Relaxed);
-            if let Ok(mut current_articles) = articles.write() {
-                *current_articles = new_articles;
+            if latest_request.load(Ordering::Relaxed) != request {
+                return;
+            }
+            match result {
+                Ok((new_articles, new_max_page)) => {
+                    max_page.store(new_max_page as u8, Ordering::Relaxed);
+                    *articles.write().unwrap() = new_articles;
+                    *load_error.write().unwrap() = None;
+                }
+                Err(e) => *load_error.write().unwrap() = Some(e.to_string()),
             }
             is_loading.store(false, Ordering::Relaxed);
         });
@@ -203,6 +361,36 @@ impl ArticlesList {
                     self.search_ui(ui);
                 });
 
+                if !self.search_text.trim().is_empty() {
+                    flex_ui.add_ui(egui_flex::item().grow(2.), |ui| {
+                        let is_pinned = self
+                            .habre_state
+                            .borrow()
+                            .feeds
+                            .borrow()
+                            .find_by_query(&self.search_query())
+                            .is_some();
+                        let button =
+                            egui::Button::new(RichText::new("📌").size(32.)).corner_radius(8.);
+                        let button = if is_pinned {
+                            button.fill(ui.visuals().selection.bg_fill)
+                        } else {
+                            button
+                        };
+                        if ui
+                            .add(button)
+                            .on_hover_text(if is_pinned {
+                                "Открепить ленту"
+                            } else {
+                                "Закрепить поиск как ленту"
+                            })
+                            .clicked()
+                        {
+                            self.toggle_pinned_search();
+                        }
+                    });
+                }
+
                 flex_ui.add_ui(egui_flex::item().grow(2.), |ui| {
                     let has_active_filters = self.has_active_filters();
                     let button_text = RichText::new("🔧").size(32.);
@@ -217,6 +405,7 @@ impl ArticlesList {
                     if ui.add(button).clicked() {
                         self.temp_sorting = self.sorting;
                         self.temp_date_filter = self.date_filter;
+                        self.temp_rating_filter = self.rating_filter;
                         self.temp_complexity_filter = self.complexity_filter;
                         self.temp_search_sorting = self.search_sorting;
                         self.show_filter_popup = true;
@@ -225,17 +414,104 @@ impl ArticlesList {
             });
     }
 
+    fn publication_type_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            for publication_type in PublicationType::ALL {
+                let selected = ui.selectable_value(
+                    &mut self.publication_type,
+                    publication_type,
+                    RichText::new(publication_type.title()).size(29.),
+                );
+                if selected.clicked() {
+                    self.current_page = 1;
+                    self.get_articles();
+                }
+            }
+        });
+    }
+
+    fn hidden_articles_ui(&mut self, ui: &mut Ui, hidden_count: usize) {
+        if hidden_count == 0 {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!("Скрыто статей: {}", hidden_count))
+                    .size(22.)
+                    .weak(),
+            );
+            let button_text = if self.show_hidden {
+                "Скрыть"
+            } else {
+                "Показать"
+            };
+            if ui.button(RichText::new(button_text).size(22.)).clicked() {
+                self.show_hidden = !self.show_hidden;
+            }
+        });
+    }
+
     fn has_active_filters(&self) -> bool {
+        if self.complexity_filter.is_some() {
+            return true;
+        }
         if !self.search_text.is_empty() {
-            self.search_sorting != ArticlesSearchSorting::Relevance
+            self.search_sorting != ArticlesSearchSorting::Relevance || self.rating_filter.is_some()
         } else {
             self.sorting != ArticlesListSorting::default()
-                || self.complexity_filter.is_some()
-                || (self.sorting == ArticlesListSorting::Best
-                    && self.date_filter != DateFilter::Daily)
+                || match self.sorting {
+                    ArticlesListSorting::Best => self.date_filter != DateFilter::Daily,
+                    ArticlesListSorting::Newest => self.rating_filter.is_some(),
+                }
         }
     }
 
+    fn rating_filter_ui(&mut self, ui: &mut Ui) {
+        ui.add_space(10.);
+        ui.label(RichText::new("Порог рейтинга").size(29.).strong());
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(
+                &mut self.temp_rating_filter,
+                None,
+                RichText::new("Все").size(25.),
+            );
+            for threshold in RATING_THRESHOLDS {
+                ui.selectable_value(
+                    &mut self.temp_rating_filter,
+                    Some(threshold),
+                    RichText::new(format!("≥{}", threshold)).size(25.),
+                );
+            }
+        });
+    }
+
+    fn complexity_filter_ui(&mut self, ui: &mut Ui) {
+        ui.add_space(10.);
+        ui.label(RichText::new("Уровень сложности").size(29.).strong());
+        ui.horizontal(|ui| {
+            ui.selectable_value(
+                &mut self.temp_complexity_filter,
+                None,
+                RichText::new("Все").size(25.),
+            );
+            ui.selectable_value(
+                &mut self.temp_complexity_filter,
+                Some(ComplexityFilter::Easy),
+                RichText::new("Простой").size(25.),
+            );
+            ui.selectable_value(
+                &mut self.temp_complexity_filter,
+                Some(ComplexityFilter::Medium),
+                RichText::new("Средний").size(25.),
+            );
+            ui.selectable_value(
+                &mut self.temp_complexity_filter,
+                Some(ComplexityFilter::Hard),
+                RichText::new("Сложный").size(25.),
+            );
+        });
+    }
+
     fn filter_popup_ui(&mut self, ui: &mut Ui) {
         if !self.show_filter_popup {
             return;
@@ -302,6 +578,9 @@ impl ArticlesList {
                             RichText::new("По рейтингу").size(25.),
                         );
                     });
+
+                    self.rating_filter_ui(ui);
+                    self.complexity_filter_ui(ui);
                 } else {
                     ui.label(RichText::new("Сначала покавать").size(29.).strong());
                     ui.horizontal(|ui| {
@@ -349,33 +628,10 @@ impl ArticlesList {
                                 );
                             });
                         }
-                        _ => {}
+                        ArticlesListSorting::Newest => self.rating_filter_ui(ui),
                     }
 
-                    ui.add_space(10.);
-                    ui.label(RichText::new("Уровень сложности").size(29.).strong());
-                    ui.horizontal(|ui| {
-                        ui.selectable_value(
-                            &mut self.temp_complexity_filter,
-                            None,
-                            RichText::new("Все").size(25.),
-                        );
-                        ui.selectable_value(
-                            &mut self.temp_complexity_filter,
-                            Some(ComplexityFilter::Easy),
-                            RichText::new("Простой").size(25.),
-                        );
-                        ui.selectable_value(
-                            &mut self.temp_complexity_filter,
-                            Some(ComplexityFilter::Medium),
-                            RichText::new("Средний").size(25.),
-                        );
-                        ui.selectable_value(
-                            &mut self.temp_complexity_filter,
-                            Some(ComplexityFilter::Hard),
-                            RichText::new("Сложный").size(25.),
-                        );
-                    });
+                    self.complexity_filter_ui(ui);
                 }
 
                 ui.add_space(20.);
@@ -409,8 +665,9 @@ impl ArticlesList {
             } else {
                 self.sorting = self.temp_sorting;
                 self.date_filter = self.temp_date_filter;
-                self.complexity_filter = self.temp_complexity_filter;
             }
+            self.rating_filter = self.temp_rating_filter;
+            self.complexity_filter = self.temp_complexity_filter;
             self.current_page = 1;
             self.get_articles();
         }
@@ -422,11 +679,13 @@ impl ArticlesList {
             } else {
                 self.sorting = ArticlesListSorting::default();
                 self.date_filter = DateFilter::Daily;
-                self.complexity_filter = None;
                 self.temp_sorting = ArticlesListSorting::default();
                 self.temp_date_filter = DateFilter::Daily;
-                self.temp_complexity_filter = None;
             }
+            self.rating_filter = None;
+            self.complexity_filter = None;
+            self.temp_rating_filter = None;
+            self.temp_complexity_filter = None;
             self.current_page = 1;
             self.get_articles();
         }
@@ -440,6 +699,7 @@ impl ArticlesList {
 impl UiView for ArticlesList {
     fn ui(&mut self, ui: &mut Ui, view_stack: &mut crate::view_stack::ViewStack) {
         self.filter_popup_ui(ui);
+        self.update_feed_seen();
 
         Flex::vertical()
             .justify(egui_flex::FlexJustify::SpaceBetween)
@@ -452,13 +712,19 @@ impl UiView for ArticlesList {
                     egui_flex::Flex::vertical().align_items(egui_flex::FlexAlign::Start),
                     |f_ui| {
                         f_ui.add_ui(egui_flex::item(), |ui| {
-                            let article_list_title = RichText::new(
-                                self.habre_state
+                            let article_list_title = RichText::new(match &self.source {
+                                ArticlesSource::Feed(feed) => feed.name.clone(),
+                                ArticlesSource::Subscriptions => "Моя лента".to_string(),
+                                ArticlesSource::Tag(tag) => format!("#{}", tag),
+                                ArticlesSource::Hub => self
+                                    .habre_state
                                     .borrow()
                                     .selected_hub
                                     .as_ref()
-                                    .map_or("Все статьи", |hub| &hub.title),
-                            )
+                                    .map_or("Все статьи".to_string(), |hub| {
+                                        hub.title.clone()
+                                    }),
+                            })
                             .size(40.)
                             .strong();
                             ui.add(Label::new(article_list_title))
@@ -468,6 +734,13 @@ impl UiView for ArticlesList {
                             self.search_with_filter_button_ui(ui)
                         });
 
+                        // Tag feeds come from the search, which doesn't split publication types.
+                        if self.search_text.is_empty()
+                            && !matches!(self.source, ArticlesSource::Tag(_))
+                        {
+                            f_ui.add_ui(egui_flex::item(), |ui| self.publication_type_ui(ui));
+                        }
+
                         f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
                     },
                 );
@@ -488,36 +761,64 @@ impl UiView for ArticlesList {
                         }
 
                         scroll_area.show(ui, |ui| {
-                            for article in self.articles.read().unwrap().iter() {
+                            if let Some(e) = self.load_error.read().unwrap().as_ref() {
+                                ui.label(
+                                    RichText::new(format!("Не удалось загрузить статьи: {}", e))
+                                        .size(25.)
+                                        .color(ui.visuals().error_fg_color),
+                                );
+                                return;
+                            }
+
+                            let articles = self.articles.clone();
+                            let articles = articles.read().unwrap();
+                            let (hidden_reasons, collapse) = {
+                                let settings = self.habre_state.borrow().settings.clone();
+                                let settings = settings.borrow();
+                                let filter = settings.content_filter();
+                                let reasons: Vec<_> = articles
+                                    .iter()
+                                    .map(|a| filter.matched_rule(a).map(|r| r.description()))
+                                    .collect();
+                                (reasons, filter.collapse)
+                            };
+                            self.hidden_articles_ui(ui, hidden_reasons.iter().flatten().count());
+
+                            for (article, hidden_reason) in articles.iter().zip(hidden_reasons) {
+                                if let Some(reason) = hidden_reason
+                                    && !self.show_hidden
+                                    && !self.revealed_articles.contains(&article.id)
+                                {
+                                    if collapse {
+                                        let collapsed = Label::new(
+                                            RichText::new(format!("Скрыто · {}", reason))
+                                                .size(22.)
+                                                .weak(),
+                                        )
+                                        .selectable(false)
+                                        .sense(Sense::click())
+                                        .ui(ui);
+                                        if collapsed.clicked() {
+                                            self.revealed_articles.insert(article.id.clone());
+                                        }
+                                    }
+                                    continue;
+                                }
+
                                 ui.with_layout(
                                     Layout::top_down_justified(egui::Align::TOP),
                                     |ui| {
-                                        let is_saved =
-                                            ArticleStorage::is_article_saved(&article.id);
-                                        let is_saving = self
-                                            .saving_articles
-                                            .read()
-                                            .unwrap()
-                                            .contains(&article.id);
-                                        let (response, save_clicked) = ArticleListItem::ui(
+                                        let article_saver =
+                                            self.habre_state.borrow().article_saver.clone();
+                                        let (response, action) = ArticleListItem::ui(
                                             ui,
                                             self.habre_state.clone(),
                                             article,
                                             view_stack,
-                                            is_saved,
-                                            is_saving,
+                                            &article_saver.status(&article.id),
                                         );
-
-                                        if save_clicked {
-                                            if is_saved {
-                                                if let Err(e) =
-                                                    ArticleStorage::delete_article(&article.id)
-                                                {
-                                                    log::warn!("Failed to delete article: {}", e);
-                                                }
-                                            } else {
-                                                self.save_article(article.clone());
-                                            }
+                                        if let Some(action) = action {
+                                            article_saver.handle_action(action, article);
                                         }
 
                                         if response.clicked() {
@@ -611,27 +912,231 @@ impl UiView for ArticlesList {
     }
 }
 
+/// Saves articles in background. Shared between all views with articles lists,
+/// so the saving progress is the same in every one of them.
+#[derive(Clone)]
+pub struct ArticleSaver {
+    async_handle: tokio::runtime::Handle,
+    habr_client: HabrClient,
+    saving_articles: Arc<RwLock<HashMap<String, Arc<SaveProgress>>>>,
+    save_errors: Arc<RwLock<HashMap<String, String>>>,
+    failed_images: Arc<RwLock<HashMap<String, Vec<String>>>>,
+}
+
+impl ArticleSaver {
+    pub fn new(async_handle: tokio::runtime::Handle) -> Self {
+        Self {
+            async_handle,
+            habr_client: HabrClient::new(),
+            saving_articles: Default::default(),
+            save_errors: Default::default(),
+            failed_images: Default::default(),
+        }
+    }
+
+    pub fn save_article(&self, article: ArticleData) {
+        let progress = Arc::new(SaveProgress::default());
+        let saving = self.saving_articles.clone();
+        saving
+            .write()
+            .unwrap()
+            .insert(article.id.clone(), progress.clone());
+        let save_errors = self.save_errors.clone();
+        save_errors.write().unwrap().remove(&article.id);
+        let failed_images = self.failed_images.clone();
+
+        let client = self.habr_client.clone();
+        self.async_handle.spawn(async move {
+            let result = match client.get_article_details(&article.id).await {
+                Ok((_title, content)) => {
+                    ArticleStorage::save_article(&article, &content, &progress).await
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            Self::finish_saving(&article.id, result, &progress, &save_errors, &failed_images);
+            saving.write().unwrap().remove(&article.id);
+        });
+    }
+
+    pub fn retry_failed_images(&self, article_id: String) {
+        let progress = Arc::new(SaveProgress::default());
+        let saving = self.saving_articles.clone();
+        saving
+            .write()
+            .unwrap()
+            .insert(article_id.clone(), progress.clone());
+        let save_errors = self.save_errors.clone();
+        save_errors.write().unwrap().remove(&article_id);
+        let failed_images = self.failed_images.clone();
+
+        self.async_handle.spawn(async move {
+            let result = ArticleStorage::retry_failed_images(&article_id, &progress).await;
+            Self::finish_saving(&article_id, result, &progress, &save_errors, &failed_images);
+            saving.write().unwrap().remove(&article_id);
+        });
+    }
+
+    fn finish_saving(
+        article_id: &str,
+        result: Result<(), String>,
+        progress: &SaveProgress,
+        save_errors: &RwLock<HashMap<String, String>>,
+        failed_images: &RwLock<HashMap<String, Vec<String>>>,
+    ) {
+        let failed = progress.failed_images();
+        let mut failed_images = failed_images.write().unwrap();
+        match result {
+            Ok(()) if !failed.is_empty() => {
+                failed_images.insert(article_id.to_string(), failed);
+            }
+            Ok(()) => {
+                failed_images.remove(article_id);
+            }
+            Err(e) => {
+                if !progress.is_cancelled() {
+                    log::warn!("Failed to save article: {}", e);
+                    save_errors
+                        .write()
+                        .unwrap()
+                        .insert(article_id.to_string(), e);
+                }
+            }
+        }
+    }
+
+    pub fn cancel_saving(&self, article_id: &str) {
+        if let Some(progress) = self.saving_articles.read().unwrap().get(article_id) {
+            progress.cancel();
+        }
+    }
+
+    pub fn is_saving(&self, article_id: &str) -> bool {
+        self.saving_articles
+            .read()
+            .unwrap()
+            .contains_key(article_id)
+    }
+
+    pub fn status(&self, article_id: &str) -> ArticleSaveStatus {
+        ArticleSaveStatus {
+            is_saved: ArticleStorage::is_article_saved(article_id),
+            progress: self
+                .saving_articles
+                .read()
+                .unwrap()
+                .get(article_id)
+                .cloned(),
+            error: self.save_errors.read().unwrap().get(article_id).cloned(),
+            failed_images: self
+                .failed_images
+                .read()
+                .unwrap()
+                .get(article_id)
+                .map_or(0, |failed| failed.len()),
+        }
+    }
+
+    pub fn delete_article(&self, article_id: &str) {
+        self.failed_images.write().unwrap().remove(article_id);
+        if let Err(e) = ArticleStorage::delete_article(article_id) {
+            log::warn!("Failed to delete article: {}", e);
+            self.save_errors
+                .write()
+                .unwrap()
+                .insert(article_id.to_string(), e);
+        }
+    }
+
+    pub fn handle_action(&self, action: ArticleItemAction, article: &ArticleData) {
+        match action {
+            ArticleItemAction::Save => self.save_article(article.clone()),
+            ArticleItemAction::Delete => self.delete_article(&article.id),
+            ArticleItemAction::CancelSaving => self.cancel_saving(&article.id),
+            ArticleItemAction::RetryFailedImages => self.retry_failed_images(article.id.clone()),
+        }
+    }
+}
+
+pub struct ArticleSaveStatus {
+    pub is_saved: bool,
+    pub progress: Option<Arc<SaveProgress>>,
+    pub error: Option<String>,
+    pub failed_images: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArticleItemAction {
+    Save,
+    Delete,
+    CancelSaving,
+    RetryFailedImages,
+}
+
 pub struct ArticleListItem;
 
 impl ArticleListItem {
+    /// Cards of the articles with saving handled and details opened on click.
+    pub fn list_ui(
+        ui: &mut Ui,
+        state: Rc<RefCell<HabreState>>,
+        articles: &[ArticleData],
+        view_stack: &mut ViewStack,
+    ) {
+        let article_saver = state.borrow().article_saver.clone();
+        let mut selected_article = None;
+        for article in articles {
+            ui.with_layout(Layout::top_down_justified(egui::Align::TOP), |ui| {
+                let (response, action) = ArticleListItem::ui(
+                    ui,
+                    state.clone(),
+                    article,
+                    view_stack,
+                    &article_saver.status(&article.id),
+                );
+                if let Some(action) = action {
+                    article_saver.handle_action(action, article);
+                }
+                if response.clicked() {
+                    selected_article = Some(article.clone());
+                }
+            });
+        }
+
+        if let Some(article) = selected_article {
+            state.borrow_mut().selected_article = Some(article);
+            let mut article_detailed_view = ArticleDetails::new(state.clone());
+            article_detailed_view.load_data();
+            view_stack.push(Rc::new(RefCell::new(article_detailed_view)));
+        }
+    }
+
     pub fn ui(
         ui: &mut Ui,
         state: Rc<RefCell<HabreState>>,
         article: &ArticleData,
         view_stack: &mut ViewStack,
-        is_saved: bool,
-        is_saving: bool,
-    ) -> (Response, bool) {
+        save_status: &ArticleSaveStatus,
+    ) -> (Response, Option<ArticleItemAction>) {
         let frame = Frame::NONE
             .corner_radius(5.)
             .fill(ui.ctx().theme().default_visuals().extreme_bg_color)
             .inner_margin(10.);
 
-        let save_clicked = std::cell::Cell::new(false);
+        let action = std::cell::Cell::new(None);
+        let settings = state.borrow().settings.clone();
+        let read_status = state
+            .borrow()
+            .history
+            .borrow()
+            .get(&article.id)
+            .map(|e| (e.finished, e.progress));
 
         let response = ui
             .scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
                 ui.set_max_width(ui.available_width());
+                if matches!(read_status, Some((true, _))) {
+                    ui.multiply_opacity(0.6);
+                }
                 frame.show(ui, |ui| {
                     ui.with_layout(Layout::top_down_justified(egui::Align::TOP), |ui| {
                         ui.set_width(ui.available_width());
@@ -643,18 +1148,47 @@ impl ArticleListItem {
                         ui.horizontal(|ui| {
                             ui.vertical(|ui| {
                                 ui.spacing_mut().item_spacing = egui::Vec2::new(0., 5.);
-                                Label::new(author_txt).selectable(false).ui(ui);
+                                let author_label = Label::new(author_txt)
+                                    .selectable(false)
+                                    .sense(Sense::click())
+                                    .ui(ui);
+                                if author_label.clicked() {
+                                    let mut author_profile = AuthorProfile::new(state.clone());
+                                    author_profile.load(&article.author);
+                                    view_stack.push(Rc::new(RefCell::new(author_profile)));
+                                }
 
                                 Label::new(RichText::new(article.published_at.as_str()).size(22.))
                                     .selectable(false)
                                     .ui(ui);
+
+                                if let Some(company) = article.company.as_ref() {
+                                    let company_badge = Label::new(
+                                        RichText::new(format!("🏢 {}", company))
+                                            .size(22.)
+                                            .color(ui.visuals().hyperlink_color),
+                                    )
+                                    .selectable(false)
+                                    .sense(Sense::click())
+                                    .ui(ui);
+                                    if company_badge.clicked() {
+                                        let mut company_details =
+                                            CompanyDetails::new(state.clone());
+                                        company_details.load(company);
+                                        view_stack.push(Rc::new(RefCell::new(company_details)));
+                                    }
+                                }
                             });
 
                             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                if is_saving {
-                                    ui.label(RichText::new("...").size(42.));
+                                if save_status.progress.is_some() {
+                                    let cancel_btn =
+                                        Button::new(RichText::new("✖").size(32.)).frame(false);
+                                    if ui.add(cancel_btn).clicked() {
+                                        action.set(Some(ArticleItemAction::CancelSaving));
+                                    }
                                 } else {
-                                    let icon = if is_saved {
+                                    let icon = if save_status.is_saved {
                                         Image::from_bytes("bytes://check", TRASH_ICON)
                                             .fit_to_exact_size([42., 42.].into())
                                     } else {
@@ -663,12 +1197,74 @@ impl ArticleListItem {
                                     };
                                     let btn = Button::image(icon).frame(false);
                                     if ui.add(btn).clicked() {
-                                        save_clicked.set(true);
+                                        action.set(Some(if save_status.is_saved {
+                                            ArticleItemAction::Delete
+                                        } else {
+                                            ArticleItemAction::Save
+                                        }));
+                                        // The ones who use collections choose them right away.
+                                        let collections = state.borrow().collections.clone();
+                                        let collections = collections.borrow();
+                                        if !save_status.is_saved
+                                            && (!collections.collections.is_empty()
+                                                || !collections.articles.is_empty())
+                                        {
+                                            state
+                                                .borrow()
+                                                .collections_popup
+                                                .borrow_mut()
+                                                .open(&article.id, &article.title);
+                                        }
                                     }
                                 }
                             });
                         });
 
+                        if let Some(progress) = save_status.progress.as_ref() {
+                            ui.add(
+                                egui::ProgressBar::new(progress.fraction())
+                                    .desired_height(30.)
+                                    .text(
+                                        RichText::new(format!(
+                                            "Изображения {}/{} · {:.1} МБ",
+                                            progress.images_done(),
+                                            progress.images_total(),
+                                            progress.bytes() as f64 / (1024. * 1024.)
+                                        ))
+                                        .size(22.),
+                                    ),
+                            );
+                            ui.ctx().request_repaint();
+                        } else if save_status.is_saved && save_status.failed_images > 0 {
+                            ui.horizontal_wrapped(|ui| {
+                                Label::new(
+                                    RichText::new(format!(
+                                        "Не загружено изображений: {}",
+                                        save_status.failed_images
+                                    ))
+                                    .size(22.)
+                                    .color(ui.visuals().warn_fg_color),
+                                )
+                                .selectable(false)
+                                .ui(ui);
+                                if ui.button(RichText::new("Повторить").size(22.)).clicked()
+                                {
+                                    action.set(Some(ArticleItemAction::RetryFailedImages));
+                                }
+                            });
+                        }
+
+                        if let Some(error) = save_status.error.as_deref() {
+                            Label::new(
+                                RichText::new(format!("Не удалось сохранить: {}", error))
+                                    .size(22.)
+                                    .color(ui.visuals().error_fg_color),
+                            )
+                            .wrap()
+                            .selectable(false)
+                            .ui(ui);
+                        }
+
                         if !article.image_url.is_empty() {
                             ui.with_layout(Layout::top_down_justified(egui::Align::Center), |ui| {
                                 Image::new(article.image_url.as_str())
@@ -698,13 +1294,31 @@ impl ArticleListItem {
                                 if i > 0 {
                                     ui.label("-");
                                 }
-                                Label::new(RichText::new(tag).size(22.))
-                                    .selectable(false)
-                                    .ui(ui);
+                                let tag_label = Label::new(
+                                    RichText::new(tag)
+                                        .size(22.)
+                                        .color(ui.visuals().hyperlink_color),
+                                )
+                                .selectable(false)
+                                .sense(Sense::click())
+                                .ui(ui);
+                                if tag_label.clicked() {
+                                    ArticlesList::push_tag_feed(state.clone(), tag, view_stack);
+                                }
                             }
                         });
 
                         ui.spacing_mut().item_spacing = egui::Vec2::new(10., 5.);
+                        if let Some(badge) = article.publication_type.badge() {
+                            Label::new(
+                                RichText::new(badge)
+                                    .size(25.)
+                                    .strong()
+                                    .color(ui.visuals().hyperlink_color),
+                            )
+                            .selectable(false)
+                            .ui(ui);
+                        }
                         Grid::new(&article.id).num_columns(2).show(ui, |ui| {
                             if let Some((label, color)) = match article.complexity.as_str() {
                                 "low" => Some(("😴 Простой", Color32::GREEN)),
@@ -752,11 +1366,58 @@ impl ArticleListItem {
                             } else {
                                 ui.label(comments_count_str);
                             }
+
+                            let read_marker = match read_status {
+                                Some((true, _)) => Some("✓ Прочитано".to_string()),
+                                Some((false, progress)) if progress > 0. => {
+                                    Some(format!("📖 {}%", (progress * 100.).round()))
+                                }
+                                _ => None,
+                            };
+                            if let Some(read_marker) = read_marker {
+                                ui.add_space(15.);
+                                Label::new(RichText::new(read_marker).size(29.).weak())
+                                    .selectable(false)
+                                    .ui(ui);
+                            }
                         })
                     })
                 });
             })
             .response;
-        (response, save_clicked.get())
+
+        response.context_menu(|ui| {
+            ui.spacing_mut().button_padding = [25., 15.].into();
+            if !article.author.is_empty()
+                && ui
+                    .button(RichText::new("Скрыть статьи автора").size(29.))
+                    .clicked()
+            {
+                settings.borrow_mut().hide_author(&article.author);
+                ui.close();
+            }
+            if let Some(company) = article.company.as_ref()
+                && ui
+                    .button(RichText::new("Скрыть блог компании").size(29.))
+                    .clicked()
+            {
+                settings.borrow_mut().hide_company(company);
+                ui.close();
+            }
+            if (save_status.is_saved || save_status.progress.is_some())
+                && ui
+                    .button(RichText::new("Коллекции и теги").size(29.))
+                    .clicked()
+            {
+                state
+                    .borrow()
+                    .collections_popup
+                    .borrow_mut()
+                    .open(&article.id, &article.title);
+                ui.close();
+            }
+        });
+
+        (response, action.get())
     }
 }
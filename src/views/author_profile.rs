@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+
+use egui::{self, Image, Label, RichText, ScrollArea, Sense, Ui, Widget};
+
+use crate::app::HabreState;
+use crate::habr_client::{
+    HabrClient,
+    article::{ArticleContent, ArticleData},
+    html_parse::extract_content_from_html,
+    user::{UserComment, UserProfile},
+};
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::article_renderer::ArticleRenderer;
+use crate::views::articles_list::ArticleListItem;
+use crate::views::comments::{Comments, format_time};
+use crate::views::link_router::open_link;
+use crate::views::loaded::{Loaded, loaded_ui};
+use crate::widgets::Pager;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AuthorTab {
+    Articles,
+    Comments,
+}
+
+pub struct AuthorProfile {
+    habre_state: Rc<RefCell<HabreState>>,
+    habr_client: HabrClient,
+
+    alias: String,
+    profile: Loaded<UserProfile>,
+    about: Arc<RwLock<Vec<ArticleContent>>>,
+    renderer: ArticleRenderer,
+    tab: AuthorTab,
+
+    articles: Loaded<Vec<ArticleData>>,
+    articles_page: u8,
+    articles_max_page: Arc<AtomicU8>,
+    comments: Loaded<Vec<UserComment>>,
+    comments_page: u8,
+    comments_max_page: Arc<AtomicU8>,
+    reset_scroll: bool,
+}
+
+impl AuthorProfile {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            habr_client: HabrClient::new(),
+
+            alias: String::new(),
+            profile: Default::default(),
+            about: Default::default(),
+            renderer: ArticleRenderer::new(),
+            tab: AuthorTab::Articles,
+
+            articles: Default::default(),
+            articles_page: 1,
+            articles_max_page: Arc::new(AtomicU8::new(1)),
+            comments: Default::default(),
+            comments_page: 1,
+            comments_max_page: Arc::new(AtomicU8::new(1)),
+            reset_scroll: false,
+        }
+    }
+
+    pub fn load(&mut self, alias: &str) {
+        self.alias = alias.to_string();
+        self.tab = AuthorTab::Articles;
+        self.articles_page = 1;
+        self.comments_page = 1;
+        *self.profile.write().unwrap() = None;
+        *self.comments.write().unwrap() = None;
+        self.about.write().unwrap().clear();
+
+        let client = self.habr_client.clone();
+        let profile = self.profile.clone();
+        let about = self.about.clone();
+        let alias = self.alias.clone();
+        self.habre_state.borrow().async_handle().spawn(async move {
+            match client.get_user_profile(&alias).await {
+                Ok(user) => {
+                    let about_html = user.about_html.clone();
+                    *profile.write().unwrap() = Some(Ok(user));
+                    if !about_html.is_empty() {
+                        *about.write().unwrap() = extract_content_from_html(about_html).await;
+                    }
+                }
+                Err(e) => *profile.write().unwrap() = Some(Err(e.to_string())),
+            }
+        });
+
+        self.load_articles();
+    }
+
+    fn load_articles(&mut self) {
+        self.reset_scroll = true;
+        *self.articles.write().unwrap() = None;
+
+        let client = self.habr_client.clone();
+        let articles = self.articles.clone();
+        let max_page = self.articles_max_page.clone();
+        let alias = self.alias.clone();
+        let page = self.articles_page;
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let result = client.get_user_articles(&alias, page).await.map(|(a, p)| {
+                max_page.store(p.clamp(1, u8::MAX as usize) as u8, Ordering::Relaxed);
+                a
+            });
+            *articles.write().unwrap() = Some(result.map_err(|e| e.to_string()));
+        });
+    }
+
+    fn load_comments(&mut self) {
+        self.reset_scroll = true;
+        *self.comments.write().unwrap() = None;
+
+        let client = self.habr_client.clone();
+        let comments = self.comments.clone();
+        let max_page = self.comments_max_page.clone();
+        let alias = self.alias.clone();
+        let page = self.comments_page;
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let result = client.get_user_comments(&alias, page).await.map(|(c, p)| {
+                max_page.store(p.clamp(1, u8::MAX as usize) as u8, Ordering::Relaxed);
+                c
+            });
+            *comments.write().unwrap() = Some(result.map_err(|e| e.to_string()));
+        });
+    }
+
+    fn header_ui(&mut self, ui: &mut Ui) {
+        let profile = self.profile.clone();
+        let profile = profile.read().unwrap();
+        let Some(profile) = loaded_ui(ui, &profile, "Не удалось загрузить профиль")
+        else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            if let Some(avatar_url) = profile.avatar_url.as_ref() {
+                let img_size = egui::Vec2::splat(ui.available_width() / 5.);
+                ui.add(
+                    Image::new("https:".to_string() + avatar_url.as_str())
+                        .fit_to_exact_size(img_size)
+                        .corner_radius(10.),
+                );
+            }
+            ui.vertical(|ui| {
+                ui.add(Label::new(RichText::new(profile.alias.as_str()).size(40.).strong()).wrap());
+                if let Some(fullname) = profile.fullname.as_ref() {
+                    ui.label(RichText::new(fullname.as_str()).size(29.));
+                }
+                if let Some(speciality) = profile.speciality.as_ref() {
+                    ui.label(RichText::new(speciality.as_str()).size(22.).weak());
+                }
+            });
+        });
+
+        ui.add_space(10.);
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(format!("Карма: {}", profile.score_stats.score)).size(25.));
+            ui.label(RichText::new(format!("Рейтинг: {:.1}", profile.rating)).size(25.));
+            if let Some(position) = profile.rating_position {
+                ui.label(RichText::new(format!("#{}", position)).size(25.).weak());
+            }
+        });
+        if let Some(registered_at) = profile.registered_at_str() {
+            ui.label(RichText::new(format!("Зарегистрирован {}", registered_at)).size(22.));
+        }
+
+        if !profile.hubs.is_empty() {
+            ui.add_space(10.);
+            ui.label(RichText::new("Хабы").size(29.).strong());
+            ui.horizontal_wrapped(|ui| {
+                for hub in profile.hubs.iter() {
+                    Label::new(RichText::new(hub.title.as_str()).size(22.))
+                        .selectable(false)
+                        .ui(ui);
+                }
+            });
+        }
+
+        ui.add_space(10.);
+        self.renderer.content_ui(ui, &self.about.read().unwrap());
+
+        ui.add_space(10.);
+        ui.horizontal(|ui| {
+            let articles_tab = ui.selectable_value(
+                &mut self.tab,
+                AuthorTab::Articles,
+                RichText::new(format!("Статьи ({})", profile.counters.post_count)).size(32.),
+            );
+            let comments_tab = ui.selectable_value(
+                &mut self.tab,
+                AuthorTab::Comments,
+                RichText::new(format!("Комментарии ({})", profile.counters.comment_count))
+                    .size(32.),
+            );
+            if comments_tab.clicked() && self.comments.read().unwrap().is_none() {
+                self.load_comments();
+            } else if articles_tab.clicked() || comments_tab.clicked() {
+                self.reset_scroll = true;
+            }
+        });
+        ui.separator();
+    }
+
+    fn articles_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let articles = self.articles.clone();
+        let articles = articles.read().unwrap();
+        match loaded_ui(ui, &articles, "Не удалось загрузить статьи") {
+            Some(articles) if articles.is_empty() => {
+                ui.label(RichText::new("Нет публикаций").size(22.).weak());
+            }
+            Some(articles) => {
+                ArticleListItem::list_ui(ui, self.habre_state.clone(), articles, view_stack);
+            }
+            None => {}
+        }
+        drop(articles);
+
+        if Pager::new(
+            &mut self.articles_page,
+            self.articles_max_page.load(Ordering::Relaxed),
+        )
+        .ui(ui)
+        .changed()
+        {
+            self.load_articles();
+        }
+    }
+
+    fn comments_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let mut selected_publication = None;
+        let comments = self.comments.clone();
+        let comments = comments.read().unwrap();
+        match loaded_ui(ui, &comments, "Не удалось загрузить комментарии")
+        {
+            None => {}
+            Some(comments) if comments.is_empty() => {
+                ui.label(RichText::new("Нет комментариев").size(22.).weak());
+            }
+            Some(comments) => {
+                for (index, comment) in comments.iter().enumerate() {
+                    if index > 0 {
+                        ui.separator();
+                    }
+                    if let Some(publication) = comment.publication.as_ref() {
+                        let title = Label::new(
+                            RichText::new(publication.title.as_str())
+                                .size(25.)
+                                .strong()
+                                .color(ui.visuals().hyperlink_color),
+                        )
+                        .selectable(false)
+                        .sense(Sense::click())
+                        .ui(ui);
+                        if title.clicked() {
+                            selected_publication = Some(publication.id.clone());
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format_time(&comment.published_at))
+                                .size(20.)
+                                .weak(),
+                        );
+                        if comment.score != 0 {
+                            ui.label(RichText::new(format!("{:+}", comment.score)).size(20.));
+                        }
+                    });
+                    ui.add(
+                        Label::new(RichText::new(comment.message.as_str()).size(25.))
+                            .wrap()
+                            .selectable(true),
+                    );
+                }
+            }
+        }
+        drop(comments);
+
+        if Pager::new(
+            &mut self.comments_page,
+            self.comments_max_page.load(Ordering::Relaxed),
+        )
+        .ui(ui)
+        .changed()
+        {
+            self.load_comments();
+        }
+
+        if let Some(article_id) = selected_publication {
+            let mut comments_view = Comments::new(article_id, self.habre_state.clone());
+            comments_view.load_comments();
+            view_stack.push(Rc::new(RefCell::new(comments_view)));
+        }
+    }
+}
+
+impl UiView for AuthorProfile {
+    fn ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let mut scroll_area = ScrollArea::vertical()
+            .max_height(ui.available_height())
+            .scroll_source(if self.renderer.is_viewing_image() {
+                egui::scroll_area::ScrollSource::NONE
+            } else {
+                egui::scroll_area::ScrollSource::ALL
+            });
+        if self.reset_scroll {
+            scroll_area = scroll_area.vertical_scroll_offset(0.);
+            self.reset_scroll = false;
+        }
+
+        scroll_area.show(ui, |ui| {
+            self.header_ui(ui);
+            match self.tab {
+                AuthorTab::Articles => self.articles_ui(ui, view_stack),
+                AuthorTab::Comments => self.comments_ui(ui, view_stack),
+            }
+        });
+
+        self.renderer.image_viewer_ui(ui);
+        if let Some(url) = self.renderer.take_clicked_link() {
+            open_link(ui, self.habre_state.clone(), &url, view_stack);
+        }
+    }
+}
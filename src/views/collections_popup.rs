@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+
+use egui::{self, Button, Label, RichText, Sense, TextEdit, Ui, Widget};
+
+use crate::storage::collections::SavedCollections;
+
+/// Assigns collections and personal tags to a saved article. Shown over any view,
+/// so the views only open it.
+#[derive(Default)]
+pub struct CollectionsPopup {
+    /// Id and title of the article.
+    article: Option<(String, String)>,
+    new_collection: String,
+    new_tag: String,
+}
+
+impl CollectionsPopup {
+    pub fn open(&mut self, article_id: &str, title: &str) {
+        self.article = Some((article_id.to_string(), title.to_string()));
+        self.new_collection.clear();
+        self.new_tag.clear();
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, collections: &RefCell<SavedCollections>) {
+        let Some((article_id, title)) = self.article.clone() else {
+            return;
+        };
+        let screen_rect = ui.ctx().viewport_rect();
+
+        let mut close = egui::Area::new(egui::Id::new("collections_popup_overlay"))
+            .fixed_pos(screen_rect.min)
+            .interactable(true)
+            .show(ui.ctx(), |ui| {
+                let (resp, painter) = ui.allocate_painter(screen_rect.size(), Sense::click());
+                painter.add(egui::Shape::rect_filled(
+                    screen_rect,
+                    egui::CornerRadius::ZERO,
+                    egui::Color32::from_black_alpha(150),
+                ));
+                resp
+            })
+            .inner
+            .clicked();
+
+        egui::Window::new("Коллекции и теги")
+            .default_rect(screen_rect)
+            .collapsible(false)
+            .resizable(false)
+            .movable(false)
+            .title_bar(true)
+            .order(egui::Order::Foreground)
+            .show(ui.ctx(), |ui| {
+                let mut collections = collections.borrow_mut();
+                let labels = collections.labels(&article_id);
+                let mut changed = false;
+
+                Label::new(RichText::new(title).size(25.).weak())
+                    .wrap()
+                    .ui(ui);
+
+                ui.add_space(10.);
+                ui.label(RichText::new("Коллекции").size(29.).strong());
+                for name in collections.collections.clone() {
+                    let mut included = labels.collections.contains(&name);
+                    if ui
+                        .checkbox(&mut included, RichText::new(name.as_str()).size(29.))
+                        .changed()
+                    {
+                        collections.set_in_collection(&article_id, &name, included);
+                        changed = true;
+                    }
+                }
+                if let Some(name) = text_input(ui, &mut self.new_collection, "Новая коллекция")
+                {
+                    collections.add_collection(&name);
+                    collections.set_in_collection(&article_id, name.trim(), true);
+                    changed = true;
+                }
+
+                ui.add_space(10.);
+                ui.label(RichText::new("Теги").size(29.).strong());
+                ui.horizontal_wrapped(|ui| {
+                    for tag in collections.all_tags() {
+                        let selected = labels.tags.contains(&tag);
+                        if ui
+                            .selectable_label(
+                                selected,
+                                RichText::new(format!("#{}", tag)).size(25.),
+                            )
+                            .clicked()
+                        {
+                            if selected {
+                                collections.remove_tag(&article_id, &tag);
+                            } else {
+                                collections.add_tag(&article_id, &tag);
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+                if let Some(tag) = text_input(ui, &mut self.new_tag, "Новый тег") {
+                    collections.add_tag(&article_id, &tag);
+                    changed = true;
+                }
+
+                if changed {
+                    collections.save();
+                }
+                if let Some(e) = collections.save_error.as_ref() {
+                    Label::new(
+                        RichText::new(format!("Не удалось сохранить: {}", e))
+                            .size(22.)
+                            .color(ui.visuals().error_fg_color),
+                    )
+                    .wrap()
+                    .ui(ui);
+                }
+
+                ui.add_space(10.);
+                if ui
+                    .button(RichText::new("Готово").size(32.).strong())
+                    .clicked()
+                {
+                    close = true;
+                }
+            });
+
+        if close {
+            self.article = None;
+        }
+    }
+}
+
+/// Text field with the add button, returns the entered text when it's submitted.
+fn text_input(ui: &mut Ui, value: &mut String, hint: &str) -> Option<String> {
+    let mut submitted = false;
+    ui.horizontal(|ui| {
+        let add_button = Button::new(RichText::new("Добавить").size(29.)).corner_radius(5.);
+        let text_edit = ui.add(
+            TextEdit::singleline(value)
+                .hint_text(RichText::new(hint).size(29.))
+                .font(egui::FontId::proportional(29.))
+                .desired_width(ui.available_width() - 160.),
+        );
+        submitted = text_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        submitted |= ui.add(add_button).clicked();
+    });
+    if !submitted || value.trim().is_empty() {
+        return None;
+    }
+    Some(std::mem::take(value))
+}
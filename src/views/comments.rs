@@ -5,13 +5,14 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
 use chrono::{DateTime, Local};
-use egui::{self, Color32, Label, RichText, ScrollArea, Spinner, Ui, Vec2, Widget};
+use egui::{self, Color32, Label, RichText, ScrollArea, Sense, Spinner, Ui, Vec2, Widget};
 
 use crate::app::HabreState;
 use crate::habr_client::HabrClient;
 use crate::habr_client::comment::Comment;
 use crate::habr_client::html_parse::extract_text_from_html;
 use crate::view_stack::UiView;
+use crate::views::author_profile::AuthorProfile;
 
 pub struct Comments {
     article_id: String,
@@ -21,6 +22,8 @@ pub struct Comments {
     comments: Arc<RwLock<Vec<Comment>>>,
     go_top: Arc<AtomicBool>,
     expanded_comments: HashSet<String>,
+    highlighted_comment: Option<String>,
+    scroll_to_highlighted: bool,
 }
 
 impl Comments {
@@ -33,9 +36,17 @@ impl Comments {
             comments: Default::default(),
             go_top: Default::default(),
             expanded_comments: HashSet::new(),
+            highlighted_comment: None,
+            scroll_to_highlighted: false,
         }
     }
 
+    /// Expands the thread of the comment and scrolls to it after loading.
+    pub fn show_comment(&mut self, comment_id: &str) {
+        self.highlighted_comment = Some(comment_id.to_string());
+        self.scroll_to_highlighted = true;
+    }
+
     pub fn load_comments(&mut self) {
         self.is_loading.store(true, Ordering::Relaxed);
         let client = self.habr_client.clone();
@@ -57,7 +68,7 @@ impl Comments {
 }
 
 impl UiView for Comments {
-    fn ui(&mut self, ui: &mut egui::Ui, _view_stack: &mut crate::view_stack::ViewStack) {
+    fn ui(&mut self, ui: &mut egui::Ui, view_stack: &mut crate::view_stack::ViewStack) {
         if self.is_loading.load(Ordering::Relaxed) {
             ui.add_sized(ui.available_size(), Spinner::new().size(100.));
             return;
@@ -70,6 +81,17 @@ impl UiView for Comments {
             self.go_top.store(false, Ordering::Relaxed);
         }
 
+        if self.scroll_to_highlighted
+            && let Some(comment_id) = self.highlighted_comment.as_ref()
+        {
+            match parent_ids(&self.comments.read().unwrap(), comment_id) {
+                Some(parent_ids) => self.expanded_comments.extend(parent_ids),
+                // The comment was deleted or the link was wrong.
+                None => self.scroll_to_highlighted = false,
+            }
+        }
+
+        let mut selected_author = None;
         scroll_area.show(ui, |ui| {
             if self.comments.read().unwrap().is_empty() {
                 ui.add(Label::new(RichText::new("Нет комментариев").size(22.)).wrap());
@@ -77,27 +99,57 @@ impl UiView for Comments {
             }
 
             for comment in self.comments.read().unwrap().iter() {
-                comment_ui(ui, comment, &mut self.expanded_comments);
+                comment_ui(
+                    ui,
+                    comment,
+                    &mut self.expanded_comments,
+                    &mut selected_author,
+                    self.highlighted_comment.as_deref(),
+                    &mut self.scroll_to_highlighted,
+                );
             }
         });
+
+        if let Some(alias) = selected_author {
+            let mut author_profile = AuthorProfile::new(self.habre_state.clone());
+            author_profile.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(author_profile)));
+        }
     }
 }
 
-fn comment_ui(ui: &mut Ui, comment: &Comment, expanded_comments: &mut HashSet<String>) {
+fn comment_ui(
+    ui: &mut Ui,
+    comment: &Comment,
+    expanded_comments: &mut HashSet<String>,
+    selected_author: &mut Option<String>,
+    highlighted_comment: Option<&str>,
+    scroll_to_highlighted: &mut bool,
+) {
     let is_expanded = expanded_comments.contains(&comment.id);
     let has_children = !comment.children.is_empty();
-
-    ui.horizontal(|ui| {
-        ui.add(Label::new(
-            RichText::new(
-                comment
-                    .author
-                    .as_ref()
-                    .map_or("Deleted User", |a| a.alias.as_str()),
-            )
-            .strong()
-            .size(22.),
-        ));
+    let is_highlighted = highlighted_comment == Some(comment.id.as_str());
+
+    let header = ui.horizontal(|ui| {
+        match comment.author.as_ref() {
+            Some(author) => {
+                let author_label = Label::new(
+                    RichText::new(author.alias.as_str())
+                        .strong()
+                        .size(22.)
+                        .color(ui.visuals().hyperlink_color),
+                )
+                .selectable(false)
+                .sense(Sense::click())
+                .ui(ui);
+                if author_label.clicked() {
+                    *selected_author = Some(author.alias.clone());
+                }
+            }
+            None => {
+                ui.add(Label::new(RichText::new("Deleted User").strong().size(22.)));
+            }
+        }
 
         ui.add(Label::new(
             RichText::new(format_time(&comment.published_at))
@@ -113,13 +165,17 @@ fn comment_ui(ui: &mut Ui, comment: &Comment, expanded_comments: &mut HashSet<St
             ));
         }
     });
+    if is_highlighted && *scroll_to_highlighted {
+        header.response.scroll_to_me(Some(egui::Align::TOP));
+        *scroll_to_highlighted = false;
+    }
 
     ui.indent(&comment.id, |ui| {
-        ui.add(
-            Label::new(RichText::new(extract_text_from_html(&comment.message)).size(25.))
-                .wrap()
-                .selectable(true),
-        );
+        let mut message = RichText::new(extract_text_from_html(&comment.message)).size(25.);
+        if is_highlighted {
+            message = message.background_color(ui.visuals().selection.bg_fill);
+        }
+        ui.add(Label::new(message).wrap().selectable(true));
 
         if has_children {
             ui.horizontal(|ui| {
@@ -145,14 +201,35 @@ fn comment_ui(ui: &mut Ui, comment: &Comment, expanded_comments: &mut HashSet<St
             });
             if is_expanded {
                 for child in &comment.children {
-                    comment_ui(ui, child, expanded_comments);
+                    comment_ui(
+                        ui,
+                        child,
+                        expanded_comments,
+                        selected_author,
+                        highlighted_comment,
+                        scroll_to_highlighted,
+                    );
                 }
             }
         }
     });
 }
 
-fn format_time(time: &str) -> String {
+/// Ids of the comments the comment is nested in, `None` if there is no such comment.
+fn parent_ids(comments: &[Comment], comment_id: &str) -> Option<Vec<String>> {
+    for comment in comments {
+        if comment.id == comment_id {
+            return Some(vec![]);
+        }
+        if let Some(mut ids) = parent_ids(&comment.children, comment_id) {
+            ids.push(comment.id.clone());
+            return Some(ids);
+        }
+    }
+    None
+}
+
+pub(crate) fn format_time(time: &str) -> String {
     if let Ok(dt) = time.parse::<DateTime<Local>>() {
         dt.format("%d.%m.%Y %H:%M").to_string()
     } else {
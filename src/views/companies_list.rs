@@ -0,0 +1,237 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicBool, AtomicU8, Ordering},
+};
+
+use egui::{
+    self, Image, Label, Response, RichText, ScrollArea, Sense, Spinner, TextEdit, Ui, UiBuilder,
+    Widget,
+};
+use egui_flex::Flex;
+
+use crate::app::HabreState;
+use crate::habr_client::{HabrClient, company::CompanySummary};
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::company_details::CompanyDetails;
+use crate::widgets::Pager;
+
+pub struct CompaniesList {
+    habre_state: Rc<RefCell<HabreState>>,
+    habr_client: HabrClient,
+
+    search_text: String,
+    search_was_changed: bool,
+
+    is_loading: Arc<AtomicBool>,
+    load_error: Arc<RwLock<Option<String>>>,
+    reset_scroll_area: bool,
+    current_page: u8,
+    max_page: Arc<AtomicU8>,
+    companies: Arc<RwLock<Vec<CompanySummary>>>,
+    is_loaded: bool,
+}
+
+impl CompaniesList {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            habr_client: HabrClient::new(),
+
+            search_text: String::new(),
+            search_was_changed: false,
+
+            is_loading: Default::default(),
+            load_error: Default::default(),
+            reset_scroll_area: false,
+            current_page: 1,
+            max_page: Arc::new(AtomicU8::new(0)),
+            companies: Default::default(),
+            is_loaded: false,
+        }
+    }
+
+    /// Loads the first page the first time the list is opened.
+    pub fn open(&mut self) {
+        if !self.is_loaded {
+            self.is_loaded = true;
+            self.get_companies();
+        }
+    }
+
+    pub fn get_companies(&mut self) {
+        self.is_loading.store(true, Ordering::Relaxed);
+        self.reset_scroll_area = true;
+
+        let client = self.habr_client.clone();
+        let search_text = self.search_text.trim().to_string();
+        let companies = self.companies.clone();
+        let load_error = self.load_error.clone();
+        let current_page = self.current_page;
+        let is_loading = self.is_loading.clone();
+        let max_page = self.max_page.clone();
+
+        self.habre_state.borrow().async_handle().spawn(async move {
+            match client.get_companies(&search_text, current_page).await {
+                Ok((new_companies, max_page_num)) => {
+                    *companies.write().unwrap() = new_companies;
+                    *load_error.write().unwrap() = None;
+                    max_page.store(
+                        max_page_num.clamp(1, u8::MAX as usize) as u8,
+                        Ordering::Relaxed,
+                    );
+                }
+                Err(e) => *load_error.write().unwrap() = Some(e.to_string()),
+            }
+            is_loading.store(false, Ordering::Relaxed);
+        });
+    }
+
+    fn search_ui(&mut self, ui: &mut Ui) {
+        let search_edit = TextEdit::singleline(&mut self.search_text)
+            .desired_width(f32::INFINITY)
+            .font(egui::epaint::text::FontId::proportional(32.))
+            .hint_text(RichText::new("Поиск компаний").size(32.))
+            .show(ui)
+            .response;
+
+        if search_edit.changed() {
+            self.search_was_changed = true;
+        }
+        if !search_edit.has_focus() && self.search_was_changed {
+            self.search_was_changed = false;
+            self.current_page = 1;
+            self.get_companies();
+        }
+    }
+}
+
+impl UiView for CompaniesList {
+    fn ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        Flex::vertical()
+            .justify(egui_flex::FlexJustify::SpaceBetween)
+            .grow_items(0.)
+            .h_full()
+            .w_full()
+            .show(ui, |f_ui| {
+                f_ui.add_flex(
+                    egui_flex::item(),
+                    Flex::vertical().align_items(egui_flex::FlexAlign::Start),
+                    |f_ui| {
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            ui.add(Label::new(RichText::new("Компании").size(40.).strong()))
+                        });
+                        f_ui.add_ui(egui_flex::item(), |ui| self.search_ui(ui));
+                        f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
+                    },
+                );
+
+                if self.is_loading.load(Ordering::Relaxed) {
+                    f_ui.add(
+                        egui_flex::item().align_self(egui_flex::FlexAlign::Center),
+                        Spinner::new().size(100.),
+                    );
+                } else {
+                    f_ui.add_ui(egui_flex::item().shrink(), |ui| {
+                        let mut scroll_area = ScrollArea::vertical()
+                            .max_width(ui.available_width())
+                            .hscroll(false)
+                            .scroll_bar_visibility(
+                                egui::scroll_area::ScrollBarVisibility::AlwaysHidden,
+                            );
+                        if self.reset_scroll_area {
+                            scroll_area = scroll_area.vertical_scroll_offset(0.);
+                            self.reset_scroll_area = false;
+                        }
+
+                        scroll_area.show(ui, |ui| {
+                            if let Some(e) = self.load_error.read().unwrap().as_ref() {
+                                ui.label(
+                                    RichText::new(format!("Не удалось загрузить компании: {}", e))
+                                        .size(25.)
+                                        .color(ui.visuals().error_fg_color),
+                                );
+                                return;
+                            }
+
+                            let mut selected_company = None;
+                            for (index, company) in
+                                self.companies.read().unwrap().iter().enumerate()
+                            {
+                                if index > 0 {
+                                    ui.separator();
+                                }
+                                if CompanyUI::ui(ui, company).clicked() {
+                                    selected_company = Some(company.alias.clone());
+                                }
+                            }
+
+                            if let Some(alias) = selected_company {
+                                let mut company_details =
+                                    CompanyDetails::new(self.habre_state.clone());
+                                company_details.load(&alias);
+                                view_stack.push(Rc::new(RefCell::new(company_details)));
+                            }
+                        });
+                    });
+                }
+
+                f_ui.add_flex(egui_flex::item(), Flex::vertical().w_full(), |f_ui| {
+                    f_ui.add_ui(egui_flex::item(), |ui| {
+                        if Pager::new(
+                            &mut self.current_page,
+                            self.max_page.load(Ordering::Relaxed),
+                        )
+                        .ui(ui)
+                        .changed()
+                        {
+                            self.get_companies();
+                        };
+                    });
+                });
+            });
+    }
+}
+
+pub struct CompanyUI;
+
+impl CompanyUI {
+    pub fn ui(ui: &mut Ui, company: &CompanySummary) -> Response {
+        let img_size = egui::Vec2::splat(ui.available_width() / 5.);
+        ui.scope_builder(
+            UiBuilder::new()
+                .id_salt(&company.alias)
+                .sense(Sense::click()),
+            |ui| {
+                ui.horizontal(|ui| {
+                    if let Some(image_url) = company.image_url.as_ref() {
+                        ui.add(
+                            Image::new("https:".to_string() + image_url.as_str())
+                                .fit_to_exact_size(img_size),
+                        );
+                    }
+                    ui.vertical(|ui| {
+                        ui.spacing_mut().item_spacing = egui::Vec2::splat(5.);
+                        Label::new(RichText::new(company.title.as_str()).size(32.).strong())
+                            .selectable(false)
+                            .ui(ui);
+                        if !company.description.is_empty() {
+                            Label::new(RichText::new(company.description.as_str()).size(25.))
+                                .selectable(false)
+                                .ui(ui);
+                        }
+                        Label::new(
+                            RichText::new(format!("Рейтинг: {:.1}", company.statistics.rating))
+                                .size(22.)
+                                .weak(),
+                        )
+                        .selectable(false)
+                        .ui(ui);
+                    });
+                });
+            },
+        )
+        .response
+    }
+}
@@ -0,0 +1,205 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+
+use egui::{self, Image, Label, RichText, ScrollArea, Ui};
+
+use crate::app::HabreState;
+use crate::habr_client::{
+    HabrClient,
+    article::{ArticleContent, ArticleData},
+    company::CompanyProfile,
+    html_parse::extract_content_from_html,
+};
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::article_renderer::{ArticleRenderer, open_url};
+use crate::views::articles_list::ArticleListItem;
+use crate::views::link_router::open_link;
+use crate::views::loaded::{Loaded, loaded_ui};
+use crate::widgets::Pager;
+
+pub struct CompanyDetails {
+    habre_state: Rc<RefCell<HabreState>>,
+    habr_client: HabrClient,
+
+    alias: String,
+    company: Loaded<CompanyProfile>,
+    about: Arc<RwLock<Vec<ArticleContent>>>,
+    renderer: ArticleRenderer,
+
+    articles: Loaded<Vec<ArticleData>>,
+    current_page: u8,
+    max_page: Arc<AtomicU8>,
+    reset_scroll: bool,
+}
+
+impl CompanyDetails {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            habr_client: HabrClient::new(),
+
+            alias: String::new(),
+            company: Default::default(),
+            about: Default::default(),
+            renderer: ArticleRenderer::new(),
+
+            articles: Default::default(),
+            current_page: 1,
+            max_page: Arc::new(AtomicU8::new(1)),
+            reset_scroll: false,
+        }
+    }
+
+    pub fn load(&mut self, alias: &str) {
+        self.alias = alias.to_string();
+        self.current_page = 1;
+        *self.company.write().unwrap() = None;
+        self.about.write().unwrap().clear();
+
+        let client = self.habr_client.clone();
+        let company = self.company.clone();
+        let about = self.about.clone();
+        let alias = self.alias.clone();
+        self.habre_state.borrow().async_handle().spawn(async move {
+            match client.get_company_profile(&alias).await {
+                Ok(profile) => {
+                    let about_html = profile.about_html.clone();
+                    *company.write().unwrap() = Some(Ok(profile));
+                    if !about_html.is_empty() {
+                        *about.write().unwrap() = extract_content_from_html(about_html).await;
+                    }
+                }
+                Err(e) => *company.write().unwrap() = Some(Err(e.to_string())),
+            }
+        });
+
+        self.load_articles();
+    }
+
+    fn load_articles(&mut self) {
+        self.reset_scroll = true;
+        *self.articles.write().unwrap() = None;
+
+        let client = self.habr_client.clone();
+        let articles = self.articles.clone();
+        let max_page = self.max_page.clone();
+        let alias = self.alias.clone();
+        let page = self.current_page;
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let result = client
+                .get_company_articles(&alias, page)
+                .await
+                .map(|(a, p)| {
+                    max_page.store(p.clamp(1, u8::MAX as usize) as u8, Ordering::Relaxed);
+                    a
+                });
+            *articles.write().unwrap() = Some(result.map_err(|e| e.to_string()));
+        });
+    }
+
+    fn header_ui(&mut self, ui: &mut Ui) {
+        let company = self.company.clone();
+        let company = company.read().unwrap();
+        let Some(company) = loaded_ui(ui, &company, "Не удалось загрузить компанию")
+        else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            if let Some(image_url) = company.image_url.as_ref() {
+                let img_size = egui::Vec2::splat(ui.available_width() / 5.);
+                ui.add(
+                    Image::new("https:".to_string() + image_url.as_str())
+                        .fit_to_exact_size(img_size),
+                );
+            }
+            ui.vertical(|ui| {
+                ui.add(Label::new(RichText::new(company.title.as_str()).size(40.).strong()).wrap());
+                ui.label(
+                    RichText::new(format!(
+                        "Подписчики: {} · Рейтинг: {:.1}",
+                        company.statistics.subscribers_count, company.statistics.rating
+                    ))
+                    .size(25.),
+                );
+            });
+        });
+
+        if !company.description.is_empty() {
+            ui.add(Label::new(RichText::new(company.description.as_str()).size(25.)).wrap());
+        }
+        if let Some(staff_number) = company.staff_number.as_ref() {
+            ui.label(RichText::new(format!("Сотрудники: {}", staff_number)).size(22.));
+        }
+        if let Some(site_url) = company.site_url.as_ref() {
+            let site_link = ui.link(
+                RichText::new(site_url.as_str())
+                    .size(22.)
+                    .color(ui.visuals().hyperlink_color),
+            );
+            if site_link.clicked() {
+                open_url(ui, site_url);
+            }
+        }
+
+        ui.add_space(10.);
+        self.renderer.content_ui(ui, &self.about.read().unwrap());
+
+        ui.add_space(10.);
+        ui.label(RichText::new("Блог").size(29.).strong());
+        ui.separator();
+    }
+
+    fn articles_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let articles = self.articles.clone();
+        let articles = articles.read().unwrap();
+        match loaded_ui(ui, &articles, "Не удалось загрузить статьи") {
+            Some(articles) if articles.is_empty() => {
+                ui.label(RichText::new("Нет публикаций").size(22.).weak());
+            }
+            Some(articles) => {
+                ArticleListItem::list_ui(ui, self.habre_state.clone(), articles, view_stack);
+            }
+            None => {}
+        }
+        drop(articles);
+
+        if Pager::new(
+            &mut self.current_page,
+            self.max_page.load(Ordering::Relaxed),
+        )
+        .ui(ui)
+        .changed()
+        {
+            self.load_articles();
+        }
+    }
+}
+
+impl UiView for CompanyDetails {
+    fn ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let mut scroll_area = ScrollArea::vertical()
+            .max_height(ui.available_height())
+            .scroll_source(if self.renderer.is_viewing_image() {
+                egui::scroll_area::ScrollSource::NONE
+            } else {
+                egui::scroll_area::ScrollSource::ALL
+            });
+        if self.reset_scroll {
+            scroll_area = scroll_area.vertical_scroll_offset(0.);
+            self.reset_scroll = false;
+        }
+
+        scroll_area.show(ui, |ui| {
+            self.header_ui(ui);
+            self.articles_ui(ui, view_stack);
+        });
+
+        self.renderer.image_viewer_ui(ui);
+        if let Some(url) = self.renderer.take_clicked_link() {
+            open_link(ui, self.habre_state.clone(), &url, view_stack);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use egui::{self, Button, Label, RichText, ScrollArea, TextEdit, Ui, Widget};
+
+use crate::app::HabreState;
+use crate::storage::content_filter::FilterKind;
+use crate::view_stack::{UiView, ViewStack};
+
+pub struct ContentFilterView {
+    habre_state: Rc<RefCell<HabreState>>,
+    new_kind: FilterKind,
+    new_value: String,
+    add_error: Option<String>,
+}
+
+impl ContentFilterView {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            new_kind: FilterKind::Author,
+            new_value: String::new(),
+            add_error: None,
+        }
+    }
+
+    fn add_rule_ui(&mut self, ui: &mut Ui) {
+        ui.label(RichText::new("Новое правило").size(29.).strong());
+        ui.horizontal_wrapped(|ui| {
+            for kind in FilterKind::ALL {
+                ui.selectable_value(
+                    &mut self.new_kind,
+                    kind,
+                    RichText::new(kind.title()).size(25.),
+                );
+            }
+        });
+
+        let hint = match self.new_kind {
+            FilterKind::Author => "Алиас автора",
+            FilterKind::Hub => "Алиас хаба",
+            FilterKind::Tag => "Тег",
+            FilterKind::Company => "Алиас компании",
+            FilterKind::Keyword => "Слово или фраза",
+            FilterKind::Regex => "Выражение для заголовка",
+        };
+        let text_edit = ui.add(
+            TextEdit::singleline(&mut self.new_value)
+                .hint_text(RichText::new(hint).size(32.))
+                .font(egui::FontId::proportional(32.))
+                .desired_width(f32::INFINITY),
+        );
+        let submitted = text_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+        let add_button = Button::new(RichText::new("Добавить").size(32.)).corner_radius(5.);
+        if ui.add(add_button).clicked() || submitted {
+            let settings = self.habre_state.borrow().settings.clone();
+            let result = settings
+                .borrow_mut()
+                .update_content_filter(|f| f.add_rule(self.new_kind, &self.new_value));
+            match result {
+                Ok(()) => {
+                    self.new_value.clear();
+                    self.add_error = None;
+                }
+                Err(e) => self.add_error = Some(e),
+            }
+        }
+
+        if let Some(e) = self.add_error.as_ref() {
+            ui.label(
+                RichText::new(e.as_str())
+                    .size(22.)
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+    }
+
+    fn rules_ui(&mut self, ui: &mut Ui) {
+        let settings = self.habre_state.borrow().settings.clone();
+        let mut collapse = settings.borrow().content_filter().collapse;
+        if ui
+            .checkbox(
+                &mut collapse,
+                RichText::new("Сворачивать вместо скрытия").size(25.),
+            )
+            .changed()
+        {
+            settings
+                .borrow_mut()
+                .update_content_filter(|f| f.collapse = collapse);
+        }
+
+        ui.add_space(10.);
+        ui.label(RichText::new("Правила").size(29.).strong());
+
+        let rules = settings.borrow().content_filter().rules().to_vec();
+        if rules.is_empty() {
+            ui.label(RichText::new("Правил пока нет").size(22.).weak());
+            return;
+        }
+
+        let mut removed_rule = None;
+        for (index, rule) in rules.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(Button::new(RichText::new("🗑").size(29.)).frame(false))
+                    .clicked()
+                {
+                    removed_rule = Some(index);
+                }
+                Label::new(RichText::new(rule.description()).size(25.))
+                    .wrap()
+                    .ui(ui);
+            });
+        }
+
+        if let Some(index) = removed_rule {
+            settings
+                .borrow_mut()
+                .update_content_filter(|f| f.remove_rule(index));
+        }
+    }
+}
+
+impl UiView for ContentFilterView {
+    fn ui(&mut self, ui: &mut Ui, _view_stack: &mut ViewStack) {
+        ui.add(Label::new(
+            RichText::new("Фильтр контента").size(40.).strong(),
+        ));
+        ui.label(
+            RichText::new("Статьи, подходящие под правила, не показываются в лентах.")
+                .size(22.)
+                .weak(),
+        );
+        ui.separator();
+
+        ScrollArea::vertical()
+            .max_height(ui.available_height())
+            .show(ui, |ui| {
+                self.add_rule_ui(ui);
+                ui.separator();
+                self.rules_ui(ui);
+
+                let settings = self.habre_state.borrow().settings.clone();
+                if let Some(e) = settings.borrow().save_error() {
+                    ui.label(
+                        RichText::new(format!("Не удалось сохранить фильтр: {}", e))
+                            .size(22.)
+                            .color(ui.visuals().error_fg_color),
+                    );
+                }
+            });
+    }
+}
@@ -0,0 +1,336 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+use egui::{self, Button, Image, Label, RichText, ScrollArea, Sense, Spinner, Ui, Widget};
+
+use crate::app::HabreState;
+use crate::habr_client::{
+    HabrClient, article::ArticleContent, company::CompanySummary,
+    html_parse::extract_content_from_html, hub::Hub, user::UserSummary,
+};
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::article_renderer::ArticleRenderer;
+use crate::views::articles_list::ArticlesList;
+use crate::views::author_profile::AuthorProfile;
+use crate::views::company_details::CompanyDetails;
+use crate::views::link_router::open_link;
+use crate::views::loaded::{Loaded, loaded_ui};
+
+const TOP_ENTRIES_COUNT: usize = 5;
+
+type LoadedList<T> = Loaded<Vec<T>>;
+
+pub struct HubDetails {
+    habre_state: Rc<RefCell<HabreState>>,
+    habr_client: HabrClient,
+    articles_selected_cb: Option<Box<dyn FnMut(&Hub, &mut ViewStack)>>,
+
+    hub: Option<Hub>,
+    /// Alias of the hub being shown, replies fetched for the other ones are dropped.
+    current_alias: Arc<RwLock<String>>,
+    fetched_hub: Loaded<Hub>,
+    load_error: Option<String>,
+    description: Arc<RwLock<Vec<ArticleContent>>>,
+    authors: LoadedList<UserSummary>,
+    companies: LoadedList<CompanySummary>,
+    renderer: ArticleRenderer,
+    reset_scroll: bool,
+}
+
+impl HubDetails {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            habr_client: HabrClient::new(),
+            articles_selected_cb: None,
+
+            hub: None,
+            current_alias: Default::default(),
+            fetched_hub: Default::default(),
+            load_error: None,
+            description: Default::default(),
+            authors: Default::default(),
+            companies: Default::default(),
+            renderer: ArticleRenderer::new(),
+            reset_scroll: false,
+        }
+    }
+
+    pub fn on_articles_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Hub, &mut ViewStack) + 'static,
+    {
+        self.articles_selected_cb = Some(Box::new(callback));
+    }
+
+    /// Opens the hub known only by alias, e.g. from a link.
+    pub fn load(&mut self, alias: &str) {
+        self.hub = None;
+        self.load_error = None;
+        *self.current_alias.write().unwrap() = alias.to_string();
+
+        let client = self.habr_client.clone();
+        let current_alias = self.current_alias.clone();
+        let fetched_hub = self.fetched_hub.clone();
+        let alias = alias.to_string();
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let result = client.get_hub(&alias).await.map_err(|e| e.to_string());
+            if *current_alias.read().unwrap() == alias {
+                *fetched_hub.write().unwrap() = Some(result);
+            }
+        });
+    }
+
+    pub fn set_hub(&mut self, hub: Hub) {
+        self.reset_scroll = true;
+        // Cleared under the alias lock, so no reply for the previous hub gets in after.
+        {
+            let mut current_alias = self.current_alias.write().unwrap();
+            *current_alias = hub.alias.clone();
+            self.description.write().unwrap().clear();
+            *self.authors.write().unwrap() = None;
+            *self.companies.write().unwrap() = None;
+        }
+
+        let handle = self.habre_state.borrow().async_handle();
+
+        let current_alias = self.current_alias.clone();
+        let description = self.description.clone();
+        let description_html = hub.description_html.clone();
+        let alias = hub.alias.clone();
+        handle.spawn(async move {
+            let content = extract_content_from_html(description_html).await;
+            if *current_alias.read().unwrap() == alias {
+                *description.write().unwrap() = content;
+            }
+        });
+
+        let client = self.habr_client.clone();
+        let current_alias = self.current_alias.clone();
+        let authors = self.authors.clone();
+        let alias = hub.alias.clone();
+        handle.spawn(async move {
+            let result = client
+                .get_hub_authors(&alias)
+                .await
+                .map(|mut users| {
+                    users.truncate(TOP_ENTRIES_COUNT);
+                    users
+                })
+                .map_err(|e| e.to_string());
+            if *current_alias.read().unwrap() == alias {
+                *authors.write().unwrap() = Some(result);
+            }
+        });
+
+        let client = self.habr_client.clone();
+        let current_alias = self.current_alias.clone();
+        let companies = self.companies.clone();
+        let alias = hub.alias.clone();
+        handle.spawn(async move {
+            let result = client
+                .get_hub_companies(&alias)
+                .await
+                .map(|mut companies| {
+                    companies.truncate(TOP_ENTRIES_COUNT);
+                    companies
+                })
+                .map_err(|e| e.to_string());
+            if *current_alias.read().unwrap() == alias {
+                *companies.write().unwrap() = Some(result);
+            }
+        });
+
+        self.hub = Some(hub);
+    }
+
+    fn header_ui(&mut self, ui: &mut Ui, hub: &Hub, view_stack: &mut ViewStack) {
+        ui.horizontal(|ui| {
+            let img_size = egui::Vec2::splat(ui.available_width() / 5.);
+            ui.add(
+                Image::new("https:".to_string() + hub.image_url.as_str())
+                    .fit_to_exact_size(img_size),
+            );
+            ui.vertical(|ui| {
+                ui.add(Label::new(RichText::new(hub.title.as_str()).size(40.).strong()).wrap());
+                ui.label(
+                    RichText::new(format!(
+                        "Подписчики: {} · Рейтинг: {:.1}",
+                        hub.statistics.subscribers_count, hub.statistics.rating
+                    ))
+                    .size(25.),
+                );
+            });
+        });
+
+        ui.add_space(10.);
+        ui.horizontal(|ui| {
+            if ui
+                .add(Button::new(RichText::new("Статьи хаба").size(32.)).corner_radius(5.))
+                .clicked()
+            {
+                match self.articles_selected_cb.as_mut() {
+                    Some(cb) => cb(hub, view_stack),
+                    // Hubs opened from links have no shared list to show.
+                    None => {
+                        self.habre_state.borrow_mut().selected_hub = Some(hub.clone());
+                        let mut articles_list = ArticlesList::new(self.habre_state.clone());
+                        articles_list.open_hub();
+                        view_stack.push(Rc::new(RefCell::new(articles_list)));
+                    }
+                }
+            }
+
+            let state = self.habre_state.borrow();
+            let is_subscribed = state.subscriptions.borrow().is_subscribed(&hub.alias);
+            let subscription_text = if is_subscribed {
+                "Отписаться"
+            } else {
+                "Подписаться"
+            };
+            if ui
+                .add(Button::new(RichText::new(subscription_text).size(32.)).corner_radius(5.))
+                .clicked()
+            {
+                let mut subscriptions = state.subscriptions.borrow_mut();
+                subscriptions.toggle(hub);
+                subscriptions.save();
+            }
+        });
+    }
+
+    fn tags_ui(&mut self, ui: &mut Ui, hub: &Hub, view_stack: &mut ViewStack) {
+        if hub.common_tags.is_empty() {
+            return;
+        }
+        ui.add_space(10.);
+        ui.label(RichText::new("Теги").size(29.).strong());
+
+        let mut selected_tag = None;
+        ui.horizontal_wrapped(|ui| {
+            for tag in hub.common_tags.iter() {
+                if ui.button(RichText::new(tag.as_str()).size(25.)).clicked() {
+                    selected_tag = Some(tag.as_str());
+                }
+            }
+        });
+
+        if let Some(tag) = selected_tag {
+            ArticlesList::push_tag_feed(self.habre_state.clone(), tag, view_stack);
+        }
+    }
+}
+
+fn loaded_list_ui<T>(
+    ui: &mut Ui,
+    title: &str,
+    list: &LoadedList<T>,
+    mut item_ui: impl FnMut(&mut Ui, &T),
+) {
+    ui.add_space(10.);
+    ui.label(RichText::new(title).size(29.).strong());
+    let list = list.read().unwrap();
+    let Some(items) = loaded_ui(ui, &list, "Не удалось загрузить") else {
+        return;
+    };
+    if items.is_empty() {
+        ui.label(RichText::new("Нет данных").size(22.).weak());
+    }
+    for item in items {
+        item_ui(ui, item);
+    }
+}
+
+impl UiView for HubDetails {
+    fn ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let fetched_hub = self.fetched_hub.write().unwrap().take();
+        match fetched_hub {
+            Some(Ok(hub)) => self.set_hub(hub),
+            Some(Err(e)) => self.load_error = Some(e),
+            None => {}
+        }
+
+        let Some(hub) = self.hub.clone() else {
+            match self.load_error.as_ref() {
+                Some(e) => {
+                    ui.label(
+                        RichText::new(format!("Не удалось загрузить хаб: {}", e))
+                            .size(25.)
+                            .color(ui.visuals().error_fg_color),
+                    );
+                }
+                None => {
+                    ui.add(Spinner::new().size(100.));
+                }
+            }
+            return;
+        };
+
+        let mut scroll_area = ScrollArea::vertical()
+            .max_height(ui.available_height())
+            .scroll_source(if self.renderer.is_viewing_image() {
+                egui::scroll_area::ScrollSource::NONE
+            } else {
+                egui::scroll_area::ScrollSource::ALL
+            });
+        if self.reset_scroll {
+            scroll_area = scroll_area.vertical_scroll_offset(0.);
+            self.reset_scroll = false;
+        }
+
+        scroll_area.show(ui, |ui| {
+            self.header_ui(ui, &hub, view_stack);
+
+            ui.add_space(10.);
+            self.renderer
+                .content_ui(ui, &self.description.read().unwrap());
+
+            self.tags_ui(ui, &hub, view_stack);
+
+            let mut selected_author = None;
+            loaded_list_ui(ui, "Авторы", &self.authors, |ui, user| {
+                let author_label =
+                    Label::new(RichText::new(user.alias.as_str()).size(25.).strong())
+                        .selectable(false)
+                        .sense(Sense::click())
+                        .ui(ui);
+                if author_label.clicked() {
+                    selected_author = Some(user.alias.clone());
+                }
+                if let Some(speciality) = user.speciality.as_ref() {
+                    ui.label(RichText::new(speciality.as_str()).size(22.).weak());
+                }
+            });
+
+            let mut selected_company = None;
+            loaded_list_ui(ui, "Компании", &self.companies, |ui, company| {
+                let company_label =
+                    Label::new(RichText::new(company.title.as_str()).size(25.).strong())
+                        .selectable(false)
+                        .sense(Sense::click())
+                        .ui(ui);
+                if company_label.clicked() {
+                    selected_company = Some(company.alias.clone());
+                }
+            });
+
+            if let Some(alias) = selected_author {
+                let mut author_profile = AuthorProfile::new(self.habre_state.clone());
+                author_profile.load(&alias);
+                view_stack.push(Rc::new(RefCell::new(author_profile)));
+            }
+
+            if let Some(alias) = selected_company {
+                let mut company_details = CompanyDetails::new(self.habre_state.clone());
+                company_details.load(&alias);
+                view_stack.push(Rc::new(RefCell::new(company_details)));
+            }
+        });
+
+        self.renderer.image_viewer_ui(ui);
+        if let Some(url) = self.renderer.take_clicked_link() {
+            open_link(ui, self.habre_state.clone(), &url, view_stack);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     cell::RefCell,
+    collections::HashMap,
     rc::Rc,
     sync::{
         Arc, RwLock,
@@ -14,18 +15,29 @@ use egui::{
 use egui_flex::Flex;
 
 use crate::view_stack::{UiView, ViewStack};
+use crate::views::content_filter::ContentFilterView;
+use crate::views::reading_history::ReadingHistoryView;
 use crate::widgets::{Pager, context_menu_button};
 use crate::{
     app::HabreState,
-    habr_client::hub::{Hub, get_hubs},
+    habr_client::{
+        HabrClient,
+        hub::{Hub, get_hubs},
+    },
+    storage::feeds::Feed,
 };
 
-// static BOOKMARK_ICON: &[u8] = include_bytes!("../assets/bookmark.png");
+static BOOKMARK_ICON: &[u8] = include_bytes!("../../assets/bookmark.png");
 
 pub struct HubsList {
     pub is_loading: Arc<AtomicBool>,
     hub_selected_cb: Option<Box<dyn FnMut(&Hub, &mut ViewStack)>>,
     saved_articles_selected_cb: Option<Box<dyn FnMut(&mut ViewStack)>>,
+    search_selected_cb: Option<Box<dyn FnMut(&mut ViewStack)>>,
+    companies_selected_cb: Option<Box<dyn FnMut(&mut ViewStack)>>,
+    my_feed_selected_cb: Option<Box<dyn FnMut(&mut ViewStack)>>,
+    feed_selected_cb: Option<Box<dyn FnMut(&Feed, &mut ViewStack)>>,
+    feeds_unseen: Arc<RwLock<HashMap<String, usize>>>,
 
     search_text: String,
     search_was_changed: bool,
@@ -47,6 +59,11 @@ impl HubsList {
             search_was_changed: false,
             hub_selected_cb: None,
             saved_articles_selected_cb: None,
+            search_selected_cb: None,
+            companies_selected_cb: None,
+            my_feed_selected_cb: None,
+            feed_selected_cb: None,
+            feeds_unseen: Default::default(),
 
             is_loading: Arc::new(AtomicBool::new(true)),
             reset_scroll_area: false,
@@ -71,6 +88,210 @@ impl HubsList {
         self.saved_articles_selected_cb = Some(Box::new(callback));
     }
 
+    pub fn on_search_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ViewStack) + 'static,
+    {
+        self.search_selected_cb = Some(Box::new(callback));
+    }
+
+    pub fn on_companies_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ViewStack) + 'static,
+    {
+        self.companies_selected_cb = Some(Box::new(callback));
+    }
+
+    pub fn on_my_feed_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ViewStack) + 'static,
+    {
+        self.my_feed_selected_cb = Some(Box::new(callback));
+    }
+
+    pub fn on_feed_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Feed, &mut ViewStack) + 'static,
+    {
+        self.feed_selected_cb = Some(Box::new(callback));
+    }
+
+    /// Counts articles of the first page of every feed which weren't there the last time
+    /// the feed was opened.
+    pub fn refresh_feeds(&self) {
+        let feeds = self.habre_state.borrow().feeds.borrow().feeds.clone();
+        let feeds_unseen = self.feeds_unseen.clone();
+
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let client = HabrClient::new();
+            for feed in feeds {
+                match client.search_articles(&feed.query, 1).await {
+                    Ok((articles, _)) => {
+                        let ids: Vec<String> = articles.into_iter().map(|a| a.id).collect();
+                        feeds_unseen
+                            .write()
+                            .unwrap()
+                            .insert(feed.id.clone(), feed.unseen_count(&ids));
+                    }
+                    Err(e) => log::warn!("Failed to refresh feed {}: {}", feed.name, e),
+                }
+            }
+        });
+    }
+
+    fn select_hub(&mut self, hub: &Hub, view_stack: &mut ViewStack) {
+        self.habre_state.borrow_mut().selected_hub = Some(hub.clone());
+        if let Some(cb) = self.hub_selected_cb.as_mut() {
+            cb(hub, view_stack);
+        }
+    }
+
+    fn toggle_subscription(&self, hub: &Hub) {
+        let state = self.habre_state.borrow();
+        let mut subscriptions = state.subscriptions.borrow_mut();
+        subscriptions.toggle(hub);
+        subscriptions.save();
+    }
+
+    fn subscriptions_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let hubs = self
+            .habre_state
+            .borrow()
+            .subscriptions
+            .borrow()
+            .hubs
+            .clone();
+        if hubs.is_empty() {
+            return;
+        }
+
+        ui.label(RichText::new("Подписки").size(29.).strong());
+        let my_feed = ui
+            .add(egui::Button::new(RichText::new("⭐ Моя лента").size(32.).strong()).frame(false));
+        if my_feed.clicked()
+            && let Some(cb) = self.my_feed_selected_cb.as_mut()
+        {
+            cb(view_stack);
+        }
+
+        let mut selected_hub = None;
+        let mut toggled_hub = None;
+        for hub in hubs.iter() {
+            ui.separator();
+            let (response, toggled) = HubUI::ui(ui, hub, true);
+            if toggled {
+                toggled_hub = Some(hub);
+            } else if response.clicked() {
+                selected_hub = Some(hub);
+            }
+        }
+
+        if let Some(e) = self
+            .habre_state
+            .borrow()
+            .subscriptions
+            .borrow()
+            .save_error
+            .as_ref()
+        {
+            ui.label(
+                RichText::new(format!("Не удалось сохранить подписки: {}", e))
+                    .size(22.)
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+        ui.separator();
+
+        if let Some(hub) = toggled_hub {
+            self.toggle_subscription(hub);
+        }
+        if let Some(hub) = selected_hub {
+            self.select_hub(hub, view_stack);
+        }
+    }
+
+    fn feeds_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let feeds = self.habre_state.borrow().feeds.borrow().feeds.clone();
+        if feeds.is_empty() {
+            return;
+        }
+
+        let mut selected_feed = None;
+        let mut removed_feed = None;
+
+        ui.label(RichText::new("Ленты").size(29.).strong());
+        for feed in feeds.iter() {
+            let unseen = self
+                .feeds_unseen
+                .read()
+                .unwrap()
+                .get(&feed.id)
+                .copied()
+                .unwrap_or(0);
+            ui.horizontal(|ui| {
+                let response = ui
+                    .scope_builder(
+                        UiBuilder::new().id_salt(&feed.id).sense(Sense::click()),
+                        |ui| {
+                            ui.horizontal(|ui| {
+                                Label::new(
+                                    RichText::new(format!("📌 {}", feed.name))
+                                        .size(32.)
+                                        .strong(),
+                                )
+                                .selectable(false)
+                                .ui(ui);
+                                if unseen > 0 {
+                                    Label::new(
+                                        RichText::new(format!("+{}", unseen))
+                                            .size(25.)
+                                            .color(ui.visuals().hyperlink_color),
+                                    )
+                                    .selectable(false)
+                                    .ui(ui);
+                                }
+                            });
+                        },
+                    )
+                    .response;
+                if response.clicked() {
+                    selected_feed = Some(feed.clone());
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(RichText::new("🗑").size(25.)).clicked() {
+                        removed_feed = Some(feed.id.clone());
+                    }
+                });
+            });
+        }
+
+        if let Some(e) = self.habre_state.borrow().feeds.borrow().save_error.as_ref() {
+            ui.label(
+                RichText::new(format!("Не удалось сохранить ленты: {}", e))
+                    .size(22.)
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+        ui.separator();
+
+        if let Some(feed_id) = removed_feed {
+            let state = self.habre_state.borrow();
+            let mut feeds = state.feeds.borrow_mut();
+            feeds.remove(&feed_id);
+            feeds.save();
+        }
+
+        if let Some(feed) = selected_feed {
+            self.feeds_unseen
+                .write()
+                .unwrap()
+                .insert(feed.id.clone(), 0);
+            if let Some(cb) = self.feed_selected_cb.as_mut() {
+                cb(&feed, view_stack);
+            }
+        }
+    }
+
     fn search_ui(&mut self, ui: &mut Ui) {
         let search_edit = TextEdit::singleline(&mut self.search_text)
             .desired_width(f32::INFINITY)
@@ -162,6 +383,10 @@ impl UiView for HubsList {
                             );
                             let mut open_settings = false;
                             let mut open_saved = false;
+                            let mut open_history = false;
+                            let mut open_search = false;
+                            let mut open_content_filter = false;
+                            let mut open_companies = false;
                             f_ui.add_ui(egui_flex::item(), |ui| {
                                 context_menu_button(ui, |ui| {
                                     ui.spacing_mut().button_padding = [25., 15.].into();
@@ -175,16 +400,59 @@ impl UiView for HubsList {
                                     {
                                         open_saved = true;
                                     }
+                                    if ui
+                                        .button(RichText::new("История чтения").size(29.))
+                                        .clicked()
+                                    {
+                                        open_history = true;
+                                    }
+                                    if ui
+                                        .button(RichText::new("Расширенный поиск").size(29.))
+                                        .clicked()
+                                    {
+                                        open_search = true;
+                                    }
+                                    if ui
+                                        .button(RichText::new("Блоги компаний").size(29.))
+                                        .clicked()
+                                    {
+                                        open_companies = true;
+                                    }
+                                    if ui
+                                        .button(RichText::new("Фильтр контента").size(29.))
+                                        .clicked()
+                                    {
+                                        open_content_filter = true;
+                                    }
                                 });
                             });
                             if open_settings {
                                 view_stack.push(self.habre_state.borrow().settings.clone());
                             }
+                            if open_companies && let Some(cb) = self.companies_selected_cb.as_mut()
+                            {
+                                cb(view_stack);
+                            }
+                            if open_content_filter {
+                                view_stack.push(Rc::new(RefCell::new(ContentFilterView::new(
+                                    self.habre_state.clone(),
+                                ))));
+                            }
+                            if open_history {
+                                view_stack.push(Rc::new(RefCell::new(ReadingHistoryView::new(
+                                    self.habre_state.clone(),
+                                ))));
+                            }
                             if open_saved {
                                 if let Some(cb) = self.saved_articles_selected_cb.as_mut() {
                                     cb(view_stack);
                                 }
                             }
+                            if open_search {
+                                if let Some(cb) = self.search_selected_cb.as_mut() {
+                                    cb(view_stack);
+                                }
+                            }
                         },
                     );
 
@@ -215,22 +483,38 @@ impl UiView for HubsList {
                         }
 
                         scroll_area.show(ui, |ui| {
+                            if self.search_text.is_empty() && self.current_page == 1 {
+                                self.subscriptions_ui(ui, view_stack);
+                                self.feeds_ui(ui, view_stack);
+                            }
+
+                            let mut selected_hub = None;
+                            let mut toggled_hub = None;
                             for (index, hub) in self.hubs.read().unwrap().iter().enumerate() {
                                 if index > 0 {
                                     ui.separator();
                                 }
 
-                                if HubUI::ui(ui, hub).clicked() {
-                                    {
-                                        let mut state = self.habre_state.borrow_mut();
-                                        state.selected_hub = Some(hub.clone());
-                                    }
-
-                                    self.hub_selected_cb.as_mut().map(|cb| {
-                                        cb(hub, view_stack);
-                                    });
+                                let is_subscribed = self
+                                    .habre_state
+                                    .borrow()
+                                    .subscriptions
+                                    .borrow()
+                                    .is_subscribed(&hub.alias);
+                                let (response, toggled) = HubUI::ui(ui, hub, is_subscribed);
+                                if toggled {
+                                    toggled_hub = Some(hub.clone());
+                                } else if response.clicked() {
+                                    selected_hub = Some(hub.clone());
                                 }
                             }
+
+                            if let Some(hub) = toggled_hub {
+                                self.toggle_subscription(&hub);
+                            }
+                            if let Some(hub) = selected_hub {
+                                self.select_hub(&hub, view_stack);
+                            }
                         })
                     });
                 }
@@ -257,44 +541,71 @@ impl UiView for HubsList {
 pub struct HubUI;
 
 impl HubUI {
-    pub fn ui(ui: &mut Ui, hub: &Hub) -> Response {
+    /// Returns the response of the hub card and whether the subscription button was clicked.
+    pub fn ui(ui: &mut Ui, hub: &Hub, is_subscribed: bool) -> (Response, bool) {
         let img_size = egui::Vec2::splat(ui.available_width() / 5.);
-        ui.scope_builder(
-            UiBuilder::new().id_salt(&hub.alias).sense(Sense::click()),
-            |ui| {
-                ui.horizontal(|ui| {
-                    ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                        ui.add(
-                            Image::new("https:".to_string() + hub.image_url.as_str())
-                                .fit_to_exact_size(img_size),
-                        );
-                    });
+        let bookmark_size = 42.;
+        let mut subscription_toggled = false;
+        let response = ui
+            .scope_builder(
+                UiBuilder::new().id_salt(&hub.alias).sense(Sense::click()),
+                |ui| {
+                    ui.horizontal(|ui| {
+                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                            ui.add(
+                                Image::new("https:".to_string() + hub.image_url.as_str())
+                                    .fit_to_exact_size(img_size),
+                            );
+                        });
 
-                    ui.add_sized(
-                        egui::Vec2::new(ui.available_width() - 10., img_size.y),
-                        |ui: &mut egui::Ui| {
-                            ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
-                                ui.spacing_mut().item_spacing = egui::Vec2::splat(5.);
+                        ui.add_sized(
+                            egui::Vec2::new(ui.available_width() - bookmark_size - 20., img_size.y),
+                            |ui: &mut egui::Ui| {
+                                ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
+                                    ui.spacing_mut().item_spacing = egui::Vec2::splat(5.);
 
-                                Label::new(RichText::new(hub.title.as_str()).size(32.).strong())
+                                    Label::new(
+                                        RichText::new(hub.title.as_str()).size(32.).strong(),
+                                    )
                                     .selectable(false)
                                     .ui(ui);
 
-                                Label::new(RichText::new(hub.description_html.as_str()).size(25.))
+                                    Label::new(
+                                        RichText::new(hub.description_html.as_str()).size(25.),
+                                    )
                                     .selectable(false)
                                     .ui(ui);
-                            })
-                            .response
-                        },
-                    );
-                    // ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    // ui.add_space(5.);
-                    //     let bookmark_icon = Image::from_bytes("bytes://bookmark", BOOKMARK_ICON).fit_to_exact_size((30., 30.).into());
-                    //     ui.add(egui::ImageButton::new(bookmark_icon))
-                    // });
-                })
-            },
-        )
-        .response
+                                })
+                                .response
+                            },
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.add_space(5.);
+                            let tint = if is_subscribed {
+                                ui.visuals().hyperlink_color
+                            } else {
+                                ui.visuals().weak_text_color()
+                            };
+                            let bookmark_icon =
+                                Image::from_bytes("bytes://bookmark", BOOKMARK_ICON)
+                                    .fit_to_exact_size(egui::Vec2::splat(bookmark_size))
+                                    .tint(tint);
+                            if ui
+                                .add(egui::Button::image(bookmark_icon).frame(false))
+                                .on_hover_text(if is_subscribed {
+                                    "Отписаться"
+                                } else {
+                                    "Подписаться"
+                                })
+                                .clicked()
+                            {
+                                subscription_toggled = true;
+                            }
+                        });
+                    })
+                },
+            )
+            .response;
+        (response, subscription_toggled)
     }
 }
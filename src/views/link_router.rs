@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use egui::Ui;
+
+use crate::app::HabreState;
+use crate::habr_client::links::{HabrLink, absolute_url};
+use crate::view_stack::ViewStack;
+use crate::views::article_details::ArticleDetails;
+use crate::views::article_renderer::open_url;
+use crate::views::author_profile::AuthorProfile;
+use crate::views::comments::Comments;
+use crate::views::company_details::CompanyDetails;
+use crate::views::hub_details::HubDetails;
+
+/// Opens habr.com links in the matching views, all the other links go to the browser.
+pub fn open_link(ui: &Ui, state: Rc<RefCell<HabreState>>, url: &str, view_stack: &mut ViewStack) {
+    let url = absolute_url(url).unwrap_or_else(|| url.to_string());
+    let Some(link) = HabrLink::parse(&url) else {
+        open_url(ui, &url);
+        return;
+    };
+
+    match link {
+        HabrLink::Article(article_id) => {
+            let mut article_details = ArticleDetails::new(state);
+            article_details.load_by_id(&article_id);
+            view_stack.push(Rc::new(RefCell::new(article_details)));
+        }
+        HabrLink::Comments {
+            article_id,
+            comment_id,
+        } => {
+            let mut comments = Comments::new(article_id, state);
+            if let Some(comment_id) = comment_id {
+                comments.show_comment(&comment_id);
+            }
+            comments.load_comments();
+            view_stack.push(Rc::new(RefCell::new(comments)));
+        }
+        HabrLink::Hub(alias) => {
+            let mut hub_details = HubDetails::new(state);
+            hub_details.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(hub_details)));
+        }
+        HabrLink::User(alias) => {
+            let mut author_profile = AuthorProfile::new(state);
+            author_profile.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(author_profile)));
+        }
+        HabrLink::Company(alias) => {
+            let mut company_details = CompanyDetails::new(state);
+            company_details.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(company_details)));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::{Arc, RwLock};
+
+use egui::{RichText, Spinner, Ui};
+
+/// Data loaded by a background task: `None` while loading, the error text if it failed.
+pub type Loaded<T> = Arc<RwLock<Option<Result<T, String>>>>;
+
+/// Shows a spinner while loading and `error_text` with the error if the loading failed.
+/// Returns the data once it's loaded.
+pub fn loaded_ui<'a, T>(
+    ui: &mut Ui,
+    loaded: &'a Option<Result<T, String>>,
+    error_text: &str,
+) -> Option<&'a T> {
+    match loaded {
+        None => {
+            ui.add(Spinner::new().size(60.));
+            None
+        }
+        Some(Err(e)) => {
+            ui.label(
+                RichText::new(format!("{}: {}", error_text, e))
+                    .size(25.)
+                    .color(ui.visuals().error_fg_color),
+            );
+            None
+        }
+        Some(Ok(data)) => Some(data),
+    }
+}
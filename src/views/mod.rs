@@ -1,6 +1,18 @@
 pub mod article_details;
+pub mod article_notes;
+pub mod article_renderer;
 pub mod articles_list;
+pub mod author_profile;
+pub mod collections_popup;
 pub mod comments;
+pub mod companies_list;
+pub mod company_details;
+pub mod content_filter;
+pub mod hub_details;
 pub mod hubs_list;
+pub mod link_router;
+pub mod loaded;
+pub mod reading_history;
 pub mod saved_articles_list;
+pub mod search;
 pub mod settings;
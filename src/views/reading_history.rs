@@ -0,0 +1,194 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use egui::{self, Frame, Label, Layout, RichText, ScrollArea, Sense, Ui, UiBuilder, Widget};
+use egui_flex::Flex;
+
+use crate::app::HabreState;
+use crate::storage::ArticleStorage;
+use crate::storage::history::HistoryEntry;
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::article_details::ArticleDetails;
+use crate::widgets::context_menu_button;
+
+enum HistoryAction {
+    Open(usize),
+    Remove(usize),
+}
+
+pub struct ReadingHistoryView {
+    habre_state: Rc<RefCell<HabreState>>,
+}
+
+impl ReadingHistoryView {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self { habre_state }
+    }
+
+    fn open_article(&self, entry: &HistoryEntry, view_stack: &mut ViewStack) {
+        let article_id = entry.metadata.id.clone();
+        self.habre_state.borrow_mut().selected_article = Some(entry.metadata.clone());
+        let mut article_details = ArticleDetails::new(self.habre_state.clone());
+        if ArticleStorage::is_article_saved(&article_id) {
+            article_details.load_saved(&article_id);
+        } else {
+            article_details.load_data();
+        }
+        view_stack.push(Rc::new(RefCell::new(article_details)));
+    }
+}
+
+impl UiView for ReadingHistoryView {
+    fn ui(&mut self, ui: &mut egui::Ui, view_stack: &mut ViewStack) {
+        let history = self.habre_state.borrow().history.clone();
+
+        Flex::vertical()
+            .align_items(egui_flex::FlexAlign::Start)
+            .justify(egui_flex::FlexJustify::Start)
+            .grow_items(0.)
+            .h_full()
+            .w_full()
+            .show(ui, |f_ui| {
+                f_ui.add_flex(
+                    egui_flex::item(),
+                    Flex::horizontal()
+                        .w_full()
+                        .justify(egui_flex::FlexJustify::SpaceBetween),
+                    |f_ui| {
+                        f_ui.add(
+                            egui_flex::item(),
+                            Label::new(RichText::new("История чтения").size(40.).strong()),
+                        );
+                        let mut clear = false;
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            context_menu_button(ui, |ui| {
+                                ui.spacing_mut().button_padding = [25., 15.].into();
+                                if ui
+                                    .button(RichText::new("Очистить историю").size(29.))
+                                    .clicked()
+                                {
+                                    clear = true;
+                                }
+                            });
+                        });
+                        if clear {
+                            let mut history = history.borrow_mut();
+                            history.clear();
+                            history.save();
+                        }
+                    },
+                );
+
+                let save_error = history.borrow().save_error.clone();
+                if let Some(error) = save_error {
+                    f_ui.add_ui(egui_flex::item(), |ui| {
+                        Label::new(
+                            RichText::new(format!("Не удалось сохранить историю: {}", error))
+                                .size(22.)
+                                .color(ui.visuals().error_fg_color),
+                        )
+                        .wrap()
+                        .ui(ui);
+                    });
+                }
+
+                f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
+
+                f_ui.add_ui(egui_flex::item().shrink(), |ui| {
+                    if history.borrow().entries.is_empty() {
+                        ui.centered_and_justified(|ui| {
+                            ui.label(RichText::new("История пуста").size(29.));
+                        });
+                        return;
+                    }
+
+                    let mut action = None;
+                    ScrollArea::vertical()
+                        .max_width(ui.available_width())
+                        .hscroll(false)
+                        .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysHidden)
+                        .show(ui, |ui| {
+                            for (index, entry) in history.borrow().entries.iter().enumerate() {
+                                let response = HistoryItem::ui(ui, entry);
+                                if response.clicked() {
+                                    action = Some(HistoryAction::Open(index));
+                                }
+                                response.context_menu(|ui| {
+                                    ui.spacing_mut().button_padding = [25., 15.].into();
+                                    if ui
+                                        .button(RichText::new("Удалить из истории").size(29.))
+                                        .clicked()
+                                    {
+                                        action = Some(HistoryAction::Remove(index));
+                                    }
+                                });
+                            }
+                        });
+
+                    match action {
+                        Some(HistoryAction::Open(index)) => {
+                            let entry = history.borrow().entries[index].clone();
+                            self.open_article(&entry, view_stack);
+                        }
+                        Some(HistoryAction::Remove(index)) => {
+                            let mut history = history.borrow_mut();
+                            let article_id = history.entries[index].metadata.id.clone();
+                            history.remove(&article_id);
+                            history.save();
+                        }
+                        None => {}
+                    }
+                });
+            });
+    }
+}
+
+struct HistoryItem;
+
+impl HistoryItem {
+    fn ui(ui: &mut Ui, entry: &HistoryEntry) -> egui::Response {
+        let article = &entry.metadata;
+        let frame = Frame::NONE
+            .corner_radius(5.)
+            .fill(ui.ctx().theme().default_visuals().extreme_bg_color)
+            .inner_margin(10.);
+
+        ui.scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
+            ui.set_max_width(ui.available_width());
+            frame.show(ui, |ui| {
+                ui.with_layout(Layout::top_down_justified(egui::Align::TOP), |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.spacing_mut().item_spacing = egui::Vec2::new(0., 5.);
+
+                    Label::new(
+                        RichText::new(article.author.as_str())
+                            .strong()
+                            .size(25.)
+                            .color(ui.ctx().theme().default_visuals().hyperlink_color),
+                    )
+                    .selectable(false)
+                    .ui(ui);
+
+                    Label::new(RichText::new(article.title.as_str()).size(32.).strong())
+                        .wrap()
+                        .selectable(false)
+                        .ui(ui);
+
+                    let progress = if entry.finished {
+                        "✓ Прочитано".to_string()
+                    } else {
+                        format!("📖 {}%", (entry.progress * 100.).round())
+                    };
+                    Label::new(
+                        RichText::new(format!("Открыто {} · {}", entry.opened_at_str(), progress))
+                            .size(22.)
+                            .weak(),
+                    )
+                    .selectable(false)
+                    .ui(ui);
+                });
+            });
+        })
+        .response
+    }
+}
@@ -1,9 +1,12 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
+use egui::text::LayoutJob;
 use egui::{
-    self, Button, Frame, Image, Label, Layout, RichText, ScrollArea, Sense, Ui, UiBuilder, Widget,
+    self, Button, FontSelection, Frame, Image, Label, Layout, ProgressBar, RichText, ScrollArea,
+    Sense, Spinner, TextEdit, Ui, UiBuilder, Widget,
 };
 
 static TRASH_ICON: &[u8] = include_bytes!("../../assets/trash.png");
@@ -11,14 +14,68 @@ use egui_flex::Flex;
 
 use crate::app::HabreState;
 use crate::habr_client::article::ArticleData;
-use crate::storage::ArticleStorage;
+use crate::storage::collections::{ArticleLabels, LabelFilter};
+use crate::storage::export::{ExportFormat, export_article};
+use crate::storage::fulltext::{FullTextIndex, Snippet};
+use crate::storage::{ArticleStorage, BrokenSavedArticle, SavedArticleEntry};
 use crate::view_stack::{UiView, ViewStack};
+use crate::widgets::context_menu_button;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SavedArticlesSorting {
+    SavedAt,
+    Title,
+    Author,
+    ReadingTime,
+}
+
+impl SavedArticlesSorting {
+    fn sort(&self, articles: &mut [SavedArticleEntry]) {
+        match self {
+            SavedArticlesSorting::SavedAt => articles.sort_by(|a, b| b.saved_at.cmp(&a.saved_at)),
+            SavedArticlesSorting::Title => articles.sort_by(|a, b| {
+                a.metadata
+                    .title
+                    .to_lowercase()
+                    .cmp(&b.metadata.title.to_lowercase())
+            }),
+            SavedArticlesSorting::Author => articles.sort_by(|a, b| {
+                a.metadata
+                    .author
+                    .to_lowercase()
+                    .cmp(&b.metadata.author.to_lowercase())
+            }),
+            SavedArticlesSorting::ReadingTime => articles.sort_by_key(|a| a.metadata.reading_time),
+        }
+    }
+}
+
+const MAX_SEARCH_RESULTS: usize = 50;
+/// Pause in typing after which the search starts.
+const SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+/// Full-text search over the saved articles, done in the background.
+#[derive(Default)]
+struct SavedSearch {
+    query: String,
+    /// Loaded by the first search after the list is refreshed.
+    index: Option<Arc<FullTextIndex>>,
+    /// Ids of the found articles with their text around the match, None while searching.
+    results: Option<Vec<(String, Option<Snippet>)>>,
+}
 
 pub struct SavedArticlesList {
     habre_state: Rc<RefCell<HabreState>>,
-    articles: Arc<RwLock<Vec<ArticleData>>>,
+    articles: Arc<RwLock<Vec<SavedArticleEntry>>>,
+    broken_articles: Vec<BrokenSavedArticle>,
     article_selected_cb: Option<Box<dyn FnMut(ArticleData, &mut ViewStack)>>,
     need_refresh: bool,
+    sorting: SavedArticlesSorting,
+    filter: LabelFilter,
+    search_text: String,
+    search_edited_at: Option<Instant>,
+    search: Arc<RwLock<SavedSearch>>,
+    status: Arc<RwLock<Option<String>>>,
 }
 
 impl SavedArticlesList {
@@ -26,8 +83,15 @@ impl SavedArticlesList {
         Self {
             habre_state,
             articles: Arc::new(RwLock::new(Vec::new())),
+            broken_articles: Vec::new(),
             article_selected_cb: None,
             need_refresh: true,
+            sorting: SavedArticlesSorting::SavedAt,
+            filter: LabelFilter::All,
+            search_text: String::new(),
+            search_edited_at: None,
+            search: Default::default(),
+            status: Default::default(),
         }
     }
 
@@ -43,11 +107,180 @@ impl SavedArticlesList {
     }
 
     fn load_articles(&mut self) {
-        let articles = ArticleStorage::list_saved_articles();
+        let mut articles = ArticleStorage::list_saved_articles();
+        self.sorting.sort(&mut articles);
         if let Ok(mut current) = self.articles.write() {
             *current = articles;
         }
+        self.broken_articles = ArticleStorage::list_broken_articles();
         self.need_refresh = false;
+
+        // Articles could be saved or deleted, the index is synced on the next search.
+        self.search.write().unwrap().index = None;
+        if !self.search_text.trim().is_empty() {
+            self.search();
+        }
+    }
+
+    fn search(&self) {
+        let query = self.search_text.trim().to_string();
+        {
+            let mut search = self.search.write().unwrap();
+            search.query = query.clone();
+            search.results = None;
+        }
+        if query.is_empty() {
+            return;
+        }
+
+        let search = self.search.clone();
+        self.habre_state
+            .borrow()
+            .async_handle()
+            .spawn_blocking(move || {
+                let index = search.read().unwrap().index.clone();
+                let index = index.unwrap_or_else(|| {
+                    let index = Arc::new(ArticleStorage::fulltext_index());
+                    search.write().unwrap().index = Some(index.clone());
+                    index
+                });
+
+                let results = index
+                    .search(&query)
+                    .into_iter()
+                    .take(MAX_SEARCH_RESULTS)
+                    .map(|found| {
+                        let snippet = index.snippet(&found.article_id, &query);
+                        (found.article_id, snippet)
+                    })
+                    .collect();
+
+                // The query could be changed while searching.
+                let mut search = search.write().unwrap();
+                if search.query == query {
+                    search.results = Some(results);
+                }
+            });
+    }
+
+    fn search_ui(&mut self, ui: &mut Ui) {
+        let search_edit = TextEdit::singleline(&mut self.search_text)
+            .desired_width(f32::INFINITY)
+            .font(egui::epaint::text::FontId::proportional(32.))
+            .hint_text(RichText::new("Поиск по сохранённым").size(32.))
+            .show(ui)
+            .response;
+
+        if search_edit.changed() {
+            self.search_edited_at = Some(Instant::now());
+        }
+        if let Some(edited_at) = self.search_edited_at {
+            let elapsed = edited_at.elapsed();
+            if elapsed >= SEARCH_DELAY {
+                self.search_edited_at = None;
+                self.search();
+            } else {
+                ui.ctx().request_repaint_after(SEARCH_DELAY - elapsed);
+            }
+        }
+    }
+
+    fn collect_image_garbage(&self) {
+        let status = self.status.clone();
+        *status.write().unwrap() = Some("Очистка изображений...".to_string());
+
+        self.habre_state
+            .borrow()
+            .async_handle()
+            .spawn_blocking(move || {
+                let message = match ArticleStorage::collect_image_garbage() {
+                    Ok(stats) => format!(
+                        "Удалено изображений: {} ({})",
+                        stats.removed_files,
+                        format_size(stats.freed_bytes)
+                    ),
+                    Err(e) => format!("Не удалось очистить изображения: {}", e),
+                };
+                *status.write().unwrap() = Some(message);
+            });
+    }
+
+    fn export_article(&self, article_id: &str, format: ExportFormat) {
+        let status = self.status.clone();
+        *status.write().unwrap() = Some(format!("Экспорт в {}...", format.name()));
+
+        let article_id = article_id.to_string();
+        self.habre_state
+            .borrow()
+            .async_handle()
+            .spawn_blocking(move || {
+                let message = match ArticleStorage::load_article(&article_id)
+                    .and_then(|(data, content)| export_article(&data, &content, format))
+                {
+                    Ok(path) => format!("Статья сохранена в {}", path.to_string_lossy()),
+                    Err(e) => format!("Не удалось экспортировать статью: {}", e),
+                };
+                *status.write().unwrap() = Some(message);
+            });
+    }
+
+    fn sorting_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            let current_sorting = self.sorting;
+            for (sorting, label) in [
+                (SavedArticlesSorting::SavedAt, "По дате сохранения"),
+                (SavedArticlesSorting::Title, "По названию"),
+                (SavedArticlesSorting::Author, "По автору"),
+                (SavedArticlesSorting::ReadingTime, "По времени чтения"),
+            ] {
+                ui.selectable_value(&mut self.sorting, sorting, RichText::new(label).size(25.));
+            }
+            if self.sorting != current_sorting {
+                if let Ok(mut articles) = self.articles.write() {
+                    self.sorting.sort(&mut articles);
+                }
+            }
+        });
+    }
+
+    fn filter_ui(&mut self, ui: &mut Ui) {
+        let collections = self.habre_state.borrow().collections.clone();
+        let collections = collections.borrow();
+        let mut tags = std::collections::BTreeSet::new();
+        for entry in self.articles.read().unwrap().iter() {
+            tags.extend(collections.labels(&entry.metadata.id).tags);
+        }
+
+        let filter_exists = match &self.filter {
+            LabelFilter::All => true,
+            LabelFilter::Collection(name) => collections.collections.contains(name),
+            LabelFilter::Tag(tag) => tags.contains(tag),
+        };
+        if !filter_exists {
+            self.filter = LabelFilter::All;
+        }
+        if collections.collections.is_empty() && tags.is_empty() {
+            return;
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(
+                &mut self.filter,
+                LabelFilter::All,
+                RichText::new("Все").size(25.),
+            );
+            for name in collections.collections.iter() {
+                ui.selectable_value(
+                    &mut self.filter,
+                    LabelFilter::Collection(name.clone()),
+                    RichText::new(format!("📁 {}", name)).size(25.),
+                );
+            }
+            for tag in tags {
+                let text = RichText::new(format!("#{}", tag)).size(25.);
+                ui.selectable_value(&mut self.filter, LabelFilter::Tag(tag), text);
+            }
+        });
     }
 }
 
@@ -64,19 +297,84 @@ impl UiView for SavedArticlesList {
             .h_full()
             .w_full()
             .show(ui, |f_ui| {
-                f_ui.add_ui(egui_flex::item(), |ui| {
-                    ui.add(Label::new(
-                        RichText::new("Сохранённые статьи").size(40.).strong(),
-                    ))
-                });
+                f_ui.add_flex(
+                    egui_flex::item(),
+                    Flex::horizontal()
+                        .w_full()
+                        .justify(egui_flex::FlexJustify::SpaceBetween),
+                    |f_ui| {
+                        f_ui.add(
+                            egui_flex::item(),
+                            Label::new(RichText::new("Сохранённые статьи").size(40.).strong()),
+                        );
+                        let mut collect_garbage = false;
+                        let mut remove_collection = None;
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            context_menu_button(ui, |ui| {
+                                ui.spacing_mut().button_padding = [25., 15.].into();
+                                if ui
+                                    .button(
+                                        RichText::new("Удалить неиспользуемые изображения")
+                                            .size(29.),
+                                    )
+                                    .clicked()
+                                {
+                                    collect_garbage = true;
+                                }
+                                if let LabelFilter::Collection(name) = &self.filter
+                                    && ui
+                                        .button(
+                                            RichText::new(format!("Удалить коллекцию «{}»", name))
+                                                .size(29.),
+                                        )
+                                        .clicked()
+                                {
+                                    remove_collection = Some(name.clone());
+                                }
+                            });
+                        });
+                        if collect_garbage {
+                            self.collect_image_garbage();
+                        }
+                        if let Some(name) = remove_collection {
+                            let collections = self.habre_state.borrow().collections.clone();
+                            let mut collections = collections.borrow_mut();
+                            collections.remove_collection(&name);
+                            collections.save();
+                            self.filter = LabelFilter::All;
+                        }
+                    },
+                );
+
+                if let Some(status) = self.status.read().unwrap().as_ref() {
+                    f_ui.add(
+                        egui_flex::item(),
+                        Label::new(RichText::new(status).size(22.)).wrap(),
+                    );
+                }
+
+                f_ui.add_ui(egui_flex::item(), |ui| self.search_ui(ui));
+                let (query, results) = {
+                    let search = self.search.read().unwrap();
+                    (search.query.clone(), search.results.clone())
+                };
+                // Found articles are sorted by relevance.
+                if query.is_empty() {
+                    f_ui.add_ui(egui_flex::item(), |ui| self.sorting_ui(ui));
+                }
+                f_ui.add_ui(egui_flex::item(), |ui| self.filter_ui(ui));
 
                 f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
 
                 f_ui.add_ui(egui_flex::item().shrink(), |ui| {
-                    if self.articles.read().unwrap().is_empty() {
+                    if self.articles.read().unwrap().is_empty() && self.broken_articles.is_empty() {
                         ui.centered_and_justified(|ui| {
                             ui.label(RichText::new("Нет сохранённых статей").size(29.));
                         });
+                    } else if !query.is_empty() && results.is_none() {
+                        ui.centered_and_justified(|ui| {
+                            ui.add(Spinner::new().size(100.));
+                        });
                     } else {
                         ScrollArea::vertical()
                             .max_width(ui.available_width())
@@ -85,13 +383,70 @@ impl UiView for SavedArticlesList {
                                 egui::scroll_area::ScrollBarVisibility::AlwaysHidden,
                             )
                             .show(ui, |ui| {
-                                for article in self.articles.read().unwrap().iter() {
-                                    let response = SavedArticleItem::ui(ui, article, || {
-                                        if let Err(e) = ArticleStorage::delete_article(&article.id)
-                                        {
+                                for broken in self.broken_articles.iter() {
+                                    if BrokenArticleItem::ui(ui, broken) {
+                                        if let Err(e) = ArticleStorage::delete_article(&broken.id) {
                                             log::warn!("Failed to delete article: {}", e);
                                         }
                                         self.need_refresh = true;
+                                    }
+                                }
+
+                                let collections = self.habre_state.borrow().collections.clone();
+                                let articles = self.articles.read().unwrap();
+                                let shown_articles: Vec<(&SavedArticleEntry, Option<&Snippet>)> =
+                                    match results.as_ref() {
+                                        Some(results) => results
+                                            .iter()
+                                            .filter_map(|(article_id, snippet)| {
+                                                articles
+                                                    .iter()
+                                                    .find(|e| e.metadata.id == *article_id)
+                                                    .map(|entry| (entry, snippet.as_ref()))
+                                            })
+                                            .collect(),
+                                        None => {
+                                            articles.iter().map(|entry| (entry, None)).collect()
+                                        }
+                                    };
+
+                                let mut shown = 0;
+                                for (entry, snippet) in shown_articles {
+                                    let article = &entry.metadata;
+                                    if !collections.borrow().matches(&article.id, &self.filter) {
+                                        continue;
+                                    }
+                                    shown += 1;
+                                    let labels = collections.borrow().labels(&article.id);
+                                    let response =
+                                        SavedArticleItem::ui(ui, entry, &labels, snippet, || {
+                                            if let Err(e) =
+                                                ArticleStorage::delete_article(&article.id)
+                                            {
+                                                log::warn!("Failed to delete article: {}", e);
+                                            }
+                                            self.need_refresh = true;
+                                        });
+                                    response.0.context_menu(|ui| {
+                                        ui.spacing_mut().button_padding = [25., 15.].into();
+                                        if ui
+                                            .button(RichText::new("Коллекции и теги").size(29.))
+                                            .clicked()
+                                        {
+                                            self.habre_state
+                                                .borrow()
+                                                .collections_popup
+                                                .borrow_mut()
+                                                .open(&article.id, &article.title);
+                                            ui.close();
+                                        }
+                                        for format in [ExportFormat::Markdown, ExportFormat::Html] {
+                                            let text = format!("Экспорт в {}", format.name());
+                                            if ui.button(RichText::new(text).size(29.)).clicked() {
+                                                self.export_article(&article.id, format);
+                                                ui.close();
+                                            }
+                                        }
                                     });
 
                                     if response.0.clicked() && !response.1 {
@@ -100,8 +455,15 @@ impl UiView for SavedArticlesList {
                                         if let Some(cb) = self.article_selected_cb.as_mut() {
                                             cb(article.clone(), view_stack);
                                         }
+                                        // Reading progress is changed while the article is open.
+                                        self.need_refresh = true;
                                     }
                                 }
+                                if shown == 0 && !query.is_empty() {
+                                    ui.label(RichText::new("Ничего не найдено").size(29.));
+                                } else if shown == 0 && !articles.is_empty() {
+                                    ui.label(RichText::new("Нет статей в подборке").size(29.));
+                                }
                             });
                     }
                 });
@@ -109,10 +471,58 @@ impl UiView for SavedArticlesList {
     }
 }
 
+struct BrokenArticleItem;
+
+impl BrokenArticleItem {
+    fn ui(ui: &mut Ui, broken: &BrokenSavedArticle) -> bool {
+        let mut delete_clicked = false;
+        Frame::NONE
+            .corner_radius(5.)
+            .fill(ui.ctx().theme().default_visuals().extreme_bg_color)
+            .inner_margin(10.)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                        Label::new(
+                            RichText::new(format!("⚠ Статья {} не читается", broken.id))
+                                .strong()
+                                .size(25.)
+                                .color(ui.visuals().error_fg_color),
+                        )
+                        .selectable(false)
+                        .ui(ui);
+                    });
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        let icon = Image::from_bytes("bytes://trash", TRASH_ICON)
+                            .fit_to_exact_size((42., 42.).into());
+                        if ui.add(Button::image(icon).frame(false)).clicked() {
+                            delete_clicked = true;
+                        }
+                    });
+                });
+
+                Label::new(RichText::new(broken.error.as_str()).size(22.))
+                    .wrap()
+                    .selectable(true)
+                    .ui(ui);
+            });
+        delete_clicked
+    }
+}
+
 struct SavedArticleItem;
 
 impl SavedArticleItem {
-    fn ui(ui: &mut Ui, article: &ArticleData, on_delete: impl FnOnce()) -> (egui::Response, bool) {
+    fn ui(
+        ui: &mut Ui,
+        entry: &SavedArticleEntry,
+        labels: &ArticleLabels,
+        snippet: Option<&Snippet>,
+        on_delete: impl FnOnce(),
+    ) -> (egui::Response, bool) {
+        let article = &entry.metadata;
         let frame = Frame::NONE
             .corner_radius(5.)
             .fill(ui.ctx().theme().default_visuals().extreme_bg_color)
@@ -152,6 +562,17 @@ impl SavedArticleItem {
                             Label::new(RichText::new(article.published_at.as_str()).size(22.))
                                 .selectable(false)
                                 .ui(ui);
+                            Label::new(
+                                RichText::new(format!(
+                                    "Сохранено {} · {}",
+                                    entry.saved_at_str(),
+                                    format_size(entry.size)
+                                ))
+                                .size(22.)
+                                .weak(),
+                            )
+                            .selectable(false)
+                            .ui(ui);
                         });
 
                         if !article.image_url.is_empty() {
@@ -181,6 +602,13 @@ impl SavedArticleItem {
                                 .ui(ui);
                         });
 
+                        if let Some(snippet) = snippet {
+                            Label::new(snippet_text(ui, snippet))
+                                .wrap()
+                                .selectable(false)
+                                .ui(ui);
+                        }
+
                         ui.horizontal(|ui| {
                             Label::new(RichText::new(format!("★ {}", article.score)).size(29.))
                                 .selectable(false)
@@ -193,7 +621,40 @@ impl SavedArticleItem {
                             )
                             .selectable(false)
                             .ui(ui);
+
+                            if entry.read_progress > 0. {
+                                ui.add_space(15.);
+                                Label::new(
+                                    RichText::new(format!(
+                                        "📖 {}%",
+                                        (entry.read_progress * 100.).round()
+                                    ))
+                                    .size(29.),
+                                )
+                                .selectable(false)
+                                .ui(ui);
+                            }
                         });
+
+                        if !labels.collections.is_empty() || !labels.tags.is_empty() {
+                            let text = labels
+                                .collections
+                                .iter()
+                                .map(|name| format!("📁 {}", name))
+                                .chain(labels.tags.iter().map(|tag| format!("#{}", tag)))
+                                .collect::<Vec<_>>()
+                                .join(" · ");
+                            Label::new(RichText::new(text).size(22.).weak())
+                                .wrap()
+                                .selectable(false)
+                                .ui(ui);
+                        }
+
+                        if entry.read_progress > 0. {
+                            ProgressBar::new(entry.read_progress)
+                                .desired_height(6.)
+                                .ui(ui);
+                        }
                     })
                 });
             })
@@ -201,3 +662,32 @@ impl SavedArticleItem {
         (response, deleted.get())
     }
 }
+
+fn snippet_text(ui: &Ui, snippet: &Snippet) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    for part in [
+        RichText::new(snippet.before.as_str()).size(25.).weak(),
+        RichText::new(snippet.matched.as_str()).size(25.).strong(),
+        RichText::new(snippet.after.as_str()).size(25.).weak(),
+    ] {
+        part.append_to(
+            &mut job,
+            ui.style(),
+            FontSelection::Default,
+            egui::Align::Center,
+        );
+    }
+    job
+}
+
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.;
+    let bytes = bytes as f64;
+    if bytes < KB {
+        format!("{} Б", bytes)
+    } else if bytes < KB * KB {
+        format!("{:.0} КБ", bytes / KB)
+    } else {
+        format!("{:.1} МБ", bytes / (KB * KB))
+    }
+}
@@ -0,0 +1,750 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicBool, AtomicU8, Ordering},
+};
+
+use egui::{self, Button, Label, Layout, RichText, ScrollArea, Spinner, TextEdit, Ui, Widget};
+use egui_flex::Flex;
+
+use crate::app::HabreState;
+use crate::habr_client::{
+    HabrClient,
+    article::{ArticlesSearchSorting, ComplexityFilter, RATING_THRESHOLDS},
+    hub::Hub,
+    search::{FacetCounts, SearchPage, SearchQuery, SearchResults, SearchTarget, parse_date},
+    tag::TagSummary,
+};
+use crate::storage::searches::Searches;
+use crate::view_stack::{UiView, ViewStack};
+use crate::views::articles_list::{ArticleListItem, ArticlesList};
+use crate::views::author_profile::AuthorProfile;
+use crate::views::companies_list::CompanyUI;
+use crate::views::company_details::CompanyDetails;
+use crate::views::hubs_list::HubUI;
+use crate::widgets::Pager;
+
+pub struct SearchView {
+    habre_state: Rc<RefCell<HabreState>>,
+    habr_client: HabrClient,
+    hub_selected_cb: Option<Box<dyn FnMut(&Hub, &mut ViewStack)>>,
+
+    query: SearchQuery,
+    date_from: String,
+    date_to: String,
+    hub: String,
+    show_filters: bool,
+    query_error: Option<String>,
+
+    searches: Searches,
+    save_name: String,
+
+    is_loading: Arc<AtomicBool>,
+    reset_scroll: bool,
+    current_page: u8,
+    max_page: Arc<AtomicU8>,
+    last_query: Option<SearchQuery>,
+    /// Query and page of the latest request, results of the older ones are dropped.
+    requested: Arc<RwLock<Option<(SearchQuery, u8)>>>,
+    results: Arc<RwLock<Option<Result<SearchPage, String>>>>,
+    facets: Arc<RwLock<Option<FacetCounts>>>,
+    found_tags: Arc<RwLock<Vec<TagSummary>>>,
+}
+
+impl SearchView {
+    pub fn new(habre_state: Rc<RefCell<HabreState>>) -> Self {
+        Self {
+            habre_state,
+            habr_client: HabrClient::new(),
+            hub_selected_cb: None,
+
+            query: SearchQuery::new(""),
+            date_from: String::new(),
+            date_to: String::new(),
+            hub: String::new(),
+            show_filters: false,
+            query_error: None,
+
+            searches: Searches::load(),
+            save_name: String::new(),
+
+            is_loading: Arc::new(AtomicBool::new(false)),
+            reset_scroll: false,
+            current_page: 1,
+            max_page: Arc::new(AtomicU8::new(0)),
+            last_query: None,
+            requested: Default::default(),
+            results: Default::default(),
+            facets: Default::default(),
+            found_tags: Default::default(),
+        }
+    }
+
+    pub fn on_hub_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Hub, &mut ViewStack) + 'static,
+    {
+        self.hub_selected_cb = Some(Box::new(callback));
+    }
+
+    pub fn set_query(&mut self, query: SearchQuery) {
+        self.date_from = query.date_from.clone().unwrap_or_default();
+        self.date_to = query.date_to.clone().unwrap_or_default();
+        self.hub = query.hub.clone().unwrap_or_default();
+        self.query = query;
+    }
+
+    fn build_query(&self) -> Result<SearchQuery, String> {
+        let date = |text: &str| -> Result<Option<String>, String> {
+            let text = text.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            parse_date(text)
+                .map(|date| Some(date.format("%Y-%m-%d").to_string()))
+                .ok_or_else(|| format!("Неверная дата «{}», ожидается ГГГГ-ММ-ДД", text))
+        };
+        let hub = self.hub.trim();
+
+        Ok(self
+            .query
+            .clone()
+            .date_from(date(&self.date_from)?)
+            .date_to(date(&self.date_to)?)
+            .hub((!hub.is_empty()).then(|| hub.to_string())))
+    }
+
+    fn search(&mut self) {
+        let query = match self.build_query() {
+            Ok(query) => query,
+            Err(e) => {
+                self.query_error = Some(e);
+                return;
+            }
+        };
+        self.query_error = None;
+        if query.is_empty() {
+            self.last_query = None;
+            *self.requested.write().unwrap() = None;
+            self.is_loading.store(false, Ordering::Relaxed);
+            *self.results.write().unwrap() = None;
+            *self.facets.write().unwrap() = None;
+            return;
+        }
+
+        let update_facets = self
+            .last_query
+            .as_ref()
+            .is_none_or(|last| last.clone().target(query.target) != query);
+        self.current_page = 1;
+        self.searches.add_recent(query.clone());
+        self.searches.save();
+        self.last_query = Some(query);
+        self.load_page();
+
+        if update_facets {
+            self.load_facets();
+        }
+    }
+
+    fn load_page(&mut self) {
+        let Some(query) = self.last_query.clone() else {
+            return;
+        };
+        self.is_loading.store(true, Ordering::Relaxed);
+        self.reset_scroll = true;
+
+        let client = self.habr_client.clone();
+        let requested = self.requested.clone();
+        let results = self.results.clone();
+        let max_page = self.max_page.clone();
+        let is_loading = self.is_loading.clone();
+        let current_page = self.current_page;
+        *requested.write().unwrap() = Some((query.clone(), current_page));
+
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let page = client
+                .search(&query, current_page)
+                .await
+                .map_err(|e| e.to_string());
+            let requested = requested.read().unwrap();
+            if requested.as_ref() != Some(&(query, current_page)) {
+                return;
+            }
+            if let Ok(page) = page.as_ref() {
+                max_page.store(page.pages_count as u8, Ordering::Relaxed);
+            }
+            *results.write().unwrap() = Some(page);
+            is_loading.store(false, Ordering::Relaxed);
+        });
+
+        if self.current_page == 1 {
+            self.load_found_tags(&query);
+        }
+    }
+
+    fn load_found_tags(&mut self, query: &SearchQuery) {
+        self.found_tags.write().unwrap().clear();
+        if query.target != SearchTarget::Tags {
+            return;
+        }
+
+        let client = self.habr_client.clone();
+        let requested = self.requested.clone();
+        let found_tags = self.found_tags.clone();
+        let query = query.clone();
+        self.habre_state.borrow().async_handle().spawn(async move {
+            if let Ok(tags) = client.guess_tags(&query.text).await
+                && requested.read().unwrap().as_ref().map(|r| &r.0) == Some(&query)
+            {
+                *found_tags.write().unwrap() = tags;
+            }
+        });
+    }
+
+    fn load_facets(&mut self) {
+        let Some(query) = self.last_query.clone() else {
+            return;
+        };
+        let client = self.habr_client.clone();
+        let requested = self.requested.clone();
+        let facets = self.facets.clone();
+        *facets.write().unwrap() = None;
+
+        self.habre_state.borrow().async_handle().spawn(async move {
+            let counts = client.facet_counts(&query).await;
+            // Facets don't depend on the target and the page.
+            let is_current = requested
+                .read()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|(r, _)| r.clone().target(query.target) == query);
+            if is_current {
+                *facets.write().unwrap() = Some(counts);
+            }
+        });
+    }
+
+    fn search_ui(&mut self, ui: &mut Ui) {
+        let search_edit = TextEdit::singleline(&mut self.query.text)
+            .desired_width(f32::INFINITY)
+            .font(egui::epaint::text::FontId::proportional(32.))
+            .hint_text(RichText::new("Поиск").size(32.))
+            .show(ui)
+            .response;
+
+        if search_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            self.search();
+        }
+    }
+
+    fn filters_ui(&mut self, ui: &mut Ui) {
+        if self.query.target == SearchTarget::Articles || self.query.target == SearchTarget::Tags {
+            ui.label(RichText::new("Сортировка").size(29.).strong());
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut self.query.sorting,
+                    ArticlesSearchSorting::Relevance,
+                    RichText::new("По релевантности").size(25.),
+                );
+                ui.selectable_value(
+                    &mut self.query.sorting,
+                    ArticlesSearchSorting::Date,
+                    RichText::new("По дате").size(25.),
+                );
+                ui.selectable_value(
+                    &mut self.query.sorting,
+                    ArticlesSearchSorting::Rating,
+                    RichText::new("По рейтингу").size(25.),
+                );
+            });
+
+            ui.add_space(10.);
+            ui.label(RichText::new("Период").size(29.).strong());
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.date_from)
+                        .desired_width(200.)
+                        .font(egui::epaint::text::FontId::proportional(25.))
+                        .hint_text(RichText::new("с ГГГГ-ММ-ДД").size(25.)),
+                );
+                ui.add(
+                    TextEdit::singleline(&mut self.date_to)
+                        .desired_width(200.)
+                        .font(egui::epaint::text::FontId::proportional(25.))
+                        .hint_text(RichText::new("по ГГГГ-ММ-ДД").size(25.)),
+                );
+            });
+
+            ui.add_space(10.);
+            ui.label(RichText::new("Порог рейтинга").size(29.).strong());
+            ui.horizontal_wrapped(|ui| {
+                ui.selectable_value(
+                    &mut self.query.min_score,
+                    None,
+                    RichText::new("Все").size(25.),
+                );
+                for threshold in RATING_THRESHOLDS {
+                    ui.selectable_value(
+                        &mut self.query.min_score,
+                        Some(threshold),
+                        RichText::new(format!("≥{}", threshold)).size(25.),
+                    );
+                }
+            });
+
+            ui.add_space(10.);
+            ui.label(RichText::new("Уровень сложности").size(29.).strong());
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut self.query.complexity,
+                    None,
+                    RichText::new("Все").size(25.),
+                );
+                ui.selectable_value(
+                    &mut self.query.complexity,
+                    Some(ComplexityFilter::Easy),
+                    RichText::new("Простой").size(25.),
+                );
+                ui.selectable_value(
+                    &mut self.query.complexity,
+                    Some(ComplexityFilter::Medium),
+                    RichText::new("Средний").size(25.),
+                );
+                ui.selectable_value(
+                    &mut self.query.complexity,
+                    Some(ComplexityFilter::Hard),
+                    RichText::new("Сложный").size(25.),
+                );
+            });
+
+            ui.add_space(10.);
+            ui.label(RichText::new("Хаб").size(29.).strong());
+            ui.add(
+                TextEdit::singleline(&mut self.hub)
+                    .desired_width(f32::INFINITY)
+                    .font(egui::epaint::text::FontId::proportional(25.))
+                    .hint_text(RichText::new("Алиас хаба, например rust").size(25.)),
+            );
+        } else {
+            ui.label(
+                RichText::new("Для этого раздела фильтров нет")
+                    .size(25.)
+                    .weak(),
+            );
+        }
+
+        ui.add_space(10.);
+        ui.horizontal(|ui| {
+            if ui.button(RichText::new("Сбросить").size(29.)).clicked() {
+                let target = self.query.target;
+                let text = std::mem::take(&mut self.query.text);
+                self.set_query(SearchQuery::new(text).target(target));
+            }
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("Применить").size(29.).strong())
+                    .clicked()
+                {
+                    self.search();
+                }
+            });
+        });
+    }
+
+    fn facets_ui(&mut self, ui: &mut Ui) {
+        let facets = *self.facets.read().unwrap();
+        let mut selected_target = None;
+
+        ui.horizontal_wrapped(|ui| {
+            for target in SearchTarget::ALL {
+                let text = match (facets, self.last_query.is_some()) {
+                    (Some(facets), true) => format!("{} ({})", target.title(), facets.get(target)),
+                    _ => target.title().to_string(),
+                };
+                if ui
+                    .selectable_label(self.query.target == target, RichText::new(text).size(25.))
+                    .clicked()
+                {
+                    selected_target = Some(target);
+                }
+            }
+        });
+
+        if let Some(target) = selected_target
+            && target != self.query.target
+        {
+            self.query.target = target;
+            self.search();
+        }
+    }
+
+    fn saved_searches_ui(&mut self, ui: &mut Ui) {
+        let mut selected = None;
+        let mut removed = None;
+
+        if !self.searches.saved.is_empty() {
+            ui.label(RichText::new("Сохранённые поиски").size(29.).strong());
+            for (index, saved) in self.searches.saved.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("🗑").size(25.)).clicked() {
+                        removed = Some(index);
+                    }
+                    let response = ui.vertical(|ui| {
+                        Label::new(RichText::new(saved.name.as_str()).size(25.).strong())
+                            .selectable(false)
+                            .ui(ui);
+                        Label::new(RichText::new(saved.query.description()).size(22.).weak())
+                            .selectable(false)
+                            .ui(ui);
+                    });
+                    if ui
+                        .interact(
+                            response.response.rect,
+                            ui.id().with(index),
+                            egui::Sense::click(),
+                        )
+                        .clicked()
+                    {
+                        selected = Some(saved.query.clone());
+                    }
+                });
+            }
+            ui.add_space(10.);
+        }
+
+        if !self.searches.recent.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Недавние").size(29.).strong());
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(RichText::new("Очистить").size(25.)).clicked() {
+                        self.searches.clear_recent();
+                        self.searches.save();
+                    }
+                });
+            });
+            for query in self.searches.recent.iter() {
+                let text = format!("{} · {}", query.text, query.description());
+                if ui
+                    .add(Button::new(RichText::new(text).size(25.)).frame(false))
+                    .clicked()
+                {
+                    selected = Some(query.clone());
+                }
+            }
+        }
+
+        if let Some(index) = removed {
+            self.searches.remove_saved(index);
+            self.searches.save();
+        }
+        if let Some(query) = selected {
+            self.set_query(query);
+            self.search();
+        }
+    }
+
+    fn save_search_ui(&mut self, ui: &mut Ui) {
+        let Some(query) = self.last_query.clone() else {
+            return;
+        };
+        if self.searches.is_saved(&query) {
+            ui.label(RichText::new("★ Поиск сохранён").size(25.).weak());
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.save_name)
+                    .desired_width(300.)
+                    .font(egui::epaint::text::FontId::proportional(25.))
+                    .hint_text(RichText::new(query.text.as_str()).size(25.)),
+            );
+            if ui
+                .button(RichText::new("☆ Сохранить поиск").size(25.))
+                .clicked()
+            {
+                let name = match self.save_name.trim() {
+                    "" => query.text.clone(),
+                    name => name.to_string(),
+                };
+                self.searches.save_search(name, query);
+                self.save_name.clear();
+                self.searches.save();
+            }
+        });
+    }
+
+    fn results_ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        let mut selected_hub: Option<Hub> = None;
+        let mut toggled_hub: Option<Hub> = None;
+        let mut selected_author: Option<String> = None;
+        let mut selected_company: Option<String> = None;
+        let mut selected_tag: Option<String> = None;
+
+        let mut scroll_area = ScrollArea::vertical()
+            .max_width(ui.available_width())
+            .hscroll(false)
+            .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysHidden);
+        if self.reset_scroll {
+            scroll_area = scroll_area.vertical_scroll_offset(0.);
+            self.reset_scroll = false;
+        }
+
+        scroll_area.show(ui, |ui| {
+            let found_tags = self.found_tags.read().unwrap();
+            if !found_tags.is_empty() {
+                ui.label(
+                    RichText::new("Теги из первой страницы найденных статей")
+                        .size(22.)
+                        .weak(),
+                );
+                ui.horizontal_wrapped(|ui| {
+                    for tag in found_tags.iter() {
+                        let tag_button = Button::new(
+                            RichText::new(format!("#{} ({})", tag.title, tag.articles_count))
+                                .size(25.),
+                        )
+                        .corner_radius(5.);
+                        if ui.add(tag_button).clicked() {
+                            selected_tag = Some(tag.title.clone());
+                        }
+                    }
+                });
+                ui.separator();
+            }
+
+            let results = self.results.read().unwrap();
+            match results.as_ref() {
+                None => {}
+                Some(Err(e)) => {
+                    ui.label(
+                        RichText::new(format!("Не удалось выполнить поиск: {}", e))
+                            .size(25.)
+                            .color(ui.visuals().error_fg_color),
+                    );
+                }
+                Some(Ok(page)) if page.results.is_empty() && page.filtered_out > 0 => {
+                    ui.label(
+                        RichText::new(
+                            "На этой странице нет статей, подходящих под фильтры. \
+                             Они могут найтись на следующих страницах",
+                        )
+                        .size(25.)
+                        .weak(),
+                    );
+                }
+                Some(Ok(page)) if page.results.is_empty() => {
+                    ui.label(RichText::new("Ничего не найдено").size(29.).weak());
+                }
+                Some(Ok(page)) => match &page.results {
+                    SearchResults::Articles(articles) => {
+                        ArticleListItem::list_ui(
+                            ui,
+                            self.habre_state.clone(),
+                            articles,
+                            view_stack,
+                        );
+                        // Filters by dates are applied to the loaded page, so it can be short.
+                        if page.filtered_out > 0 {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Скрыто фильтрами на этой странице: {}",
+                                    page.filtered_out
+                                ))
+                                .size(22.)
+                                .weak(),
+                            );
+                        }
+                    }
+                    SearchResults::Hubs(hubs) => {
+                        for (index, hub) in hubs.iter().enumerate() {
+                            if index > 0 {
+                                ui.separator();
+                            }
+                            let is_subscribed = self
+                                .habre_state
+                                .borrow()
+                                .subscriptions
+                                .borrow()
+                                .is_subscribed(&hub.alias);
+                            let (response, toggled) = HubUI::ui(ui, hub, is_subscribed);
+                            if toggled {
+                                toggled_hub = Some(hub.clone());
+                            } else if response.clicked() {
+                                selected_hub = Some(hub.clone());
+                            }
+                        }
+                    }
+                    SearchResults::Authors(users) => {
+                        for (index, user) in users.iter().enumerate() {
+                            if index > 0 {
+                                ui.separator();
+                            }
+                            let author_label =
+                                Label::new(RichText::new(user.alias.as_str()).size(29.).strong())
+                                    .selectable(false)
+                                    .sense(egui::Sense::click())
+                                    .ui(ui);
+                            if author_label.clicked() {
+                                selected_author = Some(user.alias.clone());
+                            }
+                            if let Some(fullname) = user.fullname.as_ref() {
+                                ui.label(RichText::new(fullname.as_str()).size(25.));
+                            }
+                            if let Some(speciality) = user.speciality.as_ref() {
+                                ui.label(RichText::new(speciality.as_str()).size(22.).weak());
+                            }
+                        }
+                    }
+                    SearchResults::Companies(companies) => {
+                        for (index, company) in companies.iter().enumerate() {
+                            if index > 0 {
+                                ui.separator();
+                            }
+                            if CompanyUI::ui(ui, company).clicked() {
+                                selected_company = Some(company.alias.clone());
+                            }
+                        }
+                    }
+                },
+            }
+        });
+
+        if let Some(alias) = selected_author {
+            let mut author_profile = AuthorProfile::new(self.habre_state.clone());
+            author_profile.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(author_profile)));
+        }
+
+        if let Some(tag) = selected_tag {
+            ArticlesList::push_tag_feed(self.habre_state.clone(), &tag, view_stack);
+        }
+
+        if let Some(alias) = selected_company {
+            let mut company_details = CompanyDetails::new(self.habre_state.clone());
+            company_details.load(&alias);
+            view_stack.push(Rc::new(RefCell::new(company_details)));
+        }
+
+        if let Some(hub) = toggled_hub {
+            let state = self.habre_state.borrow();
+            let mut subscriptions = state.subscriptions.borrow_mut();
+            subscriptions.toggle(&hub);
+            subscriptions.save();
+        }
+
+        if let Some(hub) = selected_hub {
+            self.habre_state.borrow_mut().selected_hub = Some(hub.clone());
+            if let Some(cb) = self.hub_selected_cb.as_mut() {
+                cb(&hub, view_stack);
+            }
+        }
+    }
+}
+
+impl UiView for SearchView {
+    fn ui(&mut self, ui: &mut Ui, view_stack: &mut ViewStack) {
+        Flex::vertical()
+            .justify(egui_flex::FlexJustify::SpaceBetween)
+            .grow_items(0.)
+            .h_full()
+            .w_full()
+            .show(ui, |f_ui| {
+                f_ui.add_flex(
+                    egui_flex::item(),
+                    Flex::vertical().align_items(egui_flex::FlexAlign::Start),
+                    |f_ui| {
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            ui.add(Label::new(RichText::new("Поиск").size(40.).strong()))
+                        });
+
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            egui_flex::Flex::horizontal()
+                                .align_items(egui_flex::FlexAlign::Center)
+                                .gap(egui::Vec2::new(10., 0.))
+                                .w_full()
+                                .show(ui, |flex_ui| {
+                                    flex_ui.add_ui(egui_flex::item().shrink().grow(1.), |ui| {
+                                        self.search_ui(ui);
+                                    });
+                                    flex_ui.add_ui(egui_flex::item().grow(2.), |ui| {
+                                        let button = Button::new(RichText::new("🔧").size(32.))
+                                            .corner_radius(8.);
+                                        let button = if self.query.has_filters() {
+                                            button.fill(ui.visuals().selection.bg_fill)
+                                        } else {
+                                            button
+                                        };
+                                        if ui.add(button).clicked() {
+                                            self.show_filters = !self.show_filters;
+                                        }
+                                    });
+                                });
+                        });
+
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            ui.vertical(|ui| {
+                                self.facets_ui(ui);
+                                if self.show_filters {
+                                    ui.separator();
+                                    self.filters_ui(ui);
+                                }
+                                if let Some(e) = self.query_error.as_ref() {
+                                    ui.label(
+                                        RichText::new(e.as_str())
+                                            .size(25.)
+                                            .color(ui.visuals().error_fg_color),
+                                    );
+                                }
+                                if let Some(e) = self.searches.save_error.as_ref() {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "Не удалось сохранить историю поиска: {}",
+                                            e
+                                        ))
+                                        .size(22.)
+                                        .color(ui.visuals().error_fg_color),
+                                    );
+                                }
+                                self.save_search_ui(ui);
+                            })
+                        });
+
+                        f_ui.add_ui(egui_flex::item(), |ui| ui.separator());
+                    },
+                );
+
+                if self.is_loading.load(Ordering::Relaxed) {
+                    f_ui.add(egui_flex::item(), Spinner::new().size(100.));
+                } else if self.last_query.is_none() {
+                    f_ui.add_ui(egui_flex::item().shrink(), |ui| {
+                        ScrollArea::vertical()
+                            .max_width(ui.available_width())
+                            .hscroll(false)
+                            .show(ui, |ui| self.saved_searches_ui(ui));
+                    });
+                } else {
+                    f_ui.add_ui(egui_flex::item().shrink(), |ui| {
+                        self.results_ui(ui, view_stack);
+                    });
+                }
+
+                if self.last_query.is_some() {
+                    f_ui.add_flex(egui_flex::item(), Flex::vertical().w_full(), |f_ui| {
+                        f_ui.add_ui(egui_flex::item(), |ui| {
+                            if Pager::new(
+                                &mut self.current_page,
+                                self.max_page.load(Ordering::Relaxed),
+                            )
+                            .ui(ui)
+                            .changed()
+                            {
+                                self.load_page();
+                            };
+                        });
+                    });
+                }
+            });
+    }
+}
@@ -3,6 +3,7 @@ use egui_theme_switch::ThemeSwitch;
 use serde::{Deserialize, Serialize};
 use toml;
 
+use crate::storage::content_filter::{ContentFilter, FilterKind};
 use crate::view_stack::UiView;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -10,13 +11,38 @@ pub struct SettingsData {
     pub font_size: f32,
     pub scale_factor: f32,
     pub dark_theme: bool,
+    /// Notify about new articles in the subscribed hubs and feeds.
+    #[serde(default)]
+    pub notify_new_articles: bool,
+    /// Minutes between the checks for new articles.
+    #[serde(default = "default_notifications_interval")]
+    pub notifications_interval: u32,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Minutes allowed between the checks, a hand edited file may have any number.
+const NOTIFICATIONS_INTERVALS: std::ops::RangeInclusive<u32> = 15..=360;
+
+fn default_notifications_interval() -> u32 {
+    60
+}
+
+/// Layout of `settings.toml`. The content filter is edited apart from the other
+/// settings, so it is not a part of `SettingsData`.
+#[derive(Deserialize, Serialize)]
+struct SettingsFile {
+    #[serde(flatten)]
+    data: SettingsData,
+    #[serde(default)]
+    content_filter: ContentFilter,
+}
+
+#[derive(Clone, Debug)]
 pub struct Settings {
     temp_data: SettingsData,
     saved_data: SettingsData,
     temp_theme: egui::ThemePreference,
+    content_filter: ContentFilter,
+    save_error: Option<String>,
 }
 
 impl Settings {
@@ -32,23 +58,64 @@ impl Settings {
         self.saved_data
     }
 
+    pub fn content_filter(&self) -> &ContentFilter {
+        &self.content_filter
+    }
+
+    pub fn save_error(&self) -> Option<&str> {
+        self.save_error.as_deref()
+    }
+
+    /// Changes the content filter and saves it right away.
+    pub fn update_content_filter<R>(&mut self, update: impl FnOnce(&mut ContentFilter) -> R) -> R {
+        let result = update(&mut self.content_filter);
+        self.save_error = self.save_to_file().err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save content filter: {}", e);
+        }
+        result
+    }
+
+    pub fn hide_author(&mut self, alias: &str) {
+        let _ = self.update_content_filter(|f| f.add_rule(FilterKind::Author, alias));
+    }
+
+    pub fn hide_company(&mut self, alias: &str) {
+        let _ = self.update_content_filter(|f| f.add_rule(FilterKind::Company, alias));
+    }
+
     fn save_settings(&mut self) {
         self.saved_data = self.temp_data;
-        self.save_to_file()
+        self.save_error = self.save_to_file().err();
+        if let Some(e) = self.save_error.as_ref() {
+            log::warn!("Failed to save settings: {}", e);
+        }
     }
 
-    fn save_to_file(&self) {
-        let ser_settings = toml::to_string(&self.saved_data).unwrap();
+    fn save_to_file(&self) -> Result<(), String> {
+        let settings_file = SettingsFile {
+            data: self.saved_data,
+            content_filter: self.content_filter.clone(),
+        };
+        let ser_settings = toml::to_string(&settings_file).map_err(|e| e.to_string())?;
         let settings_path = crate::storage::app_data_dir().join("settings.toml");
-        std::fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
-        std::fs::write(settings_path, ser_settings).unwrap();
+        crate::storage::write_atomic(&settings_path, ser_settings).map_err(|e| e.to_string())
     }
 
     pub fn read_from_file() -> Option<Self> {
         if let Ok(readed_data) =
             std::fs::read_to_string(crate::storage::app_data_dir().join("settings.toml"))
         {
-            if let Ok(settings_data) = toml::from_str::<SettingsData>(&readed_data) {
+            if let Ok(SettingsFile {
+                data: mut settings_data,
+                mut content_filter,
+            }) = toml::from_str::<SettingsFile>(&readed_data)
+            {
+                content_filter.compile();
+                settings_data.notifications_interval = settings_data.notifications_interval.clamp(
+                    *NOTIFICATIONS_INTERVALS.start(),
+                    *NOTIFICATIONS_INTERVALS.end(),
+                );
                 let theme = if settings_data.dark_theme {
                     egui::ThemePreference::Dark
                 } else {
@@ -58,6 +125,8 @@ impl Settings {
                     saved_data: settings_data,
                     temp_data: settings_data,
                     temp_theme: theme,
+                    content_filter,
+                    save_error: None,
                 })
             } else {
                 None
@@ -95,6 +164,21 @@ impl UiView for Settings {
                 .max_decimals(0),
         );
 
+        ui.checkbox(
+            &mut self.temp_data.notify_new_articles,
+            egui::RichText::new("Уведомлять о новых статьях").size(self.saved_data.font_size),
+        );
+        if self.temp_data.notify_new_articles {
+            ui.label(egui::RichText::new("Интервал проверки, мин").size(self.saved_data.font_size));
+            ui.add(
+                egui::Slider::new(
+                    &mut self.temp_data.notifications_interval,
+                    NOTIFICATIONS_INTERVALS,
+                )
+                .step_by(15.0),
+            );
+        }
+
         if ui
             .button(egui::RichText::new("Применить").size(self.saved_data.font_size))
             .clicked()
@@ -102,6 +186,14 @@ impl UiView for Settings {
             ui.ctx().set_theme(self.temp_theme);
             self.save_settings()
         }
+
+        if let Some(error) = self.save_error.as_ref() {
+            ui.label(
+                egui::RichText::new(format!("Не удалось сохранить настройки: {}", error))
+                    .size(self.saved_data.font_size)
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
     }
 }
 
@@ -114,11 +206,15 @@ impl Default for Settings {
             #[cfg(feature = "aurora")]
             scale_factor: 1.25,
             dark_theme: true,
+            notify_new_articles: false,
+            notifications_interval: default_notifications_interval(),
         };
         Self {
             temp_data: data,
             saved_data: data,
             temp_theme: egui::ThemePreference::System,
+            content_filter: ContentFilter::default(),
+            save_error: None,
         }
     }
 }